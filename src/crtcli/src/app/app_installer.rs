@@ -51,7 +51,6 @@ impl<'c> AppInstallerService<'c> {
             .into_result()?)
     }
 
-    #[allow(dead_code)]
     pub async fn install_app_from_file(
         &self,
         code: &str,
@@ -79,6 +78,24 @@ impl<'c> AppInstallerService<'c> {
             .into_result()?)
     }
 
+    pub async fn delete_package(&self, package_name: &str) -> Result<(), CrtClientError> {
+        let response = self
+            .0
+            .request(
+                Method::POST,
+                "0/ServiceModel/AppInstallerService.svc/DeletePackage",
+            )
+            .json(&json!(package_name))
+            .send_with_session(self.0)
+            .await?
+            .error_for_status()?;
+
+        Ok(response
+            .json::<StandardServiceResponse>()
+            .await?
+            .into_result()?)
+    }
+
     pub async fn load_packages_to_db<StrArr, Str>(
         &self,
         package_names: Option<StrArr>,
@@ -12,6 +12,8 @@ pub enum CrtCredentials {
         oauth_url: String,
         oauth_client_id: String,
         oauth_client_secret: String,
+        oauth_scope: Option<String>,
+        oauth_audience: Option<String>,
     },
 }
 
@@ -43,13 +45,40 @@ impl CrtCredentials {
                 .to_owned(),
             oauth_client_id: oauth_client_id.as_ref().to_owned(),
             oauth_client_secret: oauth_client_secret.as_ref().to_owned(),
+            oauth_scope: None,
+            oauth_audience: None,
         }
     }
 
+    /// Sets extra `scope` and `audience` parameters of the OAuth token request, ignored for basic credentials.
+    pub fn with_oauth_params(mut self, scope: Option<String>, audience: Option<String>) -> Self {
+        if let CrtCredentials::OAuth {
+            oauth_scope,
+            oauth_audience,
+            ..
+        } = &mut self
+        {
+            *oauth_scope = scope;
+            *oauth_audience = audience;
+        }
+
+        self
+    }
+
     pub fn url(&self) -> &str {
         match &self {
             CrtCredentials::Basic { url, .. } => url,
             CrtCredentials::OAuth { url, .. } => url,
         }
     }
+
+    /// Returns the username or the OAuth client ID.
+    pub fn user(&self) -> &str {
+        match &self {
+            CrtCredentials::Basic { username, .. } => username,
+            CrtCredentials::OAuth {
+                oauth_client_id, ..
+            } => oauth_client_id,
+        }
+    }
 }
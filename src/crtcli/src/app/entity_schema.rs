@@ -0,0 +1,144 @@
+use crate::app::{CrtClient, CrtClientError, CrtRequestBuilderExt, StandardServiceResponse};
+use reqwest::Method;
+use serde::{Deserialize, Deserializer};
+use serde_json::json;
+
+pub struct EntitySchemaService<'c>(&'c CrtClient);
+
+impl<'c> EntitySchemaService<'c> {
+    pub fn new(client: &'c CrtClient) -> Self {
+        Self(client)
+    }
+
+    pub async fn get_runtime_entity_schema(
+        &self,
+        schema_name: &str,
+    ) -> Result<RuntimeEntitySchema, CrtClientError> {
+        let response = self
+            .0
+            .request(
+                Method::POST,
+                "0/DataService/json/SyncReply/RuntimeEntitySchemaRequest",
+            )
+            .json(&json!({
+                "Name": schema_name
+            }))
+            .send_with_session(self.0)
+            .await?
+            .error_for_status()?;
+
+        response
+            .json::<RuntimeEntitySchemaResponse>()
+            .await?
+            .into_result()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RuntimeEntitySchemaResponse {
+    #[serde(flatten)]
+    base: StandardServiceResponse,
+
+    schema: Option<RuntimeEntitySchema>,
+}
+
+impl RuntimeEntitySchemaResponse {
+    fn into_result(self) -> Result<RuntimeEntitySchema, CrtClientError> {
+        self.base.into_result()?;
+
+        Ok(self
+            .schema
+            .expect("RuntimeEntitySchemaRequest response success, but schema is not received"))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RuntimeEntitySchema {
+    #[serde(rename = "uId")]
+    pub uid: String,
+
+    pub name: String,
+
+    #[serde(deserialize_with = "deserialize_runtime_entity_schema_columns")]
+    pub columns: Vec<RuntimeEntitySchemaColumn>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RuntimeEntitySchemaColumn {
+    #[serde(rename = "uId")]
+    pub uid: String,
+
+    pub name: String,
+
+    #[serde(rename = "dataValueType")]
+    pub data_value_type: u32,
+}
+
+impl RuntimeEntitySchema {
+    pub fn find_column(&self, column_name: &str) -> Option<&RuntimeEntitySchemaColumn> {
+        self.columns
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(column_name))
+    }
+}
+
+impl RuntimeEntitySchemaColumn {
+    /// Returns the physical database column name, e.g. `AccountId` for the `Account` lookup column.
+    pub fn db_column_name(&self) -> String {
+        match self.value_kind() {
+            DataValueKind::Lookup => format!("{}Id", self.name),
+            _ => self.name.clone(),
+        }
+    }
+
+    pub fn value_kind(&self) -> DataValueKind {
+        DataValueKind::from_data_value_type(self.data_value_type)
+    }
+}
+
+/// Simplified classification of Creatio `DataValueType` values
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DataValueKind {
+    Guid,
+    Lookup,
+    Text,
+    Integer,
+    Float,
+    Boolean,
+    DateTime,
+    Unsupported,
+}
+
+impl DataValueKind {
+    pub fn from_data_value_type(data_value_type: u32) -> Self {
+        match data_value_type {
+            0 => DataValueKind::Guid,
+            10 | 16 => DataValueKind::Lookup,
+            1 | 18 | 23 | 24 | 27..=30 | 42..=45 => DataValueKind::Text,
+            4 | 11 => DataValueKind::Integer,
+            5 | 6 | 31..=34 | 40 => DataValueKind::Float,
+            12 => DataValueKind::Boolean,
+            7..=9 => DataValueKind::DateTime,
+            _ => DataValueKind::Unsupported,
+        }
+    }
+}
+
+fn deserialize_runtime_entity_schema_columns<'de, D>(
+    deserializer: D,
+) -> Result<Vec<RuntimeEntitySchemaColumn>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    // Columns are returned as a collection object: { "Items": { "<ColumnUId>": { ... } } }
+    #[derive(Deserialize)]
+    struct ColumnsCollection {
+        #[serde(rename = "Items")]
+        items: indexmap::IndexMap<String, RuntimeEntitySchemaColumn>,
+    }
+
+    Ok(ColumnsCollection::deserialize(deserializer)?
+        .items
+        .into_values()
+        .collect())
+}
@@ -0,0 +1,452 @@
+use regex::Regex;
+use serde::Serialize;
+use std::sync::LazyLock;
+
+static LOG_LINE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)^(?<timestamp>\d{4}-\d{2}-\d{2}[ T]\d{2}:\d{2}:\d{2}(?:[.,]\d+)?)\s*(?:\[?(?<level>info|debug|trace|warn|warning|error|fatal)\]?(?::|\s|$)\s*)?(?<text>.*)$",
+    )
+    .expect("failed to compile regex for install log line")
+});
+
+/// Error level marker at the beginning of the message (e.g. `Error:`, `[ERROR]`)
+/// or the .NET exception header (e.g. `System.InvalidOperationException: ...`).
+static ERROR_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^\s*(?:\[?(?:error|fatal)\]?(?::|\s|$)|[\w.]*exception:)")
+        .expect("failed to compile regex for install log error")
+});
+
+/// Warning level marker at the beginning of the message (e.g. `Warning:`, `[WARN]`).
+static WARNING_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^\s*\[?warn(?:ing)?\]?(?::|\s|$)")
+        .expect("failed to compile regex for install log warning")
+});
+
+/// Data binding phrases of the Creatio installation log, e.g. `Data binding "UsrCities" applied`,
+/// `Schema data "UsrCities" installed` or `Installing data binding "UsrCities"`.
+static DATA_BINDING_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?i)\b(?:(?:data binding|schema data|data of schema|data)\s+(?:"[^"]+"|'[^']+'|«[^»]+»)\s+(?:(?:was|has been|is)\s+)?(?:installed|applied)\b|(?:installing|applying)\s+(?:data binding|schema data|data of schema|data)\s+["'«])"#,
+    )
+    .expect("failed to compile regex for install log data binding")
+});
+
+static QUOTED_NAME_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#""([^"]+)"|'([^']+)'|«([^»]+)»"#)
+        .expect("failed to compile regex for install log quoted name")
+});
+
+/// Single typed event parsed from the package installation log line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InstallLogEvent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+
+    #[serde(flatten)]
+    pub kind: InstallLogEventKind,
+
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InstallLogEventKind {
+    PackageStarted {
+        name: Option<String>,
+    },
+    PackageFinished {
+        name: Option<String>,
+    },
+    SchemaInstalled {
+        name: Option<String>,
+    },
+    SqlScriptExecuted {
+        name: Option<String>,
+    },
+    DataBindingApplied {
+        name: Option<String>,
+    },
+    Warning,
+    Error,
+    /// Continuation of the previous warning or error, e.g. exception stack trace line
+    Detail,
+    Info,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct InstallLogSummary {
+    pub packages: usize,
+    pub schemas: usize,
+    pub sql_scripts: usize,
+    pub data_bindings: usize,
+    pub warnings: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+impl InstallLogSummary {
+    fn record(&mut self, event: &InstallLogEvent) {
+        match event.kind {
+            InstallLogEventKind::PackageFinished { .. } => self.packages += 1,
+            InstallLogEventKind::SchemaInstalled { .. } => self.schemas += 1,
+            InstallLogEventKind::SqlScriptExecuted { .. } => self.sql_scripts += 1,
+            InstallLogEventKind::DataBindingApplied { .. } => self.data_bindings += 1,
+            InstallLogEventKind::Warning => self.warnings.push(event.message.clone()),
+            InstallLogEventKind::Error => self.errors.push(event.message.clone()),
+            _ => {}
+        }
+    }
+}
+
+/// Incremental parser of the package installation log.
+///
+/// Accepts raw log chunks (as they are received from [`crate::app::InstallLogWatcherEvent::Append`])
+/// and produces typed events for every complete line.
+#[derive(Debug, Default)]
+pub struct InstallLogParser {
+    pending_line: String,
+    timestamps_seen: bool,
+    last_kind: Option<InstallLogEventKind>,
+    summary: InstallLogSummary,
+}
+
+impl InstallLogParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn summary(&self) -> &InstallLogSummary {
+        &self.summary
+    }
+
+    pub fn feed(&mut self, text: &str) -> Vec<InstallLogEvent> {
+        self.pending_line.push_str(text);
+
+        let Some(last_newline) = self.pending_line.rfind('\n') else {
+            return vec![];
+        };
+
+        let rest = self.pending_line.split_off(last_newline + 1);
+        let complete = std::mem::replace(&mut self.pending_line, rest);
+
+        complete
+            .lines()
+            .filter_map(|line| self.parse_line(line))
+            .collect()
+    }
+
+    /// Parses the last line which is not terminated by a new line character.
+    pub fn finish(&mut self) -> Vec<InstallLogEvent> {
+        let line = std::mem::take(&mut self.pending_line);
+
+        self.parse_line(&line).into_iter().collect()
+    }
+
+    fn parse_line(&mut self, line: &str) -> Option<InstallLogEvent> {
+        let line = line.trim_end_matches('\r');
+
+        if line.trim().is_empty() {
+            return None;
+        }
+
+        let event = match LOG_LINE_REGEX.captures(line) {
+            Some(captures) => {
+                self.timestamps_seen = true;
+
+                let text = captures.name("text").map_or("", |m| m.as_str());
+                let level = captures.name("level").map(|m| m.as_str());
+
+                InstallLogEvent {
+                    timestamp: captures.name("timestamp").map(|m| m.as_str().to_owned()),
+                    kind: classify(text, level),
+                    message: text.to_owned(),
+                }
+            }
+            None => {
+                let is_continuation = self.timestamps_seen
+                    && matches!(
+                        self.last_kind,
+                        Some(
+                            InstallLogEventKind::Error
+                                | InstallLogEventKind::Warning
+                                | InstallLogEventKind::Detail
+                        )
+                    );
+
+                InstallLogEvent {
+                    timestamp: None,
+                    kind: match is_continuation {
+                        true => InstallLogEventKind::Detail,
+                        false => classify(line, None),
+                    },
+                    message: line.to_owned(),
+                }
+            }
+        };
+
+        self.last_kind = Some(event.kind.clone());
+        self.summary.record(&event);
+
+        Some(event)
+    }
+}
+
+fn classify(text: &str, level: Option<&str>) -> InstallLogEventKind {
+    let level = level.map(|l| l.to_ascii_lowercase());
+
+    match level.as_deref() {
+        Some("error" | "fatal") => return InstallLogEventKind::Error,
+        Some("warn" | "warning") => return InstallLogEventKind::Warning,
+        _ => {}
+    }
+
+    if ERROR_REGEX.is_match(text) {
+        return InstallLogEventKind::Error;
+    }
+
+    if WARNING_REGEX.is_match(text) {
+        return InstallLogEventKind::Warning;
+    }
+
+    let lower = text.to_lowercase();
+    let contains_any = |words: &[&str]| words.iter().any(|w| lower.contains(w));
+
+    let is_done = contains_any(&[
+        "installed",
+        "executed",
+        "applied",
+        "saved",
+        "updated",
+        "finished",
+        "completed",
+        "success",
+    ]);
+
+    if contains_any(&["sql script", "sqlscript"]) {
+        if is_done || contains_any(&["execut", "install"]) {
+            return InstallLogEventKind::SqlScriptExecuted {
+                name: extract_name(text, &["script"]),
+            };
+        }
+    } else if DATA_BINDING_REGEX.is_match(text) {
+        return InstallLogEventKind::DataBindingApplied {
+            name: extract_name(text, &["binding", "data"]),
+        };
+    } else if lower.contains("schema") {
+        if is_done || lower.contains("install") {
+            return InstallLogEventKind::SchemaInstalled {
+                name: extract_name(text, &["schema"]),
+            };
+        }
+    } else if lower.contains("package") {
+        if contains_any(&["installed", "finished", "completed", "success"]) {
+            return InstallLogEventKind::PackageFinished {
+                name: extract_name(text, &["package"]),
+            };
+        }
+
+        if contains_any(&["start", "begin", "installing", "loading"]) {
+            return InstallLogEventKind::PackageStarted {
+                name: extract_name(text, &["package"]),
+            };
+        }
+    }
+
+    InstallLogEventKind::Info
+}
+
+/// Extracts the object name: the first quoted value or the word after one of the keywords.
+fn extract_name(text: &str, keywords: &[&str]) -> Option<String> {
+    if let Some(captures) = QUOTED_NAME_REGEX.captures(text) {
+        return captures
+            .iter()
+            .skip(1)
+            .flatten()
+            .next()
+            .map(|m| m.as_str().to_owned());
+    }
+
+    let words = text.split_whitespace().collect::<Vec<_>>();
+
+    words
+        .iter()
+        .position(|w| keywords.iter().any(|k| w.eq_ignore_ascii_case(k)))
+        .and_then(|i| words.get(i + 1))
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric() && c != '_'))
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_install_log_events() {
+        let mut parser = InstallLogParser::new();
+
+        let mut events = parser.feed(
+            "2024-05-01 10:00:00,100 Package \"UsrPackage\" installation started\n\
+             2024-05-01 10:00:01,200 Schema \"UsrEntity\" installed\n\
+             2024-05-01 10:00:02,300 SQL script \"UsrScript\" executed\n\
+             2024-05-01 10:00:03,",
+        );
+
+        events.extend(parser.feed(
+            "400 Data binding \"UsrCities\" applied\n\
+             2024-05-01 10:00:04,500 Warning: schema UsrOld is obsolete\n\
+             2024-05-01 10:00:05,600 Error: Failed to install schema \"UsrBroken\"\n\
+             \x20  at Terrasoft.Core.Packages.PackageInstaller.Install()\n\
+             2024-05-01 10:00:06,700 Package \"UsrPackage\" installed",
+        ));
+
+        events.extend(parser.finish());
+
+        let kinds = events.iter().map(|e| e.kind.clone()).collect::<Vec<_>>();
+        let name = |n: &str| Some(n.to_owned());
+
+        assert_eq!(
+            kinds,
+            vec![
+                InstallLogEventKind::PackageStarted {
+                    name: name("UsrPackage")
+                },
+                InstallLogEventKind::SchemaInstalled {
+                    name: name("UsrEntity")
+                },
+                InstallLogEventKind::SqlScriptExecuted {
+                    name: name("UsrScript")
+                },
+                InstallLogEventKind::DataBindingApplied {
+                    name: name("UsrCities")
+                },
+                InstallLogEventKind::Warning,
+                InstallLogEventKind::Error,
+                InstallLogEventKind::Detail,
+                InstallLogEventKind::PackageFinished {
+                    name: name("UsrPackage")
+                },
+            ]
+        );
+
+        assert_eq!(
+            events[0].timestamp.as_deref(),
+            Some("2024-05-01 10:00:00,100")
+        );
+
+        let summary = parser.summary();
+
+        assert_eq!(
+            (
+                summary.packages,
+                summary.schemas,
+                summary.sql_scripts,
+                summary.data_bindings,
+                summary.warnings.len(),
+                summary.errors.len()
+            ),
+            (1, 1, 1, 1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn parse_data_binding_phrases_only() {
+        let mut parser = InstallLogParser::new();
+
+        let mut events = parser.feed(
+            "Schema data \"UsrCities\" installed\n\
+             Installing data binding \"UsrCountries\"\n\
+             Customer data saved to \"UsrCache\"\n\
+             Lookup data updated\n",
+        );
+
+        events.extend(parser.finish());
+
+        let kinds = events.iter().map(|e| e.kind.clone()).collect::<Vec<_>>();
+
+        assert_eq!(
+            kinds,
+            vec![
+                InstallLogEventKind::DataBindingApplied {
+                    name: Some("UsrCities".to_owned())
+                },
+                InstallLogEventKind::DataBindingApplied {
+                    name: Some("UsrCountries".to_owned())
+                },
+                InstallLogEventKind::Info,
+                InstallLogEventKind::Info,
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize_install_log_event() {
+        let event = InstallLogEvent {
+            timestamp: None,
+            kind: InstallLogEventKind::SchemaInstalled {
+                name: Some("UsrEntity".to_owned()),
+            },
+            message: "Schema \"UsrEntity\" installed".to_owned(),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"type":"schema_installed","name":"UsrEntity","message":"Schema \"UsrEntity\" installed"}"#
+        );
+    }
+
+    #[test]
+    fn parse_creatio_install_log_levels() {
+        let mut parser = InstallLogParser::new();
+
+        let mut events = parser.feed(
+            "2024-05-01 10:00:00,100 Installing package \"UsrPackage\"\n\
+             2024-05-01 10:00:01,200 Schema \"UsrErrorLog\" installed\n\
+             2024-05-01 10:00:02,300 Data binding \"UsrFailedPayments\" applied\n\
+             2024-05-01 10:00:03,400 Compilation finished: 0 error(s), 2 warning(s)\n\
+             2024-05-01 10:00:04,500 WARN Schema \"UsrOld\" uses obsolete column\n\
+             2024-05-01 10:00:05,600 ERROR Install of schema \"UsrBroken\" failed\n\
+             Terrasoft.Common.ItemNotFoundException: Item \"UsrLookup\" not found\n\
+             \x20  at Terrasoft.Core.Entities.EntitySchemaManager.GetInstanceByName(String name)\n\
+             2024-05-01 10:00:06,700 Terrasoft.Core.DB.DBException: Column \"UsrCode\" does not exist\n",
+        );
+
+        events.extend(parser.finish());
+
+        let kinds = events.iter().map(|e| e.kind.clone()).collect::<Vec<_>>();
+        let name = |n: &str| Some(n.to_owned());
+
+        assert_eq!(
+            kinds,
+            vec![
+                InstallLogEventKind::PackageStarted {
+                    name: name("UsrPackage")
+                },
+                InstallLogEventKind::SchemaInstalled {
+                    name: name("UsrErrorLog")
+                },
+                InstallLogEventKind::DataBindingApplied {
+                    name: name("UsrFailedPayments")
+                },
+                InstallLogEventKind::Info,
+                InstallLogEventKind::Warning,
+                InstallLogEventKind::Error,
+                InstallLogEventKind::Detail,
+                InstallLogEventKind::Detail,
+                InstallLogEventKind::Error,
+            ]
+        );
+
+        assert_eq!(
+            parser.summary().errors,
+            vec![
+                "Install of schema \"UsrBroken\" failed",
+                "Terrasoft.Core.DB.DBException: Column \"UsrCode\" does not exist",
+            ]
+        );
+    }
+}
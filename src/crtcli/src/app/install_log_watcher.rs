@@ -64,6 +64,7 @@ impl InstallLogWatcherBuilder {
                 .with_new_memory_cache()
                 .use_net_framework_mode(crt_client.is_net_framework())
                 .danger_accept_invalid_certs(crt_client.is_insecure())
+                .with_connection_options(crt_client.connection_options().clone())
                 .build()?,
         );
 
@@ -12,6 +12,10 @@ pub use credentials::*;
 
 pub mod workspace_explorer;
 
+pub mod entity_schema;
+
+pub mod source_code_schema;
+
 pub mod package_installer;
 
 pub use app_installer::{
@@ -21,9 +25,16 @@ mod app_installer;
 
 mod package;
 
+mod proxy;
+
 mod install_log_watcher;
 pub use install_log_watcher::*;
 
+mod install_log_parser;
+pub use install_log_parser::*;
+
+pub mod redis;
+
 pub mod session_cache;
 
 pub mod sql;
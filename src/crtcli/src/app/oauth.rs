@@ -2,6 +2,7 @@ use crate::app::CrtSessionOAuth;
 use reqwest::Client;
 use serde::Deserialize;
 use thiserror::Error;
+use time::OffsetDateTime;
 
 pub struct OAuthService<'c> {
     reqwest_client: &'c Client,
@@ -20,15 +21,30 @@ impl<'c> OAuthService<'c> {
         &self,
         client_id: String,
         client_secret: String,
+        scope: Option<String>,
+        audience: Option<String>,
     ) -> Result<CrtSessionOAuth, OAuthLoginError> {
+        let mut form = vec![
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+            ("grant_type", "client_credentials".to_owned()),
+        ];
+
+        if let Some(scope) = scope {
+            form.push(("scope", scope));
+        }
+
+        if let Some(audience) = audience {
+            form.push(("audience", audience));
+        }
+
+        // Taken before the request, so the token is considered expired slightly earlier rather than later
+        let issued_at = OffsetDateTime::now_utc();
+
         let response = self
             .reqwest_client
             .post(format!("{}/connect/token", self.oauth_base_url))
-            .form(&[
-                ("client_id", client_id),
-                ("client_secret", client_secret),
-                ("grant_type", "client_credentials".to_owned()),
-            ])
+            .form(&form)
             .send()
             .await?;
 
@@ -41,11 +57,15 @@ impl<'c> OAuthService<'c> {
             return Err(OAuthLoginError::Remote(error.error));
         }
 
-        response
+        let mut session = response
             .error_for_status()?
             .json::<CrtSessionOAuth>()
             .await
-            .map_err(OAuthLoginError::ResponseRead)
+            .map_err(OAuthLoginError::ResponseRead)?;
+
+        session.set_issued_at(issued_at);
+
+        Ok(session)
     }
 }
 
@@ -3,8 +3,8 @@ use crate::app::{CrtRequestBuilderExt, StandardServiceResponse};
 use futures::{FutureExt, TryStreamExt};
 use reqwest::Method;
 use reqwest::header::HeaderMap;
-use serde::Serialize;
-use serde_json::json;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
 use std::borrow::Cow;
 use tokio::io::AsyncReadExt;
 use tokio_util::bytes::Bytes;
@@ -15,6 +15,14 @@ pub const UPLOAD_PACKAGE_CHUNK_SIZE_ENV_KEY: &str = "CRTCLI_APP_PKG_UPLOAD_CHUNK
 
 pub struct PackageInstallerService<'c>(&'c CrtClient);
 
+/// Returns the chunk size of the package upload, `0` means that the package is uploaded in a single request.
+pub fn upload_package_chunk_size() -> usize {
+    std::env::var(UPLOAD_PACKAGE_CHUNK_SIZE_ENV_KEY)
+        .ok()
+        .and_then(|x| x.parse::<usize>().ok())
+        .unwrap_or(UPLOAD_PACKAGE_CHUNK_SIZE_DEFAULT)
+}
+
 impl<'c> PackageInstallerService<'c> {
     pub fn new(client: &'c CrtClient) -> Self {
         Self(client)
@@ -38,6 +46,18 @@ impl<'c> PackageInstallerService<'c> {
         &self,
         package_names: StrArr,
     ) -> Result<impl AsyncReadExt + 'static, CrtClientError>
+    where
+        StrArr: AsRef<[Str]> + Serialize,
+        Str: AsRef<str>,
+    {
+        Ok(self.get_zip_packages_with_length(package_names).await?.0)
+    }
+
+    /// Same as [`Self::get_zip_packages`], but also returns the archive length if the server reports it.
+    pub async fn get_zip_packages_with_length<StrArr, Str>(
+        &self,
+        package_names: StrArr,
+    ) -> Result<(impl AsyncReadExt + Unpin + 'static, Option<u64>), CrtClientError>
     where
         StrArr: AsRef<[Str]> + Serialize,
         Str: AsRef<str>,
@@ -53,8 +73,11 @@ impl<'c> PackageInstallerService<'c> {
             .await?
             .error_for_status()?;
 
-        Ok(StreamReader::new(
-            response.bytes_stream().map_err(std::io::Error::other),
+        let content_length = response.content_length();
+
+        Ok((
+            StreamReader::new(response.bytes_stream().map_err(std::io::Error::other)),
+            content_length,
         ))
     }
 
@@ -98,7 +121,7 @@ impl<'c> PackageInstallerService<'c> {
             .into_result()?)
     }
 
-    async fn upload_package_chunk(
+    pub async fn upload_package_chunk(
         &self,
         package_filename: &str,
         package_bytes_chunk: Bytes,
@@ -138,14 +161,9 @@ impl<'c> PackageInstallerService<'c> {
         package_bytes: impl Into<Bytes>,
         package_filename: impl Into<Cow<'static, str>>,
     ) -> UploadPackageChunkIter<'_> {
-        let chunk_size = std::env::var(UPLOAD_PACKAGE_CHUNK_SIZE_ENV_KEY)
-            .ok()
-            .and_then(|x| x.parse::<usize>().ok())
-            .unwrap_or(UPLOAD_PACKAGE_CHUNK_SIZE_DEFAULT);
-
         UploadPackageChunkIter {
             package_installer_service: self,
-            chunk_size,
+            chunk_size: upload_package_chunk_size(),
             current_offset: 0,
             package_bytes: package_bytes.into(),
             package_filename: package_filename.into(),
@@ -169,12 +187,11 @@ impl<'c> PackageInstallerService<'c> {
             .into_result()?)
     }
 
-    #[allow(dead_code)]
     pub async fn validate_package(
         &self,
         code: &str,
         package_filename: &str,
-    ) -> Result<(), CrtClientError> {
+    ) -> Result<PackageValidationResult, CrtClientError> {
         let response = self
             .0
             .request(
@@ -189,13 +206,161 @@ impl<'c> PackageInstallerService<'c> {
             .await?
             .error_for_status()?;
 
-        Ok(response
-            .json::<StandardServiceResponse>()
-            .await?
-            .into_result()?)
+        Ok(PackageValidationResult::from_response(
+            response.json::<Value>().await?,
+        ))
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PackageValidationResult {
+    pub success: bool,
+    pub problems: Vec<PackageValidationProblem>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PackageValidationProblem {
+    pub level: PackageValidationProblemLevel,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+
+    pub message: String,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PackageValidationProblemLevel {
+    Error,
+    Warning,
+    Info,
+}
+
+impl PackageValidationResult {
+    pub fn errors_count(&self) -> usize {
+        self.problems
+            .iter()
+            .filter(|p| p.level == PackageValidationProblemLevel::Error)
+            .count()
+    }
+
+    pub fn warnings_count(&self) -> usize {
+        self.problems
+            .iter()
+            .filter(|p| p.level == PackageValidationProblemLevel::Warning)
+            .count()
+    }
+
+    /// Collects problems from the documented fields of the validation response:
+    /// `errorInfo` and `validationResults[].messages[]`.
+    ///
+    /// Messages are errors or warnings only if their `type` says so, other messages are informational.
+    fn from_response(response: Value) -> Self {
+        let response = match serde_json::from_value::<PackageValidationResponse>(response) {
+            Ok(response) => response,
+            Err(err) => {
+                return Self {
+                    success: false,
+                    problems: vec![PackageValidationProblem {
+                        level: PackageValidationProblemLevel::Error,
+                        package: None,
+                        message: format!("unexpected validation response: {err}"),
+                    }],
+                };
+            }
+        };
+
+        let mut problems = vec![];
+
+        if let Some(error_info) = response.error_info {
+            problems.push(PackageValidationProblem {
+                level: PackageValidationProblemLevel::Error,
+                package: None,
+                message: error_info
+                    .message
+                    .filter(|x| !x.is_empty())
+                    .unwrap_or_else(|| "unknown validation error".to_owned()),
+            });
+        }
+
+        for result in response.validation_results {
+            for message in result.messages {
+                let level = match message.message_type.as_deref() {
+                    Some(t) if t.eq_ignore_ascii_case("error") => {
+                        PackageValidationProblemLevel::Error
+                    }
+                    Some(t) if t.eq_ignore_ascii_case("warning") => {
+                        PackageValidationProblemLevel::Warning
+                    }
+                    _ => PackageValidationProblemLevel::Info,
+                };
+
+                problems.push(PackageValidationProblem {
+                    level,
+                    package: result.package_name.clone(),
+                    message: message.message,
+                });
+            }
+        }
+
+        // Rejected package must have at least one error, even if server reported only warnings
+        if !response.success
+            && problems
+                .iter()
+                .all(|p| p.level != PackageValidationProblemLevel::Error)
+        {
+            problems.push(PackageValidationProblem {
+                level: PackageValidationProblemLevel::Error,
+                package: None,
+                message: "package validation failed".to_owned(),
+            });
+        }
+
+        Self {
+            success: response.success,
+            problems,
+        }
     }
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PackageValidationResponse {
+    #[serde(default = "default_validation_success")]
+    success: bool,
+
+    error_info: Option<PackageValidationErrorInfo>,
+
+    #[serde(default)]
+    validation_results: Vec<PackageValidationResultItem>,
+}
+
+fn default_validation_success() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+struct PackageValidationErrorInfo {
+    message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PackageValidationResultItem {
+    package_name: Option<String>,
+
+    #[serde(default)]
+    messages: Vec<PackageValidationMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackageValidationMessage {
+    #[serde(rename = "type")]
+    message_type: Option<String>,
+
+    message: String,
+}
+
 pub struct UploadPackageChunkIter<'a> {
     package_installer_service: &'a PackageInstallerService<'a>,
     package_filename: Cow<'static, str>,
@@ -242,3 +407,83 @@ impl UploadPackageChunkIter<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn package_validation_result_from_response() {
+        let result = PackageValidationResult::from_response(json!({
+            "success": false,
+            "errorInfo": null,
+            "validationResults": [
+                {
+                    "packageName": "UsrPackage",
+                    "messages": [
+                        { "type": "Error", "message": "Dependency CrtMissing is not installed" },
+                        { "type": "Warning", "message": "Package version is lower than installed" }
+                    ]
+                }
+            ]
+        }));
+
+        assert!(!result.success);
+        assert_eq!(result.errors_count(), 1);
+        assert_eq!(result.warnings_count(), 1);
+        assert_eq!(result.problems[0].package.as_deref(), Some("UsrPackage"));
+    }
+
+    #[test]
+    fn package_validation_ignores_undocumented_fields() {
+        let result = PackageValidationResult::from_response(json!({
+            "success": true,
+            "errorInfo": null,
+            "code": "UsrPackage",
+            "details": { "description": "Validation finished", "text": "ok" },
+            "validationResults": [
+                {
+                    "packageName": "UsrPackage",
+                    "code": "UsrOther",
+                    "messages": [
+                        { "message": "Package contains 10 schemas" },
+                        { "type": "Information", "message": "Package is signed" }
+                    ]
+                }
+            ]
+        }));
+
+        assert!(result.success);
+        assert_eq!(result.errors_count(), 0);
+        assert_eq!(result.warnings_count(), 0);
+        assert_eq!(result.problems.len(), 2);
+        assert!(
+            result
+                .problems
+                .iter()
+                .all(|p| p.level == PackageValidationProblemLevel::Info
+                    && p.package.as_deref() == Some("UsrPackage"))
+        );
+    }
+
+    #[test]
+    fn package_validation_failure_with_warnings_only_is_error() {
+        let result = PackageValidationResult::from_response(json!({
+            "success": false,
+            "validationResults": [
+                {
+                    "packageName": "UsrPackage",
+                    "messages": [
+                        { "type": "Warning", "message": "Package version is lower than installed" },
+                        { "message": "Package contains 10 schemas" }
+                    ]
+                }
+            ]
+        }));
+
+        assert!(!result.success);
+        assert_eq!(result.errors_count(), 1);
+        assert_eq!(result.warnings_count(), 1);
+        assert_eq!(result.problems.len(), 3);
+    }
+}
@@ -0,0 +1,204 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// Proxy response headers larger than this are treated as a protocol error.
+const MAX_RESPONSE_HEAD_LEN: usize = 16 * 1024;
+
+#[derive(Debug, Error)]
+pub enum ProxyConnectError {
+    #[error("proxy io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("proxy url {0} has no host")]
+    MissingHost(String),
+
+    #[error("proxy rejected CONNECT request: {0}")]
+    Rejected(String),
+
+    #[error("invalid proxy response: {0}")]
+    InvalidResponse(String),
+}
+
+/// Opens a TCP connection to the target host through the HTTP proxy using the CONNECT method.
+///
+/// Used for websocket connections, reqwest requests use the proxy on their own.
+pub async fn connect_via_http_proxy(
+    proxy: &reqwest::Url,
+    host: &str,
+    port: u16,
+) -> Result<TcpStream, ProxyConnectError> {
+    let proxy_host = proxy
+        .host_str()
+        .ok_or_else(|| ProxyConnectError::MissingHost(proxy.to_string()))?;
+    let proxy_port = proxy.port_or_known_default().unwrap_or(80);
+
+    let mut stream = TcpStream::connect((proxy_host, proxy_port)).await?;
+
+    let credentials = match proxy.username() {
+        "" => None,
+        username => Some((
+            urlencoding::decode(username)
+                .map(|x| x.into_owned())
+                .unwrap_or_else(|_| username.to_owned()),
+            proxy
+                .password()
+                .map(|password| {
+                    urlencoding::decode(password)
+                        .map(|x| x.into_owned())
+                        .unwrap_or_else(|_| password.to_owned())
+                })
+                .unwrap_or_default(),
+        )),
+    };
+
+    http_connect_handshake(
+        &mut stream,
+        host,
+        port,
+        credentials
+            .as_ref()
+            .map(|(username, password)| (username.as_str(), password.as_str())),
+    )
+    .await?;
+
+    Ok(stream)
+}
+
+async fn http_connect_handshake<S>(
+    stream: &mut S,
+    host: &str,
+    port: u16,
+    credentials: Option<(&str, &str)>,
+) -> Result<(), ProxyConnectError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let authority = match host.contains(':') {
+        true => format!("[{host}]:{port}"),
+        false => format!("{host}:{port}"),
+    };
+
+    let mut request = format!("CONNECT {authority} HTTP/1.1\r\nHost: {authority}\r\n");
+
+    if let Some((username, password)) = credentials {
+        request.push_str(&format!(
+            "Proxy-Authorization: Basic {}\r\n",
+            BASE64.encode(format!("{username}:{password}"))
+        ));
+    }
+
+    request.push_str("\r\n");
+
+    stream.write_all(request.as_bytes()).await?;
+    stream.flush().await?;
+
+    // Read byte by byte to not consume the beginning of the tunneled stream
+    let mut head = vec![];
+
+    while !head.ends_with(b"\r\n\r\n") {
+        if head.len() >= MAX_RESPONSE_HEAD_LEN {
+            return Err(ProxyConnectError::InvalidResponse(
+                "response headers are too large".to_owned(),
+            ));
+        }
+
+        match stream.read_u8().await {
+            Ok(byte) => head.push(byte),
+            Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => {
+                return Err(ProxyConnectError::InvalidResponse(
+                    "connection closed before response".to_owned(),
+                ));
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let status_line = head.lines().next().unwrap_or_default();
+
+    match status_line.split_whitespace().nth(1) {
+        Some(status) if status.starts_with('2') => Ok(()),
+        Some(_) => Err(ProxyConnectError::Rejected(status_line.to_owned())),
+        None => Err(ProxyConnectError::InvalidResponse(status_line.to_owned())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    async fn read_request_head<S: AsyncRead + Unpin>(server: &mut BufReader<S>) -> Vec<String> {
+        let mut lines = vec![];
+
+        loop {
+            let mut line = String::new();
+            server.read_line(&mut line).await.unwrap();
+
+            if line == "\r\n" {
+                return lines;
+            }
+
+            lines.push(line.trim_end().to_owned());
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_connect_with_basic_auth_and_keeps_tunneled_data() {
+        let (mut client, server) = tokio::io::duplex(1024);
+        let mut server = BufReader::new(server);
+
+        let server_task = tokio::spawn(async move {
+            let head = read_request_head(&mut server).await;
+
+            server
+                .get_mut()
+                .write_all(b"HTTP/1.1 200 Connection established\r\n\r\nhello")
+                .await
+                .unwrap();
+
+            head
+        });
+
+        http_connect_handshake(&mut client, "dev.local", 443, Some(("user", "p@ss")))
+            .await
+            .unwrap();
+
+        let mut tunneled = [0u8; 5];
+        client.read_exact(&mut tunneled).await.unwrap();
+        assert_eq!(&tunneled, b"hello");
+
+        assert_eq!(
+            server_task.await.unwrap(),
+            vec![
+                "CONNECT dev.local:443 HTTP/1.1",
+                "Host: dev.local:443",
+                "Proxy-Authorization: Basic dXNlcjpwQHNz",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn returns_rejected_status_line() {
+        let (mut client, server) = tokio::io::duplex(1024);
+        let mut server = BufReader::new(server);
+
+        tokio::spawn(async move {
+            read_request_head(&mut server).await;
+
+            server
+                .get_mut()
+                .write_all(b"HTTP/1.1 407 Proxy Authentication Required\r\n\r\n")
+                .await
+                .unwrap();
+        });
+
+        assert!(matches!(
+            http_connect_handshake(&mut client, "dev.local", 443, None).await,
+            Err(ProxyConnectError::Rejected(status)) if status == "HTTP/1.1 407 Proxy Authentication Required"
+        ));
+    }
+}
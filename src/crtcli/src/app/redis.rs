@@ -0,0 +1,371 @@
+use thiserror::Error;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+
+const SCAN_COUNT: &str = "1000";
+
+/// Same as the default `proto-max-bulk-len` of the Redis server.
+const MAX_BULK_STRING_LEN: i64 = 512 * 1024 * 1024;
+
+const MAX_ARRAY_LEN: i64 = 1024 * 1024;
+
+/// Value of the Redis serialization protocol (RESP2) reply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RespValue {
+    SimpleString(String),
+    Integer(i64),
+    BulkString(Option<Vec<u8>>),
+    Array(Option<Vec<RespValue>>),
+}
+
+#[derive(Debug, Error)]
+pub enum RedisError {
+    #[error("redis io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("redis protocol error: {0}")]
+    Protocol(String),
+
+    #[error("redis error: {0}")]
+    Server(String),
+
+    #[error("unexpected redis reply to {0}: {1:?}")]
+    UnexpectedReply(String, RespValue),
+}
+
+/// Minimal Redis client which sends commands over any byte stream, e.g. the tunneling websocket.
+pub struct RedisClient<S> {
+    stream: BufReader<S>,
+}
+
+impl<S> RedisClient<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream: BufReader::new(stream),
+        }
+    }
+
+    pub async fn command(&mut self, args: &[&[u8]]) -> Result<RespValue, RedisError> {
+        let mut request = format!("*{}\r\n", args.len()).into_bytes();
+
+        for arg in args {
+            request.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+            request.extend_from_slice(arg);
+            request.extend_from_slice(b"\r\n");
+        }
+
+        let stream = self.stream.get_mut();
+        stream.write_all(&request).await?;
+        stream.flush().await?;
+
+        self.read_value().await
+    }
+
+    pub async fn auth(&mut self, username: Option<&str>, password: &str) -> Result<(), RedisError> {
+        match username {
+            Some(username) => {
+                self.command(&[b"AUTH", username.as_bytes(), password.as_bytes()])
+                    .await?
+            }
+            None => self.command(&[b"AUTH", password.as_bytes()]).await?,
+        };
+
+        Ok(())
+    }
+
+    pub async fn select(&mut self, db: u32) -> Result<(), RedisError> {
+        self.command(&[b"SELECT", db.to_string().as_bytes()])
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns keys matching the glob-style pattern using SCAN, so the server is not blocked like with KEYS.
+    pub async fn scan(&mut self, pattern: &str) -> Result<Vec<String>, RedisError> {
+        let mut cursor = "0".to_owned();
+        let mut keys = vec![];
+
+        loop {
+            let reply = self
+                .command(&[
+                    b"SCAN",
+                    cursor.as_bytes(),
+                    b"MATCH",
+                    pattern.as_bytes(),
+                    b"COUNT",
+                    SCAN_COUNT.as_bytes(),
+                ])
+                .await?;
+
+            let (next_cursor, batch) = match reply {
+                RespValue::Array(Some(items)) => match items.as_slice() {
+                    [
+                        RespValue::BulkString(Some(next_cursor)),
+                        RespValue::Array(Some(batch)),
+                    ] => (
+                        String::from_utf8_lossy(next_cursor).into_owned(),
+                        batch.clone(),
+                    ),
+                    _ => {
+                        return Err(RedisError::UnexpectedReply(
+                            "SCAN".to_owned(),
+                            RespValue::Array(Some(items)),
+                        ));
+                    }
+                },
+                reply => return Err(RedisError::UnexpectedReply("SCAN".to_owned(), reply)),
+            };
+
+            keys.extend(batch.into_iter().filter_map(|key| match key {
+                RespValue::BulkString(Some(key)) => {
+                    Some(String::from_utf8_lossy(&key).into_owned())
+                }
+                _ => None,
+            }));
+
+            if next_cursor == "0" {
+                break;
+            }
+
+            cursor = next_cursor;
+        }
+
+        keys.sort();
+        keys.dedup();
+
+        Ok(keys)
+    }
+
+    /// Returns the type of the key, "none" if the key does not exist.
+    pub async fn key_type(&mut self, key: &str) -> Result<String, RedisError> {
+        match self.command(&[b"TYPE", key.as_bytes()]).await? {
+            RespValue::SimpleString(key_type) => Ok(key_type),
+            reply => Err(RedisError::UnexpectedReply("TYPE".to_owned(), reply)),
+        }
+    }
+
+    pub async fn del(&mut self, keys: &[String]) -> Result<i64, RedisError> {
+        let mut args: Vec<&[u8]> = vec![b"DEL"];
+        args.extend(keys.iter().map(|key| key.as_bytes()));
+
+        self.integer_command("DEL", &args).await
+    }
+
+    /// Returns the remaining time to live of the key in seconds, -1 if the key has no expiration and -2 if it does not exist.
+    pub async fn ttl(&mut self, key: &str) -> Result<i64, RedisError> {
+        self.integer_command("TTL", &[b"TTL", key.as_bytes()]).await
+    }
+
+    pub async fn info(&mut self, section: Option<&str>) -> Result<String, RedisError> {
+        let reply = match section {
+            Some(section) => self.command(&[b"INFO", section.as_bytes()]).await?,
+            None => self.command(&[b"INFO"]).await?,
+        };
+
+        match reply {
+            RespValue::BulkString(Some(info)) => Ok(String::from_utf8_lossy(&info).into_owned()),
+            reply => Err(RedisError::UnexpectedReply("INFO".to_owned(), reply)),
+        }
+    }
+
+    async fn integer_command(&mut self, name: &str, args: &[&[u8]]) -> Result<i64, RedisError> {
+        match self.command(args).await? {
+            RespValue::Integer(value) => Ok(value),
+            reply => Err(RedisError::UnexpectedReply(name.to_owned(), reply)),
+        }
+    }
+
+    async fn read_line(&mut self) -> Result<String, RedisError> {
+        let mut line = vec![];
+        self.stream.read_until(b'\n', &mut line).await?;
+
+        if !line.ends_with(b"\r\n") {
+            return Err(RedisError::Protocol(
+                "connection closed or reply line is not terminated".to_owned(),
+            ));
+        }
+
+        line.truncate(line.len() - 2);
+
+        String::from_utf8(line).map_err(|_| RedisError::Protocol("reply is not utf-8".to_owned()))
+    }
+
+    async fn read_value(&mut self) -> Result<RespValue, RedisError> {
+        let line = self.read_line().await?;
+
+        let mut chars = line.chars();
+
+        let Some(prefix) = chars.next() else {
+            return Err(RedisError::Protocol("empty reply".to_owned()));
+        };
+
+        let payload = chars.as_str();
+
+        let parse_length = |value: &str, max: i64| match value.parse::<i64>() {
+            Ok(length) if length <= max => Ok(length),
+            Ok(length) => Err(RedisError::Protocol(format!(
+                "length {length} exceeds the maximum of {max}"
+            ))),
+            Err(_) => Err(RedisError::Protocol(format!("invalid length: {value}"))),
+        };
+
+        match prefix {
+            '+' => Ok(RespValue::SimpleString(payload.to_owned())),
+            '-' => Err(RedisError::Server(payload.to_owned())),
+            ':' => Ok(RespValue::Integer(parse_length(payload, i64::MAX)?)),
+            '$' => match parse_length(payload, MAX_BULK_STRING_LEN)? {
+                length if length < 0 => Ok(RespValue::BulkString(None)),
+                length => {
+                    let length = length as usize;
+
+                    // Buffer grows as data is received instead of allocating the declared length
+                    let mut data = vec![];
+                    (&mut self.stream)
+                        .take(length as u64 + 2)
+                        .read_to_end(&mut data)
+                        .await?;
+
+                    if data.len() != length + 2 || !data.ends_with(b"\r\n") {
+                        return Err(RedisError::Protocol(
+                            "connection closed or bulk string is not terminated".to_owned(),
+                        ));
+                    }
+
+                    data.truncate(length);
+
+                    Ok(RespValue::BulkString(Some(data)))
+                }
+            },
+            '*' => match parse_length(payload, MAX_ARRAY_LEN)? {
+                length if length < 0 => Ok(RespValue::Array(None)),
+                length => {
+                    let mut items = Vec::with_capacity(length as usize);
+
+                    for _ in 0..length {
+                        items.push(Box::pin(self.read_value()).await?);
+                    }
+
+                    Ok(RespValue::Array(Some(items)))
+                }
+            },
+            _ => Err(RedisError::Protocol(format!(
+                "unsupported reply type: {prefix}"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::DuplexStream;
+
+    /// Reads one request from the client and responds with the specified raw reply.
+    async fn respond(server: &mut BufReader<DuplexStream>, reply: &[u8]) -> Vec<String> {
+        let mut line = String::new();
+        server.read_line(&mut line).await.unwrap();
+
+        let count: usize = line.trim_end()[1..].parse().unwrap();
+        let mut args = vec![];
+
+        for _ in 0..count {
+            line.clear();
+            server.read_line(&mut line).await.unwrap();
+
+            let length: usize = line.trim_end()[1..].parse().unwrap();
+            let mut arg = vec![0u8; length + 2];
+            server.read_exact(&mut arg).await.unwrap();
+            arg.truncate(length);
+
+            args.push(String::from_utf8(arg).unwrap());
+        }
+
+        server.get_mut().write_all(reply).await.unwrap();
+
+        args
+    }
+
+    #[tokio::test]
+    async fn scans_keys_until_cursor_is_zero() {
+        let (client, server) = tokio::io::duplex(1024);
+        let mut server = BufReader::new(server);
+
+        let server_task = tokio::spawn(async move {
+            let first = respond(
+                &mut server,
+                b"*2\r\n$2\r\n17\r\n*2\r\n$9\r\nsession:b\r\n$9\r\nsession:a\r\n",
+            )
+            .await;
+            let second = respond(&mut server, b"*2\r\n$1\r\n0\r\n*1\r\n$9\r\nsession:a\r\n").await;
+
+            (first, second)
+        });
+
+        let mut client = RedisClient::new(client);
+        let keys = client.scan("session:*").await.unwrap();
+
+        assert_eq!(keys, vec!["session:a", "session:b"]);
+
+        let (first, second) = server_task.await.unwrap();
+        assert_eq!(
+            first,
+            vec!["SCAN", "0", "MATCH", "session:*", "COUNT", "1000"]
+        );
+        assert_eq!(second[1], "17");
+    }
+
+    #[tokio::test]
+    async fn returns_server_errors_and_nil_values() {
+        let (client, server) = tokio::io::duplex(1024);
+        let mut server = BufReader::new(server);
+
+        let server_task = tokio::spawn(async move {
+            respond(&mut server, b"-NOAUTH Authentication required.\r\n").await;
+            respond(&mut server, b"$-1\r\n").await;
+            respond(&mut server, b":-2\r\n").await;
+        });
+
+        let mut client = RedisClient::new(client);
+
+        assert!(matches!(
+            client.select(1).await,
+            Err(RedisError::Server(message)) if message == "NOAUTH Authentication required."
+        ));
+        assert_eq!(
+            client.command(&[b"GET", b"missing"]).await.unwrap(),
+            RespValue::BulkString(None)
+        );
+        assert_eq!(client.ttl("missing").await.unwrap(), -2);
+
+        server_task.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_replies() {
+        let (client, server) = tokio::io::duplex(1024);
+        let mut server = BufReader::new(server);
+
+        let server_task = tokio::spawn(async move {
+            respond(&mut server, "é\r\n".as_bytes()).await;
+            respond(&mut server, b"$1073741824\r\n").await;
+            respond(&mut server, b"*1073741824\r\n").await;
+            respond(&mut server, b"$5\r\nab").await;
+        });
+
+        let mut client = RedisClient::new(client);
+
+        for _ in 0..3 {
+            assert!(matches!(
+                client.command(&[b"PING"]).await,
+                Err(RedisError::Protocol(_))
+            ));
+        }
+
+        let truncated = client.command(&[b"GET", b"key"]);
+        let (result, _) = tokio::join!(truncated, server_task);
+
+        assert!(matches!(result, Err(RedisError::Protocol(_))));
+    }
+}
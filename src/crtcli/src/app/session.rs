@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
 
 #[derive(Debug, Clone, Eq, PartialEq, rkyv::Archive, rkyv::Deserialize, rkyv::Serialize)]
 pub enum CrtSession {
@@ -29,6 +30,10 @@ pub struct CrtSessionOAuth {
     access_token: String,
     expires_in: i64,
     token_type: String,
+
+    /// Unix timestamp when the token was requested, it is not a part of the token response.
+    #[serde(skip)]
+    issued_timestamp: i64,
 }
 
 impl CrtSessionCookie {
@@ -49,18 +54,28 @@ impl CrtSessionCookie {
 
 impl CrtSessionCookie {
     pub fn to_cookie_value(&self) -> String {
-        let mut cookie_value =
-            format!(".ASPXAUTH={};BPMCSRF={};", self.aspxauth, self.bpmcsrf).to_owned();
+        self.cookies()
+            .iter()
+            .map(|(name, value)| format!("{name}={value};"))
+            .collect()
+    }
+
+    /// Returns name and value pairs of the session cookies.
+    pub fn cookies(&self) -> Vec<(&'static str, &str)> {
+        let mut cookies = vec![
+            (".ASPXAUTH", self.aspxauth.as_str()),
+            ("BPMCSRF", &self.bpmcsrf),
+        ];
 
         if let Some(csrftoken) = &self.csrftoken {
-            cookie_value = format!("{cookie_value}CsrfToken={csrftoken};");
+            cookies.push(("CsrfToken", csrftoken));
         }
 
         if let Some(bpmsessionid) = &self.bpmsessionid {
-            cookie_value = format!("{cookie_value}BPMSESSIONID={bpmsessionid};");
+            cookies.push(("BPMSESSIONID", bpmsessionid));
         }
 
-        cookie_value
+        cookies
     }
 
     pub fn bpmcsrf(&self) -> &str {
@@ -77,6 +92,20 @@ impl CrtSessionCookie {
 }
 
 impl CrtSessionOAuth {
+    pub fn new(
+        access_token: String,
+        expires_in: i64,
+        token_type: String,
+        issued_at: OffsetDateTime,
+    ) -> CrtSessionOAuth {
+        CrtSessionOAuth {
+            access_token,
+            expires_in,
+            token_type,
+            issued_timestamp: issued_at.unix_timestamp(),
+        }
+    }
+
     pub fn access_token(&self) -> &str {
         &self.access_token
     }
@@ -88,4 +117,22 @@ impl CrtSessionOAuth {
     pub fn expires_in(&self) -> i64 {
         self.expires_in
     }
+
+    pub fn issued_at(&self) -> OffsetDateTime {
+        OffsetDateTime::from_unix_timestamp(self.issued_timestamp)
+            .unwrap_or(OffsetDateTime::UNIX_EPOCH)
+    }
+
+    pub fn set_issued_at(&mut self, issued_at: OffsetDateTime) {
+        self.issued_timestamp = issued_at.unix_timestamp();
+    }
+
+    pub fn expires_at(&self) -> OffsetDateTime {
+        self.issued_at() + time::Duration::seconds(self.expires_in)
+    }
+
+    /// Returns true if the token is expired or expires within the specified margin.
+    pub fn expires_within(&self, margin: time::Duration) -> bool {
+        self.expires_at() - margin <= OffsetDateTime::now_utc()
+    }
 }
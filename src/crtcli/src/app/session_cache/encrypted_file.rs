@@ -0,0 +1,463 @@
+use crate::app::session_cache::{
+    BinaryFileCrtSessionCacheStorage, CrtSessionCacheEntry, CrtSessionCacheStorage,
+};
+use ring::aead::{Aad, CHACHA20_POLY1305, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::hkdf;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+const CACHE_FILENAME: &str = "sessions.cache";
+const LOCK_FILENAME: &str = "sessions.lock";
+const SECRET_FILENAME: &str = "sessions.key";
+
+const FILE_MAGIC: &[u8] = b"CRTSC1";
+const SECRET_LEN: usize = 32;
+const KEY_DERIVATION_SALT: &[u8] = b"crtcli-session-cache";
+const KEY_DERIVATION_INFO: &[u8] = b"crtcli session cache v1";
+
+#[derive(Debug, Error)]
+enum EncryptedFileStorageError {
+    #[error("session cache io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("session cache encryption error")]
+    Crypto,
+
+    #[error("session cache serialization error: {0}")]
+    Serialization(#[from] rkyv::rancor::Error),
+
+    #[error("invalid session cache file format")]
+    Format,
+
+    #[error("invalid session cache secret file")]
+    Secret,
+}
+
+impl From<ring::error::Unspecified> for EncryptedFileStorageError {
+    fn from(_: ring::error::Unspecified) -> Self {
+        Self::Crypto
+    }
+}
+
+/// Session cache stored in the per-user directory and encrypted with ChaCha20-Poly1305.
+///
+/// The key is derived from the random secret file next to the cache. Concurrent crtcli runs
+/// are synchronized with an advisory lock on a separate lock file, and the cache is replaced
+/// by atomic rename, so readers never observe partially written files.
+pub struct EncryptedFileCrtSessionCacheStorage {
+    dir: PathBuf,
+    legacy: Option<BinaryFileCrtSessionCacheStorage>,
+}
+
+impl EncryptedFileCrtSessionCacheStorage {
+    pub fn new(dir: PathBuf, legacy_filepath: Option<PathBuf>) -> Self {
+        Self {
+            dir,
+            legacy: legacy_filepath.map(|filepath| BinaryFileCrtSessionCacheStorage { filepath }),
+        }
+    }
+
+    fn cache_filepath(&self) -> PathBuf {
+        self.dir.join(CACHE_FILENAME)
+    }
+
+    fn lock(&self, exclusive: bool) -> Result<File, EncryptedFileStorageError> {
+        create_private_dir(&self.dir)?;
+
+        let lock_file = open_private_file(&self.dir.join(LOCK_FILENAME))?;
+
+        match exclusive {
+            true => lock_file.lock()?,
+            false => lock_file.lock_shared()?,
+        }
+
+        Ok(lock_file)
+    }
+
+    fn load(&self) -> Result<HashMap<u64, CrtSessionCacheEntry>, EncryptedFileStorageError> {
+        let bytes = match std::fs::read(self.cache_filepath()) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(self.load_legacy());
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let Some(key) = self.read_key()? else {
+            return Ok(HashMap::new());
+        };
+
+        let plaintext = decrypt(&key, bytes)?;
+
+        Ok(rkyv::from_bytes::<_, rkyv::rancor::Error>(&plaintext)?)
+    }
+
+    fn save(
+        &self,
+        cache: &HashMap<u64, CrtSessionCacheEntry>,
+    ) -> Result<(), EncryptedFileStorageError> {
+        // Secret file of unexpected length is replaced, the cache encrypted with it is unreadable anyway
+        let key = match self.read_key() {
+            Ok(Some(key)) => key,
+            Ok(None) | Err(EncryptedFileStorageError::Secret) => self.create_key()?,
+            Err(err) => return Err(err),
+        };
+
+        let plaintext = rkyv::to_bytes::<rkyv::rancor::Error>(cache)?;
+        let ciphertext = encrypt(&key, plaintext.to_vec())?;
+
+        let temp_filepath = self
+            .dir
+            .join(format!("{CACHE_FILENAME}.{}.tmp", std::process::id()));
+
+        let write_result = (|| {
+            let mut temp_file = create_private_file(&temp_filepath)?;
+            temp_file.write_all(&ciphertext)?;
+            temp_file.sync_all()?;
+
+            std::fs::rename(&temp_filepath, self.cache_filepath())
+        })();
+
+        if write_result.is_err() {
+            let _ = std::fs::remove_file(&temp_filepath);
+        }
+
+        write_result?;
+
+        // Plain text cache is not needed anymore once its entries are stored encrypted
+        if let Some(legacy) = &self.legacy {
+            let _ = std::fs::remove_file(&legacy.filepath);
+        }
+
+        Ok(())
+    }
+
+    fn load_legacy(&self) -> HashMap<u64, CrtSessionCacheEntry> {
+        self.legacy
+            .as_ref()
+            .map(BinaryFileCrtSessionCacheStorage::load)
+            .unwrap_or_default()
+    }
+
+    fn read_key(&self) -> Result<Option<LessSafeKey>, EncryptedFileStorageError> {
+        let mut secret = vec![];
+
+        match File::open(self.dir.join(SECRET_FILENAME)) {
+            Ok(mut file) => file.read_to_end(&mut secret)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        if secret.len() != SECRET_LEN {
+            return Err(EncryptedFileStorageError::Secret);
+        }
+
+        Ok(Some(derive_key(&secret)))
+    }
+
+    fn create_key(&self) -> Result<LessSafeKey, EncryptedFileStorageError> {
+        let mut secret = [0u8; SECRET_LEN];
+        SystemRandom::new().fill(&mut secret)?;
+
+        let mut secret_file = create_private_file(&self.dir.join(SECRET_FILENAME))?;
+        secret_file.write_all(&secret)?;
+        secret_file.sync_all()?;
+
+        Ok(derive_key(&secret))
+    }
+
+    fn try_update<F>(&self, f: F) -> Result<(), EncryptedFileStorageError>
+    where
+        F: FnOnce(&mut HashMap<u64, CrtSessionCacheEntry>),
+    {
+        let _lock = self.lock(true)?;
+
+        // Unreadable cache (e.g. corrupted or secret was changed) is replaced with a new one
+        let mut cache = self.load().unwrap_or_default();
+
+        f(&mut cache);
+
+        self.save(&cache)
+    }
+}
+
+impl CrtSessionCacheStorage for EncryptedFileCrtSessionCacheStorage {
+    fn read<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&HashMap<u64, CrtSessionCacheEntry>) -> R,
+    {
+        let cache = self
+            .lock(false)
+            .and_then(|_lock| self.load())
+            .unwrap_or_default();
+
+        f(&cache)
+    }
+
+    fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut HashMap<u64, CrtSessionCacheEntry>),
+    {
+        let _ = self.try_update(f);
+    }
+}
+
+fn derive_key(secret: &[u8]) -> LessSafeKey {
+    let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, KEY_DERIVATION_SALT).extract(secret);
+    let info = [KEY_DERIVATION_INFO];
+
+    let okm = prk
+        .expand(&info, &CHACHA20_POLY1305)
+        .expect("key length is valid for the aead algorithm");
+
+    LessSafeKey::new(UnboundKey::from(okm))
+}
+
+/// Returns `magic || nonce || ciphertext || tag`.
+fn encrypt(key: &LessSafeKey, mut data: Vec<u8>) -> Result<Vec<u8>, EncryptedFileStorageError> {
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new().fill(&mut nonce)?;
+
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(FILE_MAGIC),
+        &mut data,
+    )?;
+
+    Ok([FILE_MAGIC, &nonce, &data].concat())
+}
+
+fn decrypt(key: &LessSafeKey, data: Vec<u8>) -> Result<Vec<u8>, EncryptedFileStorageError> {
+    let Some(data) = data.strip_prefix(FILE_MAGIC) else {
+        return Err(EncryptedFileStorageError::Format);
+    };
+
+    if data.len() < NONCE_LEN {
+        return Err(EncryptedFileStorageError::Format);
+    }
+
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce)?;
+
+    let mut ciphertext = ciphertext.to_vec();
+    let plaintext_len = key
+        .open_in_place(nonce, Aad::from(FILE_MAGIC), &mut ciphertext)?
+        .len();
+
+    ciphertext.truncate(plaintext_len);
+
+    Ok(ciphertext)
+}
+
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+        std::fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(dir)?;
+
+        std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))
+    }
+
+    #[cfg(not(unix))]
+    {
+        std::fs::create_dir_all(dir)
+    }
+}
+
+fn private_file_options() -> OpenOptions {
+    let mut options = OpenOptions::new();
+    options.write(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        options.mode(0o600);
+    }
+
+    options
+}
+
+fn open_private_file(path: &Path) -> std::io::Result<File> {
+    private_file_options()
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+fn create_private_file(path: &Path) -> std::io::Result<File> {
+    private_file_options()
+        .create(true)
+        .truncate(true)
+        .open(path)
+}
+
+/// Returns the per-user cache directory of crtcli, e.g. `~/.cache/crtcli` on Linux,
+/// `~/Library/Caches/crtcli` on macOS and `%LOCALAPPDATA%\crtcli` on Windows.
+pub fn default_cache_dir() -> PathBuf {
+    let env_dir = |name: &str| {
+        std::env::var_os(name)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+
+    let user_cache_dir = if cfg!(windows) {
+        env_dir("LOCALAPPDATA")
+    } else if cfg!(target_os = "macos") {
+        env_dir("HOME").map(|home| home.join("Library").join("Caches"))
+    } else {
+        env_dir("XDG_CACHE_HOME").or_else(|| env_dir("HOME").map(|home| home.join(".cache")))
+    };
+
+    match user_cache_dir {
+        Some(dir) => dir.join("crtcli"),
+        None => {
+            let user = std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .unwrap_or_default();
+
+            std::env::temp_dir().join(format!("crtcli-{user}"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::session_cache::{
+        LegacyCrtSession, LegacyCrtSessionCacheEntry, LegacyCrtSessionOAuth,
+    };
+    use crate::app::{CrtSession, CrtSessionCookie};
+    use crate::utils::test_utils::TempDir;
+
+    fn test_entry() -> CrtSessionCacheEntry {
+        CrtSessionCacheEntry {
+            created_timestamp: 1,
+            url: "https://dev.local".to_owned(),
+            user: "Supervisor".to_owned(),
+            value: CrtSession::Cookie(CrtSessionCookie::new(
+                "secret-aspxauth".to_owned(),
+                "csrf".to_owned(),
+                None,
+                None,
+            )),
+        }
+    }
+
+    #[test]
+    fn stores_entries_encrypted() {
+        let dir = TempDir::new("session-cache");
+        let storage = EncryptedFileCrtSessionCacheStorage::new(dir.to_path_buf(), None);
+
+        storage.update(|cache| {
+            cache.insert(1, test_entry());
+        });
+
+        let bytes = std::fs::read(dir.join(CACHE_FILENAME)).unwrap();
+        assert!(bytes.starts_with(FILE_MAGIC));
+        assert!(
+            !bytes
+                .windows(b"secret-aspxauth".len())
+                .any(|w| w == b"secret-aspxauth")
+        );
+
+        let reopened = EncryptedFileCrtSessionCacheStorage::new(dir.to_path_buf(), None);
+        assert_eq!(reopened.read(|cache| cache[&1].user.clone()), "Supervisor");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode =
+                |path: PathBuf| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+
+            assert_eq!(mode(dir.to_path_buf()), 0o700);
+            assert_eq!(mode(dir.join(CACHE_FILENAME)), 0o600);
+            assert_eq!(mode(dir.join(SECRET_FILENAME)), 0o600);
+        }
+    }
+
+    #[test]
+    fn migrates_legacy_cache() {
+        let dir = TempDir::new("session-cache");
+        let legacy_filepath = TempDir::new("legacy-cache");
+
+        let legacy_cache = HashMap::from([
+            (
+                7,
+                LegacyCrtSessionCacheEntry {
+                    created_timestamp: 1,
+                    value: LegacyCrtSession::Cookie(CrtSessionCookie::new(
+                        "secret-aspxauth".to_owned(),
+                        "csrf".to_owned(),
+                        None,
+                        None,
+                    )),
+                },
+            ),
+            (
+                8,
+                LegacyCrtSessionCacheEntry {
+                    created_timestamp: 2,
+                    value: LegacyCrtSession::OAuthSession(LegacyCrtSessionOAuth {
+                        access_token: "token".to_owned(),
+                        expires_in: 3600,
+                        token_type: "Bearer".to_owned(),
+                    }),
+                },
+            ),
+        ]);
+        std::fs::write(
+            &legacy_filepath,
+            rkyv::to_bytes::<rkyv::rancor::Error>(&legacy_cache).unwrap(),
+        )
+        .unwrap();
+
+        let storage = EncryptedFileCrtSessionCacheStorage::new(
+            dir.to_path_buf(),
+            Some(legacy_filepath.to_path_buf()),
+        );
+
+        assert!(storage.read(|cache| cache.contains_key(&7)));
+        assert_eq!(
+            storage.read(|cache| match &cache[&8].value {
+                CrtSession::OAuthSession(oauth) => oauth.expires_at().unix_timestamp(),
+                _ => 0,
+            }),
+            3602
+        );
+
+        storage.update(|_| {});
+
+        assert!(!legacy_filepath.exists());
+        assert!(storage.read(|cache| cache.contains_key(&7)));
+    }
+
+    #[test]
+    fn replaces_invalid_secret_file() {
+        let dir = TempDir::new("session-cache");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(SECRET_FILENAME), b"short").unwrap();
+        std::fs::write(dir.join(CACHE_FILENAME), b"unreadable").unwrap();
+
+        let storage = EncryptedFileCrtSessionCacheStorage::new(dir.to_path_buf(), None);
+
+        storage.update(|cache| {
+            cache.insert(1, test_entry());
+        });
+
+        assert_eq!(
+            std::fs::read(dir.join(SECRET_FILENAME)).unwrap().len(),
+            SECRET_LEN
+        );
+        assert!(storage.read(|cache| cache.contains_key(&1)));
+    }
+}
@@ -0,0 +1,354 @@
+use crate::app::{CrtCredentials, CrtSession, CrtSessionCookie, CrtSessionOAuth};
+use encrypted_file::{EncryptedFileCrtSessionCacheStorage, default_cache_dir};
+use std::collections::HashMap;
+use std::env::temp_dir;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::RwLock;
+use time::OffsetDateTime;
+
+mod encrypted_file;
+
+#[derive(Debug, Clone, rkyv::Archive, rkyv::Deserialize, rkyv::Serialize)]
+struct CrtSessionCacheEntry {
+    created_timestamp: i64,
+    url: String,
+    user: String,
+    value: CrtSession,
+}
+
+impl CrtSessionCacheEntry {
+    fn expires_timestamp(&self) -> i64 {
+        match &self.value {
+            CrtSession::OAuthSession(oauth_session) => oauth_session.expires_at().unix_timestamp(),
+            _ => self.created_timestamp + COOKIE_SESSION_LIFETIME.whole_seconds(),
+        }
+    }
+
+    fn to_info(&self) -> CrtSessionCacheEntryInfo {
+        CrtSessionCacheEntryInfo {
+            url: self.url.clone(),
+            user: self.user.clone(),
+            created_at: OffsetDateTime::from_unix_timestamp(self.created_timestamp)
+                .unwrap_or(OffsetDateTime::UNIX_EPOCH),
+            expires_at: OffsetDateTime::from_unix_timestamp(self.expires_timestamp())
+                .unwrap_or(OffsetDateTime::UNIX_EPOCH),
+            session: self.value.clone(),
+        }
+    }
+}
+
+/// Cached session with the Creatio URL and user it belongs to.
+#[derive(Debug, Clone)]
+pub struct CrtSessionCacheEntryInfo {
+    pub url: String,
+    pub user: String,
+    pub created_at: OffsetDateTime,
+    pub expires_at: OffsetDateTime,
+    pub session: CrtSession,
+}
+
+const COOKIE_SESSION_LIFETIME: time::Duration = time::Duration::hours(1);
+
+trait CrtSessionCacheStorage: Send + Sync {
+    fn read<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&HashMap<u64, CrtSessionCacheEntry>) -> R;
+
+    fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut HashMap<u64, CrtSessionCacheEntry>);
+}
+
+/// Cache entry layout of previous crtcli versions, kept to read their plain text cache.
+#[derive(Debug, Clone, rkyv::Archive, rkyv::Deserialize, rkyv::Serialize)]
+struct LegacyCrtSessionCacheEntry {
+    created_timestamp: i64,
+    value: LegacyCrtSession,
+}
+
+#[derive(Debug, Clone, rkyv::Archive, rkyv::Deserialize, rkyv::Serialize)]
+enum LegacyCrtSession {
+    Cookie(CrtSessionCookie),
+    OAuthSession(LegacyCrtSessionOAuth),
+}
+
+#[derive(Debug, Clone, rkyv::Archive, rkyv::Deserialize, rkyv::Serialize)]
+struct LegacyCrtSessionOAuth {
+    access_token: String,
+    expires_in: i64,
+    token_type: String,
+}
+
+impl From<LegacyCrtSessionCacheEntry> for CrtSessionCacheEntry {
+    /// Legacy entries do not store the url and user, they stay empty until the session is renewed.
+    fn from(entry: LegacyCrtSessionCacheEntry) -> Self {
+        let value = match entry.value {
+            LegacyCrtSession::Cookie(cookie) => CrtSession::Cookie(cookie),
+            LegacyCrtSession::OAuthSession(oauth) => {
+                CrtSession::OAuthSession(CrtSessionOAuth::new(
+                    oauth.access_token,
+                    oauth.expires_in,
+                    oauth.token_type,
+                    OffsetDateTime::from_unix_timestamp(entry.created_timestamp)
+                        .unwrap_or(OffsetDateTime::UNIX_EPOCH),
+                ))
+            }
+        };
+
+        CrtSessionCacheEntry {
+            created_timestamp: entry.created_timestamp,
+            url: String::new(),
+            user: String::new(),
+            value,
+        }
+    }
+}
+
+/// Plain text cache of previous crtcli versions in the shared temp dir,
+/// it is only read to migrate entries into [`EncryptedFileCrtSessionCacheStorage`].
+struct BinaryFileCrtSessionCacheStorage {
+    filepath: PathBuf,
+}
+
+impl BinaryFileCrtSessionCacheStorage {
+    fn load(&self) -> HashMap<u64, CrtSessionCacheEntry> {
+        std::fs::read(&self.filepath)
+            .ok()
+            .and_then(|bytes| {
+                rkyv::from_bytes::<HashMap<u64, LegacyCrtSessionCacheEntry>, rkyv::rancor::Error>(
+                    &bytes,
+                )
+                .ok()
+            })
+            .map(|cache| {
+                cache
+                    .into_iter()
+                    .map(|(hash, entry)| (hash, entry.into()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Default)]
+struct MemoryCrtSessionCacheStorage {
+    cache: RwLock<HashMap<u64, CrtSessionCacheEntry>>,
+}
+
+impl MemoryCrtSessionCacheStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CrtSessionCacheStorage for MemoryCrtSessionCacheStorage {
+    fn read<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&HashMap<u64, CrtSessionCacheEntry>) -> R,
+    {
+        let guard = self.cache.read().unwrap();
+        f(&guard)
+    }
+
+    fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut HashMap<u64, CrtSessionCacheEntry>),
+    {
+        let mut guard = self.cache.write().unwrap();
+        f(&mut guard);
+    }
+}
+
+pub trait CrtSessionCache: Send + Sync {
+    fn clear_all(&self);
+
+    fn get_entry(&self, credentials: &CrtCredentials) -> Option<CrtSession>;
+
+    fn get_entry_info(&self, credentials: &CrtCredentials) -> Option<CrtSessionCacheEntryInfo>;
+
+    fn entries(&self) -> Vec<CrtSessionCacheEntryInfo>;
+
+    fn set_entry(&self, credentials: &CrtCredentials, session: CrtSession);
+
+    /// Removes the cached session, returns false if there was no session for the credentials.
+    fn remove_entry(&self, credentials: &CrtCredentials) -> bool;
+}
+
+struct DefaultCrtSessionCache<S>
+where
+    S: CrtSessionCacheStorage,
+{
+    storage: S,
+}
+
+impl<S> DefaultCrtSessionCache<S>
+where
+    S: CrtSessionCacheStorage,
+{
+    fn hash_credentials(credentials: &CrtCredentials) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        credentials.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn get_outdated_since_timestamp() -> i64 {
+        (OffsetDateTime::now_utc() - COOKIE_SESSION_LIFETIME).unix_timestamp()
+    }
+}
+
+impl<S> CrtSessionCache for DefaultCrtSessionCache<S>
+where
+    S: CrtSessionCacheStorage,
+{
+    fn clear_all(&self) {
+        self.storage.update(|cache| cache.clear());
+    }
+
+    fn get_entry(&self, credentials: &CrtCredentials) -> Option<CrtSession> {
+        let hash = Self::hash_credentials(credentials);
+        let outdated_since = Self::get_outdated_since_timestamp();
+
+        self.storage.read(|cache| {
+            cache
+                .get(&hash)
+                .filter(|x| x.created_timestamp > outdated_since)
+                .map(|x| x.value.clone())
+        })
+    }
+
+    fn get_entry_info(&self, credentials: &CrtCredentials) -> Option<CrtSessionCacheEntryInfo> {
+        let hash = Self::hash_credentials(credentials);
+
+        self.storage
+            .read(|cache| cache.get(&hash).map(CrtSessionCacheEntry::to_info))
+    }
+
+    fn entries(&self) -> Vec<CrtSessionCacheEntryInfo> {
+        let mut entries: Vec<_> = self
+            .storage
+            .read(|cache| cache.values().map(CrtSessionCacheEntry::to_info).collect());
+
+        entries.sort_by(|a, b| (&a.url, &a.user).cmp(&(&b.url, &b.user)));
+
+        entries
+    }
+
+    fn set_entry(&self, credentials: &CrtCredentials, session: CrtSession) {
+        let hash = Self::hash_credentials(credentials);
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let default_outdated_since = Self::get_outdated_since_timestamp();
+
+        self.storage.update(|cache| {
+            cache.insert(
+                hash,
+                CrtSessionCacheEntry {
+                    created_timestamp: now,
+                    url: credentials.url().to_owned(),
+                    user: credentials.user().to_owned(),
+                    value: session,
+                },
+            );
+
+            cache.retain(|_, x| match &x.value {
+                CrtSession::OAuthSession(_) => x.expires_timestamp() >= now,
+                _ => x.created_timestamp > default_outdated_since,
+            });
+        });
+    }
+
+    fn remove_entry(&self, credentials: &CrtCredentials) -> bool {
+        let hash = Self::hash_credentials(credentials);
+        let mut removed = false;
+
+        self.storage.update(|cache| {
+            removed = cache.remove(&hash).is_some();
+        });
+
+        removed
+    }
+}
+
+pub fn create_default_session_cache() -> Box<dyn CrtSessionCache> {
+    Box::new(DefaultCrtSessionCache {
+        storage: EncryptedFileCrtSessionCacheStorage::new(
+            default_cache_dir(),
+            Some(temp_dir().join("crtcli-sessions.cache")),
+        ),
+    })
+}
+
+pub fn create_memory_session_cache() -> Box<dyn CrtSessionCache> {
+    Box::new(DefaultCrtSessionCache {
+        storage: MemoryCrtSessionCacheStorage::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{CrtSessionCookie, CrtSessionOAuth};
+
+    #[test]
+    fn lists_and_removes_entries() {
+        let cache = create_memory_session_cache();
+        let credentials = CrtCredentials::new("https://dev.local/", "Supervisor", "Supervisor");
+
+        cache.set_entry(
+            &credentials,
+            CrtSession::Cookie(CrtSessionCookie::new(
+                "auth".to_owned(),
+                "csrf".to_owned(),
+                None,
+                None,
+            )),
+        );
+
+        let entries = cache.entries();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].url, "https://dev.local");
+        assert_eq!(entries[0].user, "Supervisor");
+        assert_eq!(
+            entries[0].expires_at - entries[0].created_at,
+            COOKIE_SESSION_LIFETIME
+        );
+
+        assert!(cache.remove_entry(&credentials));
+        assert!(!cache.remove_entry(&credentials));
+        assert!(cache.entries().is_empty());
+    }
+
+    #[test]
+    fn oauth_entry_expires_by_issue_time() {
+        let cache = create_memory_session_cache();
+        let credentials = CrtCredentials::new_oauth(
+            "https://dev.local",
+            "https://dev-is.local/connect/token",
+            "client",
+            "secret",
+        );
+
+        let mut oauth_session: CrtSessionOAuth = serde_json::from_str(
+            r#"{"access_token":"token","expires_in":3600,"token_type":"Bearer"}"#,
+        )
+        .unwrap();
+
+        let issued_at =
+            OffsetDateTime::now_utc() - time::Duration::minutes(59) - time::Duration::seconds(30);
+        oauth_session.set_issued_at(issued_at);
+
+        assert!(oauth_session.expires_within(time::Duration::minutes(1)));
+        assert!(!oauth_session.expires_within(time::Duration::seconds(10)));
+
+        cache.set_entry(&credentials, CrtSession::OAuthSession(oauth_session));
+
+        let entry = cache.get_entry_info(&credentials).unwrap();
+
+        assert_eq!(entry.user, "client");
+        assert_eq!(
+            entry.expires_at.unix_timestamp(),
+            (issued_at + time::Duration::hours(1)).unix_timestamp()
+        );
+    }
+}
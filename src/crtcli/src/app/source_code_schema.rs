@@ -0,0 +1,124 @@
+use crate::app::{CrtClient, CrtClientError, CrtRequestBuilderExt, StandardServiceResponse};
+use reqwest::Method;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+pub struct SourceCodeSchemaDesignerService<'c>(&'c CrtClient);
+
+impl<'c> SourceCodeSchemaDesignerService<'c> {
+    pub fn new(client: &'c CrtClient) -> Self {
+        Self(client)
+    }
+
+    pub async fn get_schema(
+        &self,
+        schema_uid: &str,
+        package_uid: &str,
+    ) -> Result<SourceCodeSchema, CrtClientError> {
+        let response = self
+            .0
+            .request(
+                Method::POST,
+                "0/ServiceModel/SourceCodeSchemaDesignerService.svc/GetSchema",
+            )
+            .json(&json!({
+                "schemaUId": schema_uid,
+                "packageUId": package_uid
+            }))
+            .send_with_session(self.0)
+            .await?
+            .error_for_status()?;
+
+        response.json::<GetSchemaResponse>().await?.into_result()
+    }
+
+    /// Finds source code schemas by name in all packages.
+    ///
+    /// Schemas are listed by WorkspaceExplorerService, only schemas which are opened by the designer
+    /// service are returned, so schemas of other types with the same name are skipped.
+    pub async fn find_schemas(
+        &self,
+        schema_name: &str,
+    ) -> Result<Vec<SourceCodeSchemaInfo>, CrtClientError> {
+        let candidates = self
+            .0
+            .workspace_explorer_service()
+            .get_workspace_items()
+            .await?
+            .into_iter()
+            .filter(|item| item.name.eq_ignore_ascii_case(schema_name));
+
+        let mut schemas = vec![];
+
+        for item in candidates {
+            if self.get_schema(&item.uid, &item.package_uid).await.is_ok() {
+                schemas.push(SourceCodeSchemaInfo {
+                    schema_uid: item.uid,
+                    package_uid: item.package_uid,
+                    package_name: item.package_name,
+                });
+            }
+        }
+
+        Ok(schemas)
+    }
+
+    pub async fn save_schema(&self, schema: &SourceCodeSchema) -> Result<(), CrtClientError> {
+        let response = self
+            .0
+            .request(
+                Method::POST,
+                "0/ServiceModel/SourceCodeSchemaDesignerService.svc/SaveSchema",
+            )
+            .json(&schema.0)
+            .send_with_session(self.0)
+            .await?
+            .error_for_status()?;
+
+        Ok(response
+            .json::<StandardServiceResponse>()
+            .await?
+            .into_result()?)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SourceCodeSchemaInfo {
+    pub schema_uid: String,
+    pub package_uid: String,
+    pub package_name: String,
+}
+
+/// Source code schema as it is returned by designer service.
+///
+/// The schema is kept as raw json to send back all properties unchanged on save.
+#[derive(Debug, Deserialize)]
+pub struct SourceCodeSchema(Value);
+
+impl SourceCodeSchema {
+    pub fn body(&self) -> &str {
+        self.0["body"].as_str().unwrap_or_default()
+    }
+
+    pub fn set_body(&mut self, body: String) {
+        self.0["body"] = Value::String(body);
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GetSchemaResponse {
+    #[serde(flatten)]
+    base: StandardServiceResponse,
+
+    schema: Option<SourceCodeSchema>,
+}
+
+impl GetSchemaResponse {
+    fn into_result(self) -> Result<SourceCodeSchema, CrtClientError> {
+        self.base.into_result()?;
+
+        Ok(self
+            .schema
+            .expect("GetSchema response success, but schema is not received"))
+    }
+}
@@ -0,0 +1,516 @@
+use crate::app::CrtDbType;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SqlBuilderError {
+    #[error("sql text value {0:?} contains control characters")]
+    InvalidText(String),
+
+    #[error("sql uuid value {0:?} is not a valid uuid")]
+    InvalidUuid(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SqlValue {
+    Text(String),
+    Uuid(String),
+    Bool(bool),
+    Int(i64),
+    Null,
+}
+
+impl SqlValue {
+    pub fn text(value: &str) -> Result<Self, SqlBuilderError> {
+        if value.chars().any(char::is_control) {
+            return Err(SqlBuilderError::InvalidText(value.to_owned()));
+        }
+
+        Ok(SqlValue::Text(value.to_owned()))
+    }
+
+    /// Uuid value is stored in the canonical hyphenated form, as braced or simple
+    /// forms accepted by the parser are not understood by every database.
+    pub fn uuid(value: &str) -> Result<Self, SqlBuilderError> {
+        uuid::Uuid::try_parse(value)
+            .map(|parsed| SqlValue::Uuid(parsed.hyphenated().to_string()))
+            .map_err(|_| SqlBuilderError::InvalidUuid(value.to_owned()))
+    }
+
+    fn render(&self, db_type: CrtDbType) -> String {
+        match (self, db_type) {
+            (SqlValue::Text(value), CrtDbType::MsSql) => {
+                format!("N'{}'", value.replace('\'', "''"))
+            }
+            (SqlValue::Text(value), _) => format!("'{}'", value.replace('\'', "''")),
+            (SqlValue::Uuid(value), _) => format!("'{value}'"),
+            (SqlValue::Bool(value), CrtDbType::MsSql) => (*value as u8).to_string(),
+            (SqlValue::Bool(true), _) => "True".to_owned(),
+            (SqlValue::Bool(false), _) => "False".to_owned(),
+            (SqlValue::Int(value), _) => value.to_string(),
+            (SqlValue::Null, _) => "NULL".to_owned(),
+        }
+    }
+}
+
+fn quote_ident(ident: &str) -> String {
+    debug_assert!(
+        !ident.contains('"'),
+        "sql identifier must not contain quotes"
+    );
+
+    format!("\"{ident}\"")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SqlColumn {
+    table_alias: Option<&'static str>,
+    name: &'static str,
+}
+
+pub fn col(name: &'static str) -> SqlColumn {
+    SqlColumn {
+        table_alias: None,
+        name,
+    }
+}
+
+/// Column qualified by table alias, e.g. `p."Name"`.
+pub fn col_of(table_alias: &'static str, name: &'static str) -> SqlColumn {
+    SqlColumn {
+        table_alias: Some(table_alias),
+        name,
+    }
+}
+
+impl SqlColumn {
+    pub fn eq(self, value: SqlValue) -> SqlCondition {
+        SqlCondition::Eq(self, value)
+    }
+
+    pub fn eq_col(self, other: SqlColumn) -> SqlCondition {
+        SqlCondition::ColumnEq(self, other)
+    }
+
+    pub fn in_select(self, select: SqlSelect) -> SqlCondition {
+        SqlCondition::In(self, Box::new(select))
+    }
+
+    fn render(&self) -> String {
+        match self.table_alias {
+            Some(alias) => format!("{alias}.{}", quote_ident(self.name)),
+            None => quote_ident(self.name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SqlCondition {
+    Eq(SqlColumn, SqlValue),
+    ColumnEq(SqlColumn, SqlColumn),
+    In(SqlColumn, Box<SqlSelect>),
+    NotExists(Box<SqlSelect>),
+    And(Vec<SqlCondition>),
+}
+
+impl SqlCondition {
+    pub fn not_exists(select: SqlSelect) -> Self {
+        SqlCondition::NotExists(Box::new(select))
+    }
+
+    pub fn and(self, other: SqlCondition) -> Self {
+        match self {
+            SqlCondition::And(mut conditions) => {
+                conditions.push(other);
+                SqlCondition::And(conditions)
+            }
+            condition => SqlCondition::And(vec![condition, other]),
+        }
+    }
+
+    fn render(&self, db_type: CrtDbType) -> String {
+        match self {
+            SqlCondition::Eq(column, SqlValue::Null) => format!("{} IS NULL", column.render()),
+            SqlCondition::Eq(column, value) => {
+                format!("{} = {}", column.render(), value.render(db_type))
+            }
+            SqlCondition::ColumnEq(left, right) => {
+                format!("{} = {}", left.render(), right.render())
+            }
+            SqlCondition::In(column, select) => {
+                format!("{} IN ({})", column.render(), select.render(db_type))
+            }
+            SqlCondition::NotExists(select) => format!("NOT EXISTS ({})", select.render(db_type)),
+            SqlCondition::And(conditions) => conditions
+                .iter()
+                .map(|c| match c {
+                    SqlCondition::And(_) => format!("({})", c.render(db_type)),
+                    _ => c.render(db_type),
+                })
+                .collect::<Vec<_>>()
+                .join(" AND "),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SqlTable {
+    name: &'static str,
+    alias: Option<&'static str>,
+}
+
+pub fn table(name: &'static str) -> SqlTable {
+    SqlTable { name, alias: None }
+}
+
+impl SqlTable {
+    pub fn alias(self, alias: &'static str) -> Self {
+        Self {
+            alias: Some(alias),
+            ..self
+        }
+    }
+
+    fn render(&self) -> String {
+        match self.alias {
+            Some(alias) => format!("{} {alias}", quote_ident(self.name)),
+            None => quote_ident(self.name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SqlSelectItem {
+    Column(SqlColumn, Option<&'static str>),
+    Value(SqlValue),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlSelect {
+    items: Vec<SqlSelectItem>,
+    from: SqlTable,
+    joins: Vec<(SqlTable, SqlCondition)>,
+    filter: Option<SqlCondition>,
+}
+
+impl SqlSelect {
+    pub fn from(table: SqlTable) -> Self {
+        Self {
+            items: vec![],
+            from: table,
+            joins: vec![],
+            filter: None,
+        }
+    }
+
+    pub fn column(mut self, column: SqlColumn) -> Self {
+        self.items.push(SqlSelectItem::Column(column, None));
+        self
+    }
+
+    pub fn column_as(mut self, column: SqlColumn, alias: &'static str) -> Self {
+        self.items.push(SqlSelectItem::Column(column, Some(alias)));
+        self
+    }
+
+    pub fn value(mut self, value: SqlValue) -> Self {
+        self.items.push(SqlSelectItem::Value(value));
+        self
+    }
+
+    pub fn inner_join(mut self, table: SqlTable, on: SqlCondition) -> Self {
+        self.joins.push((table, on));
+        self
+    }
+
+    pub fn filter(mut self, condition: SqlCondition) -> Self {
+        self.filter = Some(condition);
+        self
+    }
+
+    fn render(&self, db_type: CrtDbType) -> String {
+        let items = self
+            .items
+            .iter()
+            .map(|item| match item {
+                SqlSelectItem::Column(column, Some(alias)) => {
+                    format!("{} AS {}", column.render(), quote_ident(alias))
+                }
+                SqlSelectItem::Column(column, None) => column.render(),
+                SqlSelectItem::Value(value) => value.render(db_type),
+            })
+            .collect::<Vec<_>>();
+
+        let mut sql = format!(
+            "SELECT {} FROM {}",
+            match items.is_empty() {
+                true => "*".to_owned(),
+                false => items.join(", "),
+            },
+            self.from.render()
+        );
+
+        for (table, on) in &self.joins {
+            sql.push_str(&format!(
+                " INNER JOIN {} ON {}",
+                table.render(),
+                on.render(db_type)
+            ));
+        }
+
+        push_where(&mut sql, self.filter.as_ref(), db_type);
+
+        sql
+    }
+}
+
+/// Small typed SQL builder used by [`super::SqlScripts`] to avoid formatting raw user input into queries.
+///
+/// Identifiers (tables, columns, aliases) are `&'static str` and are always quoted,
+/// values are validated on creation and escaped during rendering for the target [`CrtDbType`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SqlStatement {
+    Select(SqlSelect),
+    Update {
+        table: SqlTable,
+        set: Vec<(&'static str, SqlValue)>,
+        filter: Option<SqlCondition>,
+    },
+    Delete {
+        table: SqlTable,
+        filter: Option<SqlCondition>,
+    },
+}
+
+impl SqlStatement {
+    pub fn update(table: SqlTable) -> Self {
+        SqlStatement::Update {
+            table,
+            set: vec![],
+            filter: None,
+        }
+    }
+
+    pub fn delete(table: SqlTable) -> Self {
+        SqlStatement::Delete {
+            table,
+            filter: None,
+        }
+    }
+
+    /// Adds `column = value` assignment, ignored for non-update statements.
+    pub fn set(mut self, column: &'static str, value: SqlValue) -> Self {
+        if let SqlStatement::Update { set, .. } = &mut self {
+            set.push((column, value));
+        }
+
+        self
+    }
+
+    pub fn filter(self, condition: SqlCondition) -> Self {
+        match self {
+            SqlStatement::Select(select) => SqlStatement::Select(select.filter(condition)),
+            SqlStatement::Update { table, set, .. } => SqlStatement::Update {
+                table,
+                set,
+                filter: Some(condition),
+            },
+            SqlStatement::Delete { table, .. } => SqlStatement::Delete {
+                table,
+                filter: Some(condition),
+            },
+        }
+    }
+
+    pub fn render(&self, db_type: CrtDbType) -> String {
+        match self {
+            SqlStatement::Select(select) => select.render(db_type),
+            SqlStatement::Update { table, set, filter } => {
+                let mut sql = format!(
+                    "UPDATE {} SET {}",
+                    table.render(),
+                    set.iter()
+                        .map(|(column, value)| format!(
+                            "{} = {}",
+                            quote_ident(column),
+                            value.render(db_type)
+                        ))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+
+                push_where(&mut sql, filter.as_ref(), db_type);
+
+                sql
+            }
+            SqlStatement::Delete { table, filter } => {
+                let mut sql = format!("DELETE FROM {}", table.render());
+
+                push_where(&mut sql, filter.as_ref(), db_type);
+
+                sql
+            }
+        }
+    }
+}
+
+impl From<SqlSelect> for SqlStatement {
+    fn from(value: SqlSelect) -> Self {
+        SqlStatement::Select(value)
+    }
+}
+
+fn push_where(sql: &mut String, filter: Option<&SqlCondition>, db_type: CrtDbType) {
+    if let Some(filter) = filter {
+        sql.push_str(" WHERE ");
+        sql.push_str(&filter.render(db_type));
+    }
+}
+
+/// Renders statements separated by `;` as a single script for the SQL runner.
+pub fn render_batch(statements: &[SqlStatement], db_type: CrtDbType) -> String {
+    statements
+        .iter()
+        .map(|statement| format!("{};\n", statement.render(db_type)))
+        .collect()
+}
+
+/// Renders statements as a single script which is executed in one transaction,
+/// so a failed statement rolls back the previous ones.
+pub fn render_transaction(statements: &[SqlStatement], db_type: CrtDbType) -> String {
+    let batch = render_batch(statements, db_type);
+
+    match db_type {
+        CrtDbType::MsSql => format!("SET XACT_ABORT ON;\nBEGIN TRAN;\n{batch}COMMIT TRAN;\n"),
+        CrtDbType::Postgres => format!("BEGIN;\n{batch}COMMIT;\n"),
+        CrtDbType::Oracle => {
+            format!("BEGIN\n{batch}COMMIT;\nEXCEPTION WHEN OTHERS THEN\nROLLBACK;\nRAISE;\nEND;\n")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package_ids(name: &str) -> SqlSelect {
+        SqlSelect::from(table("SysPackage"))
+            .column(col("Id"))
+            .filter(col("Name").eq(SqlValue::text(name).unwrap()))
+    }
+
+    #[test]
+    fn text_values_are_escaped_per_dialect() {
+        let statement = SqlStatement::update(table("SysPackage"))
+            .set("InstallType", SqlValue::Int(1))
+            .filter(col("Name").eq(SqlValue::text("Usr'; DROP TABLE \"SysPackage\"; --").unwrap()));
+
+        assert_eq!(
+            statement.render(CrtDbType::MsSql),
+            r#"UPDATE "SysPackage" SET "InstallType" = 1 WHERE "Name" = N'Usr''; DROP TABLE "SysPackage"; --'"#
+        );
+        assert_eq!(
+            statement.render(CrtDbType::Postgres),
+            r#"UPDATE "SysPackage" SET "InstallType" = 1 WHERE "Name" = 'Usr''; DROP TABLE "SysPackage"; --'"#
+        );
+        assert_eq!(
+            statement.render(CrtDbType::Oracle),
+            r#"UPDATE "SysPackage" SET "InstallType" = 1 WHERE "Name" = 'Usr''; DROP TABLE "SysPackage"; --'"#
+        );
+    }
+
+    #[test]
+    fn bool_values_are_rendered_per_dialect() {
+        let statement = SqlStatement::update(table("SysSchema"))
+            .set("IsChanged", SqlValue::Bool(false))
+            .set("IsLocked", SqlValue::Bool(true))
+            .filter(col("SysPackageId").in_select(package_ids("UsrPackage")));
+
+        assert_eq!(
+            statement.render(CrtDbType::MsSql),
+            r#"UPDATE "SysSchema" SET "IsChanged" = 0, "IsLocked" = 1 WHERE "SysPackageId" IN (SELECT "Id" FROM "SysPackage" WHERE "Name" = N'UsrPackage')"#
+        );
+        assert_eq!(
+            statement.render(CrtDbType::Postgres),
+            r#"UPDATE "SysSchema" SET "IsChanged" = False, "IsLocked" = True WHERE "SysPackageId" IN (SELECT "Id" FROM "SysPackage" WHERE "Name" = 'UsrPackage')"#
+        );
+        assert_eq!(
+            statement.render(CrtDbType::Oracle),
+            r#"UPDATE "SysSchema" SET "IsChanged" = False, "IsLocked" = True WHERE "SysPackageId" IN (SELECT "Id" FROM "SysPackage" WHERE "Name" = 'UsrPackage')"#
+        );
+    }
+
+    #[test]
+    fn select_with_joins_and_guard_is_rendered() {
+        let select = SqlSelect::from(table("SysPackageDependency").alias("d"))
+            .column_as(col_of("p", "Name"), "Name")
+            .inner_join(
+                table("SysPackage").alias("p"),
+                col_of("p", "Id").eq_col(col_of("d", "SysPackageId")),
+            )
+            .filter(
+                col_of("p", "Name")
+                    .eq(SqlValue::text("UsrPackage").unwrap())
+                    .and(SqlCondition::not_exists(
+                        SqlSelect::from(table("SysSchema"))
+                            .value(SqlValue::Int(1))
+                            .filter(col("Name").eq(SqlValue::Null)),
+                    )),
+            );
+
+        assert_eq!(
+            render_batch(&[select.into()], CrtDbType::Postgres),
+            "SELECT p.\"Name\" AS \"Name\" FROM \"SysPackageDependency\" d \
+            INNER JOIN \"SysPackage\" p ON p.\"Id\" = d.\"SysPackageId\" \
+            WHERE p.\"Name\" = 'UsrPackage' AND NOT EXISTS (SELECT 1 FROM \"SysSchema\" WHERE \"Name\" IS NULL);\n"
+        );
+    }
+
+    #[test]
+    fn invalid_values_are_rejected() {
+        assert_eq!(
+            SqlValue::text("Usr\0Package"),
+            Err(SqlBuilderError::InvalidText("Usr\0Package".to_owned()))
+        );
+        assert_eq!(
+            SqlValue::uuid("' OR 1=1 --"),
+            Err(SqlBuilderError::InvalidUuid("' OR 1=1 --".to_owned()))
+        );
+        assert_eq!(
+            SqlValue::uuid("ae8519c2-2aac-4a00-aa61-b0ffaac99ea3")
+                .unwrap()
+                .render(CrtDbType::MsSql),
+            "'ae8519c2-2aac-4a00-aa61-b0ffaac99ea3'"
+        );
+        assert_eq!(
+            SqlValue::uuid("{AE8519C2-2AAC-4A00-AA61-B0FFAAC99EA3}"),
+            Ok(SqlValue::Uuid(
+                "ae8519c2-2aac-4a00-aa61-b0ffaac99ea3".to_owned()
+            ))
+        );
+        assert_eq!(
+            SqlValue::uuid("ae8519c22aac4a00aa61b0ffaac99ea3")
+                .unwrap()
+                .render(CrtDbType::Postgres),
+            "'ae8519c2-2aac-4a00-aa61-b0ffaac99ea3'"
+        );
+    }
+
+    #[test]
+    fn transaction_is_rendered_per_dialect() {
+        let statements = [SqlStatement::delete(table("SysPackage"))
+            .filter(col("Name").eq(SqlValue::text("UsrPackage").unwrap()))];
+
+        assert_eq!(
+            render_transaction(&statements, CrtDbType::MsSql),
+            "SET XACT_ABORT ON;\nBEGIN TRAN;\nDELETE FROM \"SysPackage\" WHERE \"Name\" = N'UsrPackage';\nCOMMIT TRAN;\n"
+        );
+        assert_eq!(
+            render_transaction(&statements, CrtDbType::Postgres),
+            "BEGIN;\nDELETE FROM \"SysPackage\" WHERE \"Name\" = 'UsrPackage';\nCOMMIT;\n"
+        );
+        assert!(
+            render_transaction(&statements, CrtDbType::Oracle)
+                .ends_with("COMMIT;\nEXCEPTION WHEN OTHERS THEN\nROLLBACK;\nRAISE;\nEND;\n")
+        );
+    }
+}
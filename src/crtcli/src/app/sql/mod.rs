@@ -1,3 +1,6 @@
+mod builder;
+pub use builder::*;
+
 mod runner;
 pub use runner::*;
 
@@ -133,37 +133,18 @@ impl SqlRunner for SqlConsoleSqlRunner {
             rows_affected: response_body.rows_affected,
             table: match response_body.query_results {
                 None => None,
-                Some(query_result) => {
-                    match query_result.len() {
-                        0 => Some(Vec::new()),
-                        len => {
-                            if len > 1 {
-                                eprintln!(
-                                    "more than one table returned, this currently unsupported, the first table will out"
-                                );
-                            }
-
-                            todo!()
-
-                            //todo column strings should be separated
-
-                            // let table = query_result.remove(0);
-                            //
-                            // table.rows
-                            //     .into_iter()
-                            //     .map(|r| {
-                            //         let map = HashMap::new();
-                            //
-                            //         for (i, rv) in r.into_iter().enumerate() {
-                            //
-                            //         }
-                            //
-                            //
-                            //     })
-                            //     .collect()
+                Some(mut query_results) => match query_results.len() {
+                    0 => Some(Vec::new()),
+                    len => {
+                        if len > 1 {
+                            eprintln!(
+                                "more than one table returned, this currently unsupported, the first table will out"
+                            );
                         }
+
+                        Some(query_results.remove(0).into_table())
                     }
-                }
+                },
             },
         })
     }
@@ -201,6 +182,23 @@ struct SqlConsoleQueryResult {
     rows: Vec<Vec<String>>,
 }
 
+impl SqlConsoleQueryResult {
+    fn into_table(self) -> Vec<serde_json::Map<String, serde_json::Value>> {
+        let columns = self.columns;
+
+        self.rows
+            .into_iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .cloned()
+                    .zip(row.into_iter().map(serde_json::Value::String))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
 pub struct AutodetectSqlRunner;
 
 macro_rules! next_if_not_found {
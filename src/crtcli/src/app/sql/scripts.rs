@@ -1,4 +1,6 @@
-use crate::app::{CrtClient, CrtClientError, CrtDbType};
+use crate::app::source_code_schema::SourceCodeSchemaInfo;
+use crate::app::sql::builder::*;
+use crate::app::{CrtClient, CrtClientError};
 
 pub struct SqlScripts<'c>(&'c CrtClient);
 
@@ -7,136 +9,408 @@ impl<'c> SqlScripts<'c> {
         Self(client)
     }
 
-    pub async fn mark_package_as_not_changed(
+    async fn execute(&self, statements: &[SqlStatement]) -> Result<u64, CrtClientError> {
+        let query = render_batch(statements, self.0.db_type().await?);
+
+        Ok(self.0.sql(&query).await?.rows_affected)
+    }
+
+    async fn execute_in_transaction(
         &self,
-        package_uid: &str,
+        statements: &[SqlStatement],
     ) -> Result<u64, CrtClientError> {
-        let query = match self.0.db_type().await? {
-            CrtDbType::MsSql => format!(
-                r#"UPDATE "SysPackage"
-                SET "IsChanged" = 0, "IsLocked" = 0 
-                WHERE "UId" = '{package_uid}';
-                
-                UPDATE "SysSchema" 
-                SET "IsChanged" = 0, "IsLocked" = 0 
-                WHERE "SysPackageId" IN (SELECT "Id" FROM "SysPackage" WHERE "UId" = '{package_uid}');
-                
-                UPDATE "SysPackageSchemaData" 
-                SET "IsChanged" = 0, "IsLocked" = 0 
-                WHERE "SysPackageId" IN (SELECT "Id" FROM "SysPackage" WHERE "UId" = '{package_uid}');
-                
-                UPDATE "SysPackageSqlScript" 
-                SET "IsChanged" = 0, "IsLocked" = 0 
-                WHERE "SysPackageId" IN (SELECT "Id" FROM "SysPackage" WHERE "UId" = '{package_uid}');    
-                            
-                UPDATE "SysPackageReferenceAssembly" 
-                SET "IsChanged" = 0, "IsLocked" = 0 
-                WHERE "SysPackageId" IN (SELECT "Id" FROM "SysPackage" WHERE "UId" = '{package_uid}');
-                "#
-            ),
-            CrtDbType::Oracle | CrtDbType::Postgres => format!(
-                r#"UPDATE "SysPackage"
-                SET "IsChanged" = False, "IsLocked" = False 
-                WHERE "UId" = '{package_uid}';
-                
-                UPDATE "SysSchema" 
-                SET "IsChanged" = False, "IsLocked" = False 
-                WHERE "SysPackageId" IN (SELECT "Id" FROM "SysPackage" WHERE "UId" = '{package_uid}');
-                
-                UPDATE "SysPackageSchemaData" 
-                SET "IsChanged" = False, "IsLocked" = False 
-                WHERE "SysPackageId" IN (SELECT "Id" FROM "SysPackage" WHERE "UId" = '{package_uid}');
-                
-                UPDATE "SysPackageSqlScript" 
-                SET "IsChanged" = False, "IsLocked" = False 
-                WHERE "SysPackageId" IN (SELECT "Id" FROM "SysPackage" WHERE "UId" = '{package_uid}');
-                
-                UPDATE "SysPackageReferenceAssembly" 
-                SET "IsChanged" = False, "IsLocked" = False 
-                WHERE "SysPackageId" IN (SELECT "Id" FROM "SysPackage" WHERE "UId" = '{package_uid}');
-                "#
-            ),
-        };
+        let query = render_transaction(statements, self.0.db_type().await?);
 
         Ok(self.0.sql(&query).await?.rows_affected)
     }
 
+    async fn query(
+        &self,
+        select: SqlSelect,
+    ) -> Result<Vec<serde_json::Map<String, serde_json::Value>>, CrtClientError> {
+        let query = render_batch(&[select.into()], self.0.db_type().await?);
+
+        Ok(self.0.sql(&query).await?.table.unwrap_or_default())
+    }
+
+    pub async fn mark_package_as_not_changed(
+        &self,
+        package_uid: &str,
+    ) -> Result<u64, CrtClientError> {
+        self.execute(&mark_package_as_not_changed_statements(package_uid)?)
+            .await
+    }
+
     pub async fn delete_package_localizations(
         &self,
         package_uid: &str,
     ) -> Result<u64, CrtClientError> {
-        let query = format!(
-            r#"DELETE FROM "SysLocalizableValue" 
-            WHERE "SysPackageId" IN (
-                SELECT "Id" FROM "SysPackage" WHERE "UId" = '{package_uid}'
-            );
-            
-            DELETE FROM "SysPackageResourceChecksum" 
-            WHERE "SysPackageId" IN (
-                SELECT "Id" FROM "SysPackage" WHERE "UId" = '{package_uid}'
-            );
-            
-            DELETE FROM "SysPackageDataLcz" WHERE "SysPackageSchemaDataId" IN (
-                SELECT "Id" FROM "SysPackageSchemaData" WHERE "SysPackageId" IN (
-                    SELECT "Id" FROM "SysPackage" WHERE "UId" = '{package_uid}'
-                )
-            );
-            
-            DELETE FROM "SysPackageSchemaData" 
-            WHERE "SysPackageId" IN (
-                SELECT "Id" FROM "SysPackage" WHERE "UId" = '{package_uid}'
-            );
-            "#
-        );
+        let package_ids = package_ids_by_uid(package_uid)?;
 
-        Ok(self.0.sql(&query).await?.rows_affected)
+        self.execute(&[
+            SqlStatement::delete(table("SysLocalizableValue"))
+                .filter(col("SysPackageId").in_select(package_ids.clone())),
+            SqlStatement::delete(table("SysPackageResourceChecksum"))
+                .filter(col("SysPackageId").in_select(package_ids.clone())),
+            SqlStatement::delete(table("SysPackageDataLcz")).filter(
+                col("SysPackageSchemaDataId").in_select(
+                    SqlSelect::from(table("SysPackageSchemaData"))
+                        .column(col("Id"))
+                        .filter(col("SysPackageId").in_select(package_ids.clone())),
+                ),
+            ),
+            SqlStatement::delete(table("SysPackageSchemaData"))
+                .filter(col("SysPackageId").in_select(package_ids)),
+        ])
+        .await
     }
 
     pub async fn clear_schema_content(&self, package_uid: &str) -> Result<u64, CrtClientError> {
-        let query = format!(
-            r#"DELETE FROM "SysSchemaContent" WHERE "SysSchemaId" IN (
-                SELECT "Id" FROM "SysSchema" WHERE "SysPackageId" IN (
-                    SELECT "Id" FROM "SysPackage" WHERE "UId" = '{package_uid}'
-                )
-            );
-            
-            UPDATE "SysSchema"
-            SET "Checksum" = '',
-                "MetaData" = NULL,
-                "Descriptor" = NULL,
-                "CreatedOn" = NULL,
-                "ModifiedById" = NULL,
-                "CreatedById" = NULL,
-                "ModifiedOn" = NULL,
-                "ClientContentModifiedOn" = NULL
-            WHERE "SysPackageId" IN (
-                SELECT "Id" FROM "SysPackage" WHERE "UId" = '{package_uid}'
-            );
-            "#
-        );
+        let package_ids = package_ids_by_uid(package_uid)?;
 
-        Ok(self.0.sql(&query).await?.rows_affected)
+        self.execute(&[
+            SqlStatement::delete(table("SysSchemaContent")).filter(
+                col("SysSchemaId").in_select(
+                    SqlSelect::from(table("SysSchema"))
+                        .column(col("Id"))
+                        .filter(col("SysPackageId").in_select(package_ids.clone())),
+                ),
+            ),
+            SqlStatement::update(table("SysSchema"))
+                .set("Checksum", SqlValue::text("")?)
+                .set("MetaData", SqlValue::Null)
+                .set("Descriptor", SqlValue::Null)
+                .set("CreatedOn", SqlValue::Null)
+                .set("ModifiedById", SqlValue::Null)
+                .set("CreatedById", SqlValue::Null)
+                .set("ModifiedOn", SqlValue::Null)
+                .set("ClientContentModifiedOn", SqlValue::Null)
+                .filter(col("SysPackageId").in_select(package_ids)),
+        ])
+        .await
     }
 
     pub async fn lock_package(&self, package_name: &str) -> Result<u64, CrtClientError> {
-        let query = format!(
-            r#"UPDATE "SysPackage" 
-            SET "InstallType" = 1
-            WHERE "Name" = '{package_name}';
-            "#
+        self.execute(&[SqlStatement::update(table("SysPackage"))
+            .set("InstallType", SqlValue::Int(1))
+            .filter(col("Name").eq(SqlValue::text(package_name)?))])
+            .await
+    }
+
+    pub async fn unlock_package(&self, package_name: &str) -> Result<u64, CrtClientError> {
+        self.execute(&[SqlStatement::update(table("SysPackage"))
+            .set("InstallType", SqlValue::Int(0))
+            .filter(col("Name").eq(SqlValue::text(package_name)?))])
+            .await
+    }
+
+    /// Returns names of the packages which have the specified package in DependsOn.
+    pub async fn find_package_dependents(
+        &self,
+        package_name: &str,
+    ) -> Result<Vec<String>, CrtClientError> {
+        let table = self
+            .query(
+                SqlSelect::from(table("SysPackageDependency").alias("d"))
+                    .column_as(col_of("p", "Name"), "Name")
+                    .inner_join(
+                        table("SysPackage").alias("p"),
+                        col_of("p", "Id").eq_col(col_of("d", "SysPackageId")),
+                    )
+                    .inner_join(
+                        table("SysPackage").alias("t"),
+                        col_of("t", "Id").eq_col(col_of("d", "DependOnPackageId")),
+                    )
+                    .filter(col_of("t", "Name").eq(SqlValue::text(package_name)?)),
+            )
+            .await?;
+
+        Ok(table
+            .into_iter()
+            .filter_map(|row| {
+                row.iter()
+                    .find(|(k, _)| k.eq_ignore_ascii_case("Name"))
+                    .and_then(|(_, v)| v.as_str())
+                    .map(str::to_owned)
+            })
+            .collect())
+    }
+
+    /// Returns names of the packages which the package depends on.
+    pub async fn find_package_dependencies(
+        &self,
+        package_name: &str,
+    ) -> Result<Vec<String>, CrtClientError> {
+        let table = self
+            .query(
+                SqlSelect::from(table("SysPackageDependency").alias("d"))
+                    .column_as(col_of("t", "Name"), "Name")
+                    .inner_join(
+                        table("SysPackage").alias("p"),
+                        col_of("p", "Id").eq_col(col_of("d", "SysPackageId")),
+                    )
+                    .inner_join(
+                        table("SysPackage").alias("t"),
+                        col_of("t", "Id").eq_col(col_of("d", "DependOnPackageId")),
+                    )
+                    .filter(col_of("p", "Name").eq(SqlValue::text(package_name)?)),
+            )
+            .await?;
+
+        Ok(table
+            .into_iter()
+            .filter_map(|row| {
+                row.iter()
+                    .find(|(k, _)| k.eq_ignore_ascii_case("Name"))
+                    .and_then(|(_, v)| v.as_str())
+                    .map(str::to_owned)
+            })
+            .collect())
+    }
+
+    /// Deletes the package and its content from the database in a single transaction.
+    ///
+    /// Unless `ignore_deps` is set, each statement is guarded to affect the package only
+    /// if no other package depends on it, so nothing is deleted for the required package.
+    /// Affected rows are not reported for the transaction, use [`Self::package_exists`] to check the result.
+    pub async fn delete_package(
+        &self,
+        package_name: &str,
+        ignore_deps: bool,
+    ) -> Result<(), CrtClientError> {
+        self.execute_in_transaction(&delete_package_statements(package_name, ignore_deps)?)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn package_exists(&self, package_name: &str) -> Result<bool, CrtClientError> {
+        let table = self
+            .query(
+                SqlSelect::from(table("SysPackage"))
+                    .column(col("Id"))
+                    .filter(col("Name").eq(SqlValue::text(package_name)?)),
+            )
+            .await?;
+
+        Ok(!table.is_empty())
+    }
+
+    /// Returns names of the schemas marked as changed, which sources are not generated yet.
+    pub async fn find_changed_schemas(&self) -> Result<Vec<String>, CrtClientError> {
+        let table = self
+            .query(
+                SqlSelect::from(table("SysSchema"))
+                    .column(col("Name"))
+                    .filter(col("IsChanged").eq(SqlValue::Bool(true))),
+            )
+            .await?;
+
+        Ok(table
+            .into_iter()
+            .filter_map(|row| {
+                row.iter()
+                    .find(|(k, _)| k.eq_ignore_ascii_case("Name"))
+                    .and_then(|(_, v)| v.as_str())
+                    .map(str::to_owned)
+            })
+            .collect())
+    }
+
+    pub async fn find_source_code_schemas(
+        &self,
+        schema_name: &str,
+    ) -> Result<Vec<SourceCodeSchemaInfo>, CrtClientError> {
+        let table = self
+            .query(
+                SqlSelect::from(table("SysSchema").alias("s"))
+                    .column_as(col_of("s", "UId"), "SchemaUId")
+                    .column_as(col_of("p", "UId"), "PackageUId")
+                    .column_as(col_of("p", "Name"), "PackageName")
+                    .inner_join(
+                        table("SysPackage").alias("p"),
+                        col_of("p", "Id").eq_col(col_of("s", "SysPackageId")),
+                    )
+                    .filter(col_of("s", "Name").eq(SqlValue::text(schema_name)?).and(
+                        col_of("s", "ManagerName").eq(SqlValue::text("SourceCodeSchemaManager")?),
+                    )),
+            )
+            .await?;
+
+        Ok(table
+            .into_iter()
+            .filter_map(|row| {
+                let get = |key: &str| {
+                    row.iter()
+                        .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                        .and_then(|(_, v)| v.as_str())
+                        .map(str::to_owned)
+                };
+
+                Some(SourceCodeSchemaInfo {
+                    schema_uid: get("SchemaUId")?,
+                    package_uid: get("PackageUId")?,
+                    package_name: get("PackageName")?,
+                })
+            })
+            .collect())
+    }
+}
+
+fn package_ids_by_uid(package_uid: &str) -> Result<SqlSelect, SqlBuilderError> {
+    Ok(SqlSelect::from(table("SysPackage"))
+        .column(col("Id"))
+        .filter(col("UId").eq(SqlValue::uuid(package_uid)?)))
+}
+
+fn mark_package_as_not_changed_statements(
+    package_uid: &str,
+) -> Result<Vec<SqlStatement>, SqlBuilderError> {
+    let package_ids = package_ids_by_uid(package_uid)?;
+
+    let mark_not_changed = |statement: SqlStatement| {
+        statement
+            .set("IsChanged", SqlValue::Bool(false))
+            .set("IsLocked", SqlValue::Bool(false))
+    };
+
+    Ok(vec![
+        mark_not_changed(SqlStatement::update(table("SysPackage")))
+            .filter(col("UId").eq(SqlValue::uuid(package_uid)?)),
+        mark_not_changed(SqlStatement::update(table("SysSchema")))
+            .filter(col("SysPackageId").in_select(package_ids.clone())),
+        mark_not_changed(SqlStatement::update(table("SysPackageSchemaData")))
+            .filter(col("SysPackageId").in_select(package_ids.clone())),
+        mark_not_changed(SqlStatement::update(table("SysPackageSqlScript")))
+            .filter(col("SysPackageId").in_select(package_ids.clone())),
+        mark_not_changed(SqlStatement::update(table("SysPackageReferenceAssembly")))
+            .filter(col("SysPackageId").in_select(package_ids)),
+    ])
+}
+
+fn delete_package_statements(
+    package_name: &str,
+    ignore_deps: bool,
+) -> Result<Vec<SqlStatement>, SqlBuilderError> {
+    let mut package_filter = col_of("p", "Name").eq(SqlValue::text(package_name)?);
+
+    if !ignore_deps {
+        package_filter = package_filter.and(SqlCondition::not_exists(
+            SqlSelect::from(table("SysPackageDependency").alias("d"))
+                .value(SqlValue::Int(1))
+                .filter(col_of("d", "DependOnPackageId").eq_col(col_of("p", "Id"))),
+        ));
+    }
+
+    let package_ids = SqlSelect::from(table("SysPackage").alias("p"))
+        .column(col_of("p", "Id"))
+        .filter(package_filter);
+
+    let by_package = |name: &'static str| {
+        SqlStatement::delete(table(name)).filter(col("SysPackageId").in_select(package_ids.clone()))
+    };
+
+    let ids_by_package = |name: &'static str| {
+        SqlSelect::from(table(name))
+            .column(col("Id"))
+            .filter(col("SysPackageId").in_select(package_ids.clone()))
+    };
+
+    let schema_uids = SqlSelect::from(table("SysSchema"))
+        .column(col("UId"))
+        .filter(col("SysPackageId").in_select(package_ids.clone()));
+
+    Ok(vec![
+        SqlStatement::delete(table("SysModule"))
+            .filter(col("SectionSchemaUId").in_select(schema_uids)),
+        SqlStatement::delete(table("SysSchemaProperty"))
+            .filter(col("SysSchemaId").in_select(ids_by_package("SysSchema"))),
+        SqlStatement::delete(table("SysSchemaContent"))
+            .filter(col("SysSchemaId").in_select(ids_by_package("SysSchema"))),
+        by_package("SysLocalizableValue"),
+        by_package("SysPackageResourceChecksum"),
+        SqlStatement::delete(table("SysPackageDataLcz")).filter(
+            col("SysPackageSchemaDataId").in_select(ids_by_package("SysPackageSchemaData")),
+        ),
+        by_package("SysPackageSchemaData"),
+        by_package("SysPackageSqlScript"),
+        by_package("SysPackageReferenceAssembly"),
+        by_package("SysSchema"),
+        by_package("SysPackageDependency"),
+        SqlStatement::delete(table("SysPackage")).filter(col("Id").in_select(package_ids.clone())),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::CrtDbType;
+
+    const PACKAGE_UID: &str = "ae8519c2-2aac-4a00-aa61-b0ffaac99ea3";
+
+    #[test]
+    fn mark_package_as_not_changed_is_rendered_per_dialect() {
+        let statements = mark_package_as_not_changed_statements(PACKAGE_UID).unwrap();
+
+        let mssql = render_batch(&statements, CrtDbType::MsSql);
+        let postgres = render_batch(&statements, CrtDbType::Postgres);
+        let oracle = render_batch(&statements, CrtDbType::Oracle);
+
+        assert_eq!(
+            mssql.lines().next(),
+            Some(
+                r#"UPDATE "SysPackage" SET "IsChanged" = 0, "IsLocked" = 0 WHERE "UId" = 'ae8519c2-2aac-4a00-aa61-b0ffaac99ea3';"#
+            )
+        );
+        assert_eq!(
+            postgres.lines().next(),
+            Some(
+                r#"UPDATE "SysPackage" SET "IsChanged" = False, "IsLocked" = False WHERE "UId" = 'ae8519c2-2aac-4a00-aa61-b0ffaac99ea3';"#
+            )
         );
+        assert_eq!(postgres, oracle);
+        assert_eq!(mssql.lines().count(), 5);
+    }
 
-        Ok(self.0.sql(&query).await?.rows_affected)
+    #[test]
+    fn package_uid_must_be_valid_uuid() {
+        assert_eq!(
+            mark_package_as_not_changed_statements("x' OR '1'='1").unwrap_err(),
+            SqlBuilderError::InvalidUuid("x' OR '1'='1".to_owned())
+        );
     }
 
-    pub async fn unlock_package(&self, package_name: &str) -> Result<u64, CrtClientError> {
-        let query = format!(
-            r#"UPDATE "SysPackage" 
-            SET "InstallType" = 0
-            WHERE "Name" = '{package_name}';
-            "#
+    #[test]
+    fn delete_package_is_guarded_by_dependents() {
+        let statements = delete_package_statements("UsrPackage", false).unwrap();
+
+        let mssql = render_batch(&statements, CrtDbType::MsSql);
+        let postgres = render_batch(&statements, CrtDbType::Postgres);
+
+        assert_eq!(
+            postgres.lines().last(),
+            Some(
+                r#"DELETE FROM "SysPackage" WHERE "Id" IN (SELECT p."Id" FROM "SysPackage" p WHERE p."Name" = 'UsrPackage' AND NOT EXISTS (SELECT 1 FROM "SysPackageDependency" d WHERE d."DependOnPackageId" = p."Id"));"#
+            )
+        );
+        assert!(
+            mssql
+                .lines()
+                .all(|line| line.contains("p.\"Name\" = N'UsrPackage'"))
         );
+        assert_eq!(mssql.lines().count(), 12);
+    }
 
-        Ok(self.0.sql(&query).await?.rows_affected)
+    #[test]
+    fn delete_package_ignores_dependents_if_requested() {
+        let statements = delete_package_statements("UsrPackage", true).unwrap();
+        let postgres = render_transaction(&statements, CrtDbType::Postgres);
+
+        assert!(!postgres.contains("NOT EXISTS"));
+        assert!(postgres.starts_with("BEGIN;\nDELETE FROM \"SysModule\""));
+        assert!(postgres.contains("DELETE FROM \"SysSchemaProperty\""));
+        assert!(postgres.ends_with(
+            "DELETE FROM \"SysPackage\" WHERE \"Id\" IN (SELECT p.\"Id\" FROM \"SysPackage\" p WHERE p.\"Name\" = 'UsrPackage');\nCOMMIT;\n"
+        ));
     }
 }
@@ -1,10 +1,16 @@
 use crate::app::{CrtClient, CrtClientError, CrtRequestBuilderExt};
+use futures::future::ready;
+use futures::{SinkExt, StreamExt};
 use indexmap::IndexMap;
 use reqwest::Method;
 use serde::Deserialize;
 use std::ops::Deref;
+use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
 use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use tokio_util::bytes::Bytes;
+use tokio_util::io::{CopyToBytes, SinkWriter, StreamReader};
 
 pub struct CrtCliTunnelingService<'c>(&'c CrtClient);
 
@@ -72,6 +78,35 @@ impl<'c> CrtCliTunnelingService<'c> {
 
         Ok(stream)
     }
+
+    /// Connects to the host like [`Self::connect`], but exposes the websocket as a plain byte stream.
+    pub async fn connect_stream(
+        &self,
+        host: &str,
+        port: u16,
+    ) -> Result<impl AsyncRead + AsyncWrite + Unpin + use<>, CrtClientError> {
+        let (ws_write, ws_read) = self.connect(host, port).await?.split();
+
+        let reader = StreamReader::new(ws_read.filter_map(|message| {
+            ready(match message {
+                Ok(Message::Binary(data)) => Some(Ok(data)),
+                Ok(_) => None,
+                Err(err) => Some(Err(std::io::Error::other(err))),
+            })
+        }));
+
+        let writer = SinkWriter::new(CopyToBytes::new(
+            ws_write
+                .with(|data: Bytes| {
+                    ready(Ok::<_, tokio_tungstenite::tungstenite::Error>(
+                        Message::Binary(data),
+                    ))
+                })
+                .sink_map_err(std::io::Error::other),
+        ));
+
+        Ok(tokio::io::join(reader, writer))
+    }
 }
 
 #[derive(Debug, Deserialize)]
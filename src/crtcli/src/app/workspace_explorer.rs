@@ -79,7 +79,6 @@ impl<'c> WorkspaceExplorerService<'c> {
         Ok(response.json().await?)
     }
 
-    #[allow(dead_code)]
     pub async fn generate_modified_schema_sources(&self) -> Result<BaseResponse, CrtClientError> {
         let response = self
             .0
@@ -95,7 +94,6 @@ impl<'c> WorkspaceExplorerService<'c> {
         Ok(response.json().await?)
     }
 
-    #[allow(dead_code)]
     pub async fn generate_required_schemas_sources(&self) -> Result<BaseResponse, CrtClientError> {
         let response = self
             .0
@@ -111,7 +109,6 @@ impl<'c> WorkspaceExplorerService<'c> {
         Ok(response.json().await?)
     }
 
-    #[allow(dead_code)]
     pub async fn generate_all_schemas_sources(&self) -> Result<BaseResponse, CrtClientError> {
         let response = self
             .0
@@ -127,7 +124,6 @@ impl<'c> WorkspaceExplorerService<'c> {
         Ok(response.json().await?)
     }
 
-    #[allow(dead_code)]
     pub async fn generate_all_schemas_sources_background(
         &self,
     ) -> Result<BaseResponse, CrtClientError> {
@@ -160,6 +156,25 @@ impl<'c> WorkspaceExplorerService<'c> {
         response.json::<GetPackagesResponse>().await?.into_result()
     }
 
+    /// Returns all schemas of the workspace, as they are listed in the configuration section.
+    pub async fn get_workspace_items(&self) -> Result<Vec<WorkspaceItem>, CrtClientError> {
+        let response = self
+            .0
+            .request(
+                Method::POST,
+                "0/ServiceModel/WorkspaceExplorerService.svc/GetWorkspaceItems",
+            )
+            .header(reqwest::header::CONTENT_LENGTH, "0")
+            .send_with_session(self.0)
+            .await?
+            .error_for_status()?;
+
+        response
+            .json::<GetWorkspaceItemsResponse>()
+            .await?
+            .into_result()
+    }
+
     pub async fn get_is_file_system_development_mode(&self) -> Result<bool, CrtClientError> {
         let response = self
             .0
@@ -199,6 +214,10 @@ impl BaseResponse {
             .as_ref()
             .is_some_and(|x| x.iter().any(|x| !x.warning))
     }
+
+    pub fn is_failed(&self) -> bool {
+        !self.success && (self.has_any_error() || self.error_info.is_some())
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -246,6 +265,26 @@ pub struct GetPackagesResponseItem {
     uid: String,
 
     name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    maintainer: Option<String>,
+}
+
+impl GetPackagesResponseItem {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    pub fn maintainer(&self) -> Option<&str> {
+        self.maintainer.as_deref()
+    }
 }
 
 impl Display for GetPackagesResponseItem {
@@ -254,6 +293,38 @@ impl Display for GetPackagesResponseItem {
     }
 }
 
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceItem {
+    #[serde(rename = "uId")]
+    pub uid: String,
+
+    pub name: String,
+
+    #[serde(rename = "packageUId")]
+    pub package_uid: String,
+
+    pub package_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GetWorkspaceItemsResponse {
+    #[serde(flatten)]
+    base: StandardServiceResponse,
+
+    items: Option<Vec<WorkspaceItem>>,
+}
+
+impl GetWorkspaceItemsResponse {
+    fn into_result(self) -> Result<Vec<WorkspaceItem>, CrtClientError> {
+        self.base.into_result()?;
+
+        Ok(self
+            .items
+            .expect("GetWorkspaceItems response success, but items property is not received"))
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct GetIsFileDesignModeResponse {
     #[serde(flatten)]
@@ -1,3 +1,4 @@
+use crate::utils::forward_mapping::{ForwardMappingArg, ForwardMappingArgParsingError};
 use anstyle::{AnsiColor, Color, Style};
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -26,11 +27,24 @@ pub struct DotAppConfig {
     pub oauth_url: Option<String>,
     pub oauth_client_id: Option<String>,
     pub oauth_client_secret: Option<String>,
+    pub oauth_scope: Option<String>,
+    pub oauth_audience: Option<String>,
 
     pub insecure: Option<bool>,
 
     #[serde(alias = "netframework")]
     pub net_framework: Option<bool>,
+
+    /// Certificate paths are relative to the directory of the config file
+    pub ca_cert: Option<PathBuf>,
+    pub client_cert: Option<PathBuf>,
+    pub client_key: Option<PathBuf>,
+
+    pub proxy: Option<String>,
+
+    /// Named local port forwarding rules for `app tunnel`, e.g. `db = "5432:db-host:5432"`
+    #[serde(default)]
+    pub tunnels: HashMap<String, String>,
 }
 
 #[derive(Debug, Error)]
@@ -46,7 +60,13 @@ pub enum DotConfigLoadFileError {
 }
 
 #[derive(Debug, Error)]
-pub enum DotConfigValidationError {}
+pub enum DotConfigValidationError {
+    #[error("invalid tunnel '{1}' rule of app '{0}': {2}")]
+    Tunnel(String, String, #[source] ForwardMappingArgParsingError),
+
+    #[error("client_key of app '{0}' is specified without client_cert")]
+    ClientKeyWithoutCert(String),
+}
 
 impl DotConfig {
     // to make this fn public, we should post validate the result
@@ -61,10 +81,14 @@ impl DotConfig {
             DotConfigLoadFileError::Read(config_filepath.as_ref().to_owned(), err)
         })?;
 
-        let config = Self::from_str(&config_str).map_err(|err| {
+        let mut config = Self::from_str(&config_str).map_err(|err| {
             DotConfigLoadFileError::Parse(config_filepath.as_ref().to_owned(), err)
         })?;
 
+        if let Some(config_dir) = config_filepath.as_ref().parent() {
+            config.resolve_relative_paths(config_dir);
+        }
+
         config.validate().map_err(|err| {
             DotConfigLoadFileError::Validate(config_filepath.as_ref().to_owned(), err)
         })?;
@@ -137,7 +161,38 @@ impl DotConfig {
         }
     }
 
+    fn resolve_relative_paths(&mut self, config_dir: &Path) {
+        for app_config in self.apps.values_mut() {
+            for path in [
+                &mut app_config.ca_cert,
+                &mut app_config.client_cert,
+                &mut app_config.client_key,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                if path.is_relative() {
+                    *path = config_dir.join(&path);
+                }
+            }
+        }
+    }
+
     fn validate(&self) -> Result<(), DotConfigValidationError> {
+        for (app_name, app_config) in &self.apps {
+            if app_config.client_key.is_some() && app_config.client_cert.is_none() {
+                return Err(DotConfigValidationError::ClientKeyWithoutCert(
+                    app_name.clone(),
+                ));
+            }
+
+            for (tunnel_name, tunnel) in &app_config.tunnels {
+                ForwardMappingArg::try_from(tunnel.as_str()).map_err(|err| {
+                    DotConfigValidationError::Tunnel(app_name.clone(), tunnel_name.clone(), err)
+                })?;
+            }
+        }
+
         Ok(())
     }
 
@@ -149,3 +204,59 @@ impl DotConfig {
         self.default_app.as_ref()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_app_tunnels() {
+        let config = DotConfig::from_str(
+            r#"
+            [apps.dev]
+            url = "https://dev.local"
+
+            [apps.dev.tunnels]
+            db = "5432:db-host:5432"
+            redis = "6379:redis:port"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.apps()["dev"].tunnels["db"], "5432:db-host:5432");
+        assert!(matches!(
+            config.validate(),
+            Err(DotConfigValidationError::Tunnel(app, tunnel, ForwardMappingArgParsingError::Port(port)))
+                if app == "dev" && tunnel == "redis" && port == "port"
+        ));
+    }
+
+    #[test]
+    fn resolves_certificate_paths_relative_to_config_dir() {
+        let mut config = DotConfig::from_str(
+            r#"
+            [apps.onprem]
+            url = "https://onprem.local"
+            ca_cert = "certs/ca.pem"
+            client_cert = "/etc/crtcli/client.pem"
+            proxy = "http://proxy.local:3128"
+            "#,
+        )
+        .unwrap();
+
+        config.resolve_relative_paths(Path::new("/work/project"));
+
+        let app = &config.apps()["onprem"];
+
+        assert_eq!(
+            app.ca_cert.as_deref(),
+            Some(Path::new("/work/project/certs/ca.pem"))
+        );
+        assert_eq!(
+            app.client_cert.as_deref(),
+            Some(Path::new("/etc/crtcli/client.pem"))
+        );
+        assert_eq!(app.client_key, None);
+        assert_eq!(app.proxy.as_deref(), Some("http://proxy.local:3128"));
+    }
+}
@@ -0,0 +1,589 @@
+use crate::app::workspace_explorer::GetPackagesResponseItem;
+use crate::app::{CrtClient, CrtClientError};
+use crate::cmd::app::{AppCommand, AppCommands, AppConnectionArgs};
+use crate::cmd::cli::{CommandDynError, CommandResult};
+use crate::pkg::bundling::extractor::is_pkg_file_content_smart_equal;
+use crate::pkg::bundling::{PkgGZipDecoder, PkgGZipDecoderError};
+use crate::pkg::transforms::{
+    PkgFileTransform, SortingComparer, SortingPkgFileTransform, SortingPkgFileTransformError,
+};
+use anstyle::{AnsiColor, Color, Style};
+use clap::Args;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Cursor, Read};
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::io::AsyncReadExt;
+use zip::ZipArchive;
+use zip::result::ZipError;
+
+#[derive(Args, Debug)]
+pub struct CompareCommand {
+    /// Creatio Base URL or App alias from .crtcli.toml of the instance to compare with
+    #[arg(value_name = "URL/APP", value_hint = clap::ValueHint::Url)]
+    target: String,
+
+    /// A space-separated or comma-separated list of package names to compare (default: all packages)
+    ///
+    /// With `--deep`, all specified packages which are installed on both instances are compared file by file,
+    /// even if their versions and maintainers are the same.
+    #[arg(value_delimiter = ',', value_hint = clap::ValueHint::Other)]
+    packages: Vec<String>,
+
+    /// Download the differing packages from both instances and compare their files
+    ///
+    /// Files are compared after the sorting transform is applied,
+    /// insignificant schema differences (like modification date) are ignored.
+    #[arg(long)]
+    deep: bool,
+
+    /// Display the output in JSON format
+    #[arg(long)]
+    json: bool,
+
+    #[arg(skip)]
+    connection_args: AppConnectionArgs,
+}
+
+#[derive(Debug, Error)]
+pub enum CompareCommandError {
+    #[error("failed to setup target app client: {0}")]
+    TargetClient(#[source] CommandDynError),
+
+    #[error("failed to get packages list from {0}: {1}")]
+    GetPackages(String, #[source] CrtClientError),
+
+    #[error("failed to download packages from {0}: {1}")]
+    Download(String, #[source] CrtClientError),
+
+    #[error("failed to read packages archive from {0}: {1}")]
+    ReadArchive(String, #[source] std::io::Error),
+
+    #[error("failed to open packages archive from {0}: {1}")]
+    OpenArchive(String, #[source] ZipError),
+
+    #[error("failed to decode package {0}: {1}")]
+    DecodePackage(String, #[source] PkgGZipDecoderError),
+
+    #[error("failed to apply sorting transform to {0}: {1}")]
+    Sorting(String, #[source] SortingPkgFileTransformError),
+}
+
+#[derive(Debug, Serialize)]
+struct CompareReport {
+    source: String,
+    target: String,
+    packages: Vec<PackageComparison>,
+}
+
+#[derive(Debug, Serialize)]
+struct PackageComparison {
+    name: String,
+    status: PackageComparisonStatus,
+    source_version: Option<String>,
+    target_version: Option<String>,
+    source_maintainer: Option<String>,
+    target_maintainer: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    files: Option<Vec<FileComparison>>,
+}
+
+#[derive(Debug, Serialize, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum PackageComparisonStatus {
+    MissingInTarget,
+    MissingInSource,
+    Different,
+    Same,
+}
+
+#[derive(Debug, Serialize)]
+struct FileComparison {
+    path: String,
+    status: FileComparisonStatus,
+}
+
+#[derive(Debug, Serialize, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+enum FileComparisonStatus {
+    MissingInTarget,
+    MissingInSource,
+    Modified,
+}
+
+impl CompareCommand {
+    pub fn with_connection_args(self, connection_args: AppConnectionArgs) -> Self {
+        Self {
+            connection_args,
+            ..self
+        }
+    }
+}
+
+impl AppCommand for CompareCommand {
+    async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
+        let target_client =
+            AppCommands::setup_client_by_url_or_alias(&self.target, &self.connection_args)
+                .map_err(CompareCommandError::TargetClient)?;
+
+        let progress = spinner!(
+            "Fetching packages list from {bold}{source}{bold:#} and {bold}{target}{bold:#}",
+            bold = Style::new().bold(),
+            source = client.base_url(),
+            target = target_client.base_url(),
+        );
+
+        let source_service = client.workspace_explorer_service();
+        let target_service = target_client.workspace_explorer_service();
+
+        let (source_packages, target_packages) =
+            tokio::join!(source_service.get_packages(), target_service.get_packages());
+
+        let source_packages = source_packages
+            .map_err(|err| CompareCommandError::GetPackages(client.base_url().to_owned(), err))?;
+
+        let target_packages = target_packages.map_err(|err| {
+            CompareCommandError::GetPackages(target_client.base_url().to_owned(), err)
+        })?;
+
+        progress.finish_and_clear();
+
+        let mut comparisons = compare_packages(&source_packages, &target_packages, &self.packages);
+
+        if self.deep {
+            let deep_package_names = comparisons
+                .iter()
+                .filter(|c| match c.status {
+                    PackageComparisonStatus::Different => true,
+                    PackageComparisonStatus::Same => !self.packages.is_empty(),
+                    _ => false,
+                })
+                .map(|c| c.name.clone())
+                .collect::<Vec<_>>();
+
+            if !deep_package_names.is_empty() {
+                let progress = spinner!(
+                    "Downloading {bold}{count}{bold:#} packages from both instances",
+                    bold = Style::new().bold(),
+                    count = deep_package_names.len(),
+                );
+
+                let (source_files, target_files) = tokio::try_join!(
+                    download_packages_files(&client, &deep_package_names),
+                    download_packages_files(&target_client, &deep_package_names)
+                )?;
+
+                progress.finish_and_clear();
+
+                for comparison in &mut comparisons {
+                    if !deep_package_names.contains(&comparison.name) {
+                        continue;
+                    }
+
+                    let empty = BTreeMap::new();
+
+                    let files = compare_package_files(
+                        find_package_files(&source_files, &comparison.name).unwrap_or(&empty),
+                        find_package_files(&target_files, &comparison.name).unwrap_or(&empty),
+                    );
+
+                    if !files.is_empty() {
+                        comparison.status = PackageComparisonStatus::Different;
+                    }
+
+                    comparison.files = Some(files);
+                }
+            }
+        }
+
+        comparisons.retain(|c| c.status != PackageComparisonStatus::Same);
+
+        let report = CompareReport {
+            source: client.base_url().to_owned(),
+            target: target_client.base_url().to_owned(),
+            packages: comparisons,
+        };
+
+        match self.json {
+            true => println!("{}", serde_json::json!(report)),
+            false => print_compare_report(&report),
+        }
+
+        Ok(())
+    }
+}
+
+fn compare_packages(
+    source_packages: &[GetPackagesResponseItem],
+    target_packages: &[GetPackagesResponseItem],
+    filter: &[String],
+) -> Vec<PackageComparison> {
+    let source = packages_by_name(source_packages, filter);
+    let target = packages_by_name(target_packages, filter);
+
+    let keys = source
+        .keys()
+        .chain(target.keys())
+        .cloned()
+        .collect::<BTreeSet<_>>();
+
+    return keys
+        .into_iter()
+        .map(|key| {
+            let source_package = source.get(&key).copied();
+            let target_package = target.get(&key).copied();
+
+            let name = source_package
+                .or(target_package)
+                .map_or(key, |p| p.name().to_owned());
+
+            let source_version = source_package.and_then(|p| p.version().map(String::from));
+            let target_version = target_package.and_then(|p| p.version().map(String::from));
+
+            let source_maintainer = source_package.and_then(|p| p.maintainer().map(String::from));
+            let target_maintainer = target_package.and_then(|p| p.maintainer().map(String::from));
+
+            let status = match (source_package, target_package) {
+                (Some(_), None) => PackageComparisonStatus::MissingInTarget,
+                (None, _) => PackageComparisonStatus::MissingInSource,
+                (Some(_), Some(_))
+                    if source_version != target_version
+                        || source_maintainer != target_maintainer =>
+                {
+                    PackageComparisonStatus::Different
+                }
+                (Some(_), Some(_)) => PackageComparisonStatus::Same,
+            };
+
+            PackageComparison {
+                name,
+                status,
+                source_version,
+                target_version,
+                source_maintainer,
+                target_maintainer,
+                files: None,
+            }
+        })
+        .collect();
+
+    /// Creatio package names are case-insensitive, so the map is keyed by the lowercase name.
+    fn packages_by_name<'a>(
+        packages: &'a [GetPackagesResponseItem],
+        filter: &[String],
+    ) -> BTreeMap<String, &'a GetPackagesResponseItem> {
+        packages
+            .iter()
+            .filter(|p| {
+                filter.is_empty() || filter.iter().any(|f| f.eq_ignore_ascii_case(p.name()))
+            })
+            .map(|p| (p.name().to_ascii_lowercase(), p))
+            .collect()
+    }
+}
+
+type PackageFiles = BTreeMap<String, Vec<u8>>;
+
+fn find_package_files<'a>(
+    packages: &'a BTreeMap<String, PackageFiles>,
+    package_name: &str,
+) -> Option<&'a PackageFiles> {
+    packages
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(package_name))
+        .map(|(_, files)| files)
+}
+
+/// Downloads packages and decodes their files with the sorting transform applied.
+async fn download_packages_files(
+    client: &CrtClient,
+    package_names: &[String],
+) -> Result<BTreeMap<String, PackageFiles>, CompareCommandError> {
+    let url = || client.base_url().to_owned();
+
+    let mut archive = vec![];
+
+    client
+        .package_installer_service()
+        .get_zip_packages(package_names)
+        .await
+        .map_err(|err| CompareCommandError::Download(url(), err))?
+        .read_to_end(&mut archive)
+        .await
+        .map_err(|err| CompareCommandError::ReadArchive(url(), err))?;
+
+    let mut zip = ZipArchive::new(Cursor::new(archive))
+        .map_err(|err| CompareCommandError::OpenArchive(url(), err))?;
+
+    let transform = SortingPkgFileTransform::new(SortingComparer::default());
+    let mut packages = BTreeMap::new();
+
+    for i in 0..zip.len() {
+        let mut gzip = zip
+            .by_index(i)
+            .map_err(|err| CompareCommandError::OpenArchive(url(), err))?;
+
+        let package_name = gzip
+            .name()
+            .strip_suffix(".gz")
+            .unwrap_or(gzip.name())
+            .to_owned();
+
+        let mut gzip_content = vec![];
+
+        gzip.read_to_end(&mut gzip_content)
+            .map_err(|err| CompareCommandError::ReadArchive(url(), err))?;
+
+        let mut files = PackageFiles::new();
+
+        for file in PkgGZipDecoder::from(gzip_content.as_slice()) {
+            let file =
+                file.map_err(|err| CompareCommandError::DecodePackage(package_name.clone(), err))?;
+
+            let filename = file.to_native_path_string().into_owned();
+
+            let content = transform
+                .transform(&filename, file.content)
+                .map_err(|err| CompareCommandError::Sorting(filename.clone(), err))?;
+
+            if let Some(content) = content {
+                files.insert(filename, content);
+            }
+        }
+
+        packages.insert(package_name, files);
+    }
+
+    Ok(packages)
+}
+
+fn compare_package_files(source: &PackageFiles, target: &PackageFiles) -> Vec<FileComparison> {
+    let paths = source.keys().chain(target.keys()).collect::<BTreeSet<_>>();
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let status = match (source.get(path), target.get(path)) {
+                (Some(_), None) => FileComparisonStatus::MissingInTarget,
+                (None, _) => FileComparisonStatus::MissingInSource,
+                (Some(s), Some(t)) if is_pkg_file_content_smart_equal(path, s, t) => return None,
+                (Some(_), Some(_)) => FileComparisonStatus::Modified,
+            };
+
+            Some(FileComparison {
+                path: path.replace('\\', "/"),
+                status,
+            })
+        })
+        .collect()
+}
+
+fn print_compare_report(report: &CompareReport) {
+    let bold = Style::new().bold();
+    let dimmed = Style::new().dimmed();
+    let green = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green)));
+    let yellow = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Yellow)));
+    let red = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Red)));
+
+    println!(
+        "{bold}Source:{bold:#} {source}\n{bold}Target:{bold:#} {target}",
+        source = report.source,
+        target = report.target
+    );
+    println!();
+
+    if report.packages.is_empty() {
+        println!("{green}No differences found{green:#}");
+        return;
+    }
+
+    let headers = ["Package", "Status", "Version", "Maintainer"];
+
+    let rows = report
+        .packages
+        .iter()
+        .map(|p| {
+            let pair = |source: &Option<String>, target: &Option<String>| match (source, target) {
+                (s, t) if s == t => s.clone().unwrap_or_default(),
+                (s, t) => format!(
+                    "{} → {}",
+                    s.as_deref().unwrap_or("—"),
+                    t.as_deref().unwrap_or("—")
+                ),
+            };
+
+            [
+                p.name.clone(),
+                match p.status {
+                    PackageComparisonStatus::MissingInTarget => "missing in target",
+                    PackageComparisonStatus::MissingInSource => "missing in source",
+                    PackageComparisonStatus::Different => "different",
+                    PackageComparisonStatus::Same => "same",
+                }
+                .to_owned(),
+                pair(&p.source_version, &p.target_version),
+                pair(&p.source_maintainer, &p.target_maintainer),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let widths = (0..headers.len())
+        .map(|i| {
+            rows.iter()
+                .map(|r| r[i].chars().count())
+                .chain([headers[i].len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+
+    println!(
+        "{bold}{}{bold:#}",
+        headers
+            .iter()
+            .zip(&widths)
+            .map(|(h, w)| format!("{h:<w$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+    );
+
+    for (row, package) in rows.iter().zip(&report.packages) {
+        let status_style = match package.status {
+            PackageComparisonStatus::MissingInTarget | PackageComparisonStatus::MissingInSource => {
+                red
+            }
+            PackageComparisonStatus::Different => yellow,
+            PackageComparisonStatus::Same => dimmed,
+        };
+
+        let line = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(i, (cell, w))| {
+                let padding = " ".repeat(w - cell.chars().count());
+
+                match i {
+                    1 => format!("{status_style}{cell}{status_style:#}{padding}"),
+                    _ => format!("{cell}{padding}"),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        println!("{}", line.trim_end());
+
+        for file in package.files.iter().flatten() {
+            let (marker, style) = match file.status {
+                FileComparisonStatus::MissingInTarget => ("-", red),
+                FileComparisonStatus::MissingInSource => ("+", green),
+                FileComparisonStatus::Modified => ("~", yellow),
+            };
+
+            println!("    {style}{marker} {path}{style:#}", path = file.path);
+        }
+    }
+
+    println!();
+    println!(
+        "{dimmed}Total: {} differing packages{dimmed:#}",
+        report.packages.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(name: &str, version: &str, maintainer: &str) -> GetPackagesResponseItem {
+        serde_json::from_value(serde_json::json!({
+            "uId": uuid::Uuid::new_v4().to_string(),
+            "name": name,
+            "version": version,
+            "maintainer": maintainer,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn compares_packages_case_insensitively() {
+        let source = vec![
+            package("UsrSame", "1.0.0", "Customer"),
+            package("UsrChanged", "1.0.0", "Customer"),
+            package("UsrOnlySource", "1.0.0", "Customer"),
+        ];
+
+        let target = vec![
+            package("usrsame", "1.0.0", "Customer"),
+            package("UsrChanged", "1.1.0", "Customer"),
+            package("UsrOnlyTarget", "1.0.0", "Customer"),
+        ];
+
+        let statuses = compare_packages(&source, &target, &[])
+            .into_iter()
+            .map(|c| (c.name, c.status))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            statuses,
+            vec![
+                ("UsrChanged".to_owned(), PackageComparisonStatus::Different),
+                (
+                    "UsrOnlySource".to_owned(),
+                    PackageComparisonStatus::MissingInTarget
+                ),
+                (
+                    "UsrOnlyTarget".to_owned(),
+                    PackageComparisonStatus::MissingInSource
+                ),
+                ("UsrSame".to_owned(), PackageComparisonStatus::Same),
+            ]
+        );
+
+        let filtered = compare_packages(&source, &target, &["usrchanged".to_owned()]);
+
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].name, "UsrChanged");
+        assert_eq!(filtered[0].source_version.as_deref(), Some("1.0.0"));
+        assert_eq!(filtered[0].target_version.as_deref(), Some("1.1.0"));
+    }
+
+    #[test]
+    fn compares_package_files() {
+        let files = |entries: &[(&str, &str)]| {
+            entries
+                .iter()
+                .map(|(path, content)| (path.to_string(), content.as_bytes().to_vec()))
+                .collect::<PackageFiles>()
+        };
+
+        let source = files(&[
+            ("Files\\same.txt", "same"),
+            ("Files\\changed.txt", "before"),
+            ("Files\\source.txt", "source"),
+        ]);
+
+        let target = files(&[
+            ("Files\\same.txt", "same"),
+            ("Files\\changed.txt", "after"),
+            ("Files\\target.txt", "target"),
+        ]);
+
+        let comparisons = compare_package_files(&source, &target)
+            .into_iter()
+            .map(|c| (c.path, format!("{:?}", c.status)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            comparisons,
+            vec![
+                ("Files/changed.txt".to_owned(), "Modified".to_owned()),
+                ("Files/source.txt".to_owned(), "MissingInTarget".to_owned()),
+                ("Files/target.txt".to_owned(), "MissingInSource".to_owned()),
+            ]
+        );
+    }
+}
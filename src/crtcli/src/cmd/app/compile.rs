@@ -1,12 +1,17 @@
 use crate::app::CrtClient;
 use crate::app::workspace_explorer::{BaseResponse, BuildPackageError};
+use crate::cfg::WorkspaceConfig;
 use crate::cmd::app;
 use crate::cmd::app::AppCommand;
 use crate::cmd::cli::{CommandDynError, CommandResult};
+use crate::pkg::paths::map_server_source_path_to_package_file;
 use anstream::stdout;
 use anstyle::{AnsiColor, Color, Style};
-use clap::Args;
+use clap::{Args, ValueEnum};
+use serde_json::json;
+use std::collections::HashMap;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use thiserror::Error;
 
@@ -19,6 +24,24 @@ pub struct CompileCommand {
     /// Restart application after successful compilation
     #[arg(short, long)]
     pub restart: bool,
+
+    /// Format of the compilation errors report
+    #[arg(long, value_enum, default_value_t)]
+    pub report_format: BuildReportFormat,
+}
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, ValueEnum)]
+pub enum BuildReportFormat {
+    /// Human-readable `path:line:col: error CSxxxx: message` lines
+    #[default]
+    Text,
+
+    /// GitHub Actions workflow commands (`::error file=...::message`)
+    #[value(alias = "gh")]
+    Github,
+
+    /// SARIF 2.1.0 json log
+    Sarif,
 }
 
 #[derive(Debug, Error)]
@@ -46,7 +69,7 @@ impl AppCommand for CompileCommand {
             client.workspace_explorer_service().build().await?
         };
 
-        progress.suspend(|| print_build_response(&response))?;
+        progress.suspend(|| print_build_response(&response, self.report_format))?;
 
         progress.finish_with_message(format!(
             "{green}Creatio application {operation_str} successfully at {green_bold}{url}{green_bold:#}{green}!{green:#}",
@@ -71,51 +94,222 @@ impl AppCommand for CompileCommand {
     }
 }
 
-pub fn print_build_response(response: &BaseResponse) -> CommandResult {
-    let warn_printer = BuildPackageErrorPrinter::new_for_warning();
-    let error_printer = BuildPackageErrorPrinter::new_for_error();
+pub fn print_build_response(response: &BaseResponse, format: BuildReportFormat) -> CommandResult {
+    let errors = response.errors.as_deref().unwrap_or_default();
+
+    let source_mapper = match errors.iter().any(|e| !e.filename.is_empty()) {
+        true => BuildErrorSourceMapper::load_from_current_dir(),
+        false => BuildErrorSourceMapper::default(),
+    };
+
+    let error_style = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Red)));
+
+    match format {
+        BuildReportFormat::Text => {
+            let warn_printer = BuildPackageErrorPrinter::new_for_warning();
+            let error_printer = BuildPackageErrorPrinter::new_for_error();
 
-    let mut stdout = stdout().lock();
+            let mut stdout = stdout().lock();
 
-    if let Some(errors) = &response.errors {
-        for error in errors {
-            match error.warning {
-                true => warn_printer.print(&mut stdout, error),
-                false => error_printer.print(&mut stdout, error),
+            for error in errors {
+                let path = source_mapper.map(&error.filename);
+
+                match error.warning {
+                    true => warn_printer.print(&mut stdout, error, &path),
+                    false => error_printer.print(&mut stdout, error, &path),
+                }
+            }
+        }
+        BuildReportFormat::Github => {
+            let mut stdout = std::io::stdout().lock();
+
+            for error in errors {
+                print_github_annotation(&mut stdout, error, &source_mapper.map(&error.filename));
             }
         }
+        BuildReportFormat::Sarif => {
+            let sarif = build_sarif_log(errors, &source_mapper);
+
+            let mut stdout = std::io::stdout().lock();
+
+            serde_json::to_writer_pretty(&mut stdout, &sarif)?;
+            writeln!(stdout)?;
+        }
     }
 
+    // Keep stdout clean for machine-readable reports
+    let mut info_output: Box<dyn Write> = match format {
+        BuildReportFormat::Sarif => Box::new(anstream::stderr().lock()),
+        _ => Box::new(stdout().lock()),
+    };
+
     if let Some(error_info) = &response.error_info {
         writeln!(
-            stdout,
-            "{style}Error message -> {}{style:#}",
+            info_output,
+            "{error_style}Error message -> {}{error_style:#}",
             error_info.message,
-            style = error_printer.error_style
         )
         .unwrap();
     }
 
     if let Some(message) = &response.message {
-        writeln!(stdout, "> {message}").unwrap();
+        writeln!(info_output, "> {message}").unwrap();
     }
 
-    match (
-        response.success,
-        response.has_any_error(),
-        &response.error_info,
-    ) {
-        (true, _, _) => {}
-        (false, false, None) => {}
-        _ => return Err("compilation finished with errors".into()),
+    if response.is_failed() {
+        return Err("compilation finished with errors".into());
     }
 
     Ok(())
 }
 
+/// Maps server-side compilation file paths to the local package files from the workspace.
+#[derive(Default)]
+struct BuildErrorSourceMapper {
+    packages: HashMap<String, PathBuf>,
+}
+
+impl BuildErrorSourceMapper {
+    fn load_from_current_dir() -> Self {
+        let Ok(config) = WorkspaceConfig::load_default_from_current_dir() else {
+            return Self::default();
+        };
+
+        let packages = config
+            .packages()
+            .iter()
+            .filter_map(|p| {
+                let name = p.package_name().ok()?.to_lowercase();
+                let path = p.path().strip_prefix(".").unwrap_or(p.path());
+
+                Some((name, path.to_path_buf()))
+            })
+            .collect();
+
+        Self { packages }
+    }
+
+    fn map(&self, server_path: &str) -> String {
+        self.map_to_local_file(server_path)
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| server_path.to_owned())
+    }
+
+    fn map_to_local_file(&self, server_path: &str) -> Option<PathBuf> {
+        if self.packages.is_empty() {
+            return None;
+        }
+
+        let file_ref = map_server_source_path_to_package_file(server_path)?;
+
+        let exists_in = |package_folder: &Path| {
+            let path = package_folder.join(&file_ref.relative_path);
+
+            path.exists().then_some(path)
+        };
+
+        if let Some(package_folder) = file_ref
+            .package_name
+            .as_ref()
+            .and_then(|name| self.packages.get(&name.to_lowercase()))
+            && let Some(path) = exists_in(package_folder)
+        {
+            return Some(path);
+        }
+
+        self.packages.values().find_map(|p| exists_in(p))
+    }
+}
+
+fn print_github_annotation(mut w: impl Write, e: &BuildPackageError, path: &str) {
+    fn escape_data(value: &str) -> String {
+        value
+            .replace('%', "%25")
+            .replace('\r', "%0D")
+            .replace('\n', "%0A")
+    }
+
+    fn escape_property(value: &str) -> String {
+        escape_data(value).replace(':', "%3A").replace(',', "%2C")
+    }
+
+    let command = match e.warning {
+        true => "warning",
+        false => "error",
+    };
+
+    let mut properties = vec![format!("title={}", escape_property(&e.error_number))];
+
+    if !path.is_empty() {
+        properties.insert(
+            0,
+            format!("file={}", escape_property(&path.replace('\\', "/"))),
+        );
+        properties.insert(1, format!("line={}", e.line));
+        properties.insert(2, format!("col={}", e.column));
+    }
+
+    writeln!(
+        w,
+        "::{command} {}::{}",
+        properties.join(","),
+        escape_data(&e.error_text)
+    )
+    .unwrap();
+}
+
+fn build_sarif_log(
+    errors: &[BuildPackageError],
+    source_mapper: &BuildErrorSourceMapper,
+) -> serde_json::Value {
+    let results = errors
+        .iter()
+        .map(|e| {
+            let mut result = json!({
+                "ruleId": e.error_number,
+                "level": if e.warning { "warning" } else { "error" },
+                "message": {
+                    "text": e.error_text
+                }
+            });
+
+            if !e.filename.is_empty() {
+                result["locations"] = json!([{
+                    "physicalLocation": {
+                        "artifactLocation": {
+                            "uri": source_mapper.map(&e.filename).replace('\\', "/")
+                        },
+                        "region": {
+                            "startLine": e.line.max(1),
+                            "startColumn": e.column.max(1)
+                        }
+                    }
+                }]);
+            }
+
+            result
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "crtcli",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/heabijay/crtcli"
+                }
+            },
+            "results": results
+        }]
+    })
+}
+
 struct BuildPackageErrorPrinter {
+    location_style: Style,
     type_style: Style,
-    file_style: Style,
     error_style: Style,
 }
 
@@ -130,29 +324,27 @@ impl BuildPackageErrorPrinter {
 
     fn new_from_style(base_style: Style) -> Self {
         Self {
+            location_style: Style::new().bold(),
             type_style: base_style.bold(),
-            file_style: base_style.italic(),
             error_style: base_style,
         }
     }
 
-    fn print(&self, mut w: impl Write, e: &BuildPackageError) {
-        match e.warning {
-            true => write!(w, "{style}WARN{style:#}", style = self.type_style).unwrap(),
-            false => write!(w, "{style}ERROR{style:#}", style = self.type_style).unwrap(),
-        };
-
-        match e.filename.as_str() {
-            "" => (),
-            _ => write!(
+    fn print(&self, mut w: impl Write, e: &BuildPackageError, path: &str) {
+        if !path.is_empty() {
+            write!(
                 w,
-                " {style}{}({}:{}){style:#}",
-                e.filename,
+                "{style}{path}:{}:{}:{style:#} ",
                 e.line,
                 e.column,
-                style = self.file_style
+                style = self.location_style
             )
-            .unwrap(),
+            .unwrap();
+        }
+
+        match e.warning {
+            true => write!(w, "{style}warning{style:#}", style = self.type_style).unwrap(),
+            false => write!(w, "{style}error{style:#}", style = self.type_style).unwrap(),
         };
 
         writeln!(
@@ -0,0 +1,186 @@
+use crate::app::{CrtClient, CrtClientError};
+use crate::cmd::app;
+use crate::cmd::app::AppCommand;
+use crate::cmd::app::compile::BuildReportFormat;
+use crate::cmd::app::print_build_response;
+use crate::cmd::cli::{CommandDynError, CommandResult};
+use anstyle::{AnsiColor, Color, Style};
+use clap::{Args, ValueEnum};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+
+const BACKGROUND_POLLING_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Args, Debug)]
+pub struct GenerateSourcesCommand {
+    /// Which schemas sources should be generated
+    #[arg(value_enum, default_value_t)]
+    target: GenerateSourcesTarget,
+
+    /// Generate all schemas sources in background and poll until generation is finished (only for 'all' target)
+    #[arg(short, long)]
+    background: bool,
+
+    /// Maximum time in seconds to wait for background generation to finish
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        default_value_t = 1800,
+        requires = "background"
+    )]
+    timeout: u64,
+
+    /// Compile the Creatio application after successful sources generation
+    #[arg(short, long)]
+    compile: bool,
+
+    /// Format of the errors report
+    #[arg(long, value_enum, default_value_t)]
+    report_format: BuildReportFormat,
+}
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, ValueEnum)]
+pub enum GenerateSourcesTarget {
+    /// Sources of the modified schemas
+    #[default]
+    Modified,
+
+    /// Sources of the schemas which require generation
+    Required,
+
+    /// Sources of all schemas
+    All,
+}
+
+#[derive(Debug, Error)]
+pub enum GenerateSourcesCommandError {
+    #[error("--background option is supported only for 'all' target")]
+    BackgroundNotSupported,
+
+    #[error("background generation did not finish in {0} seconds, last status: {1}")]
+    BackgroundTimeout(u64, String),
+
+    #[error("failed to read background generation state: {0}")]
+    BackgroundState(#[source] CrtClientError),
+
+    #[error("App compile error: {0}")]
+    AppCompile(#[source] CommandDynError),
+}
+
+impl AppCommand for GenerateSourcesCommand {
+    async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
+        if self.background && self.target != GenerateSourcesTarget::All {
+            return Err(GenerateSourcesCommandError::BackgroundNotSupported.into());
+        }
+
+        let target_str = match self.target {
+            GenerateSourcesTarget::Modified => "modified",
+            GenerateSourcesTarget::Required => "required",
+            GenerateSourcesTarget::All => "all",
+        };
+
+        let progress = spinner_precise!(
+            "Generating {target_str} schemas sources at {bold}{url}{bold:#}",
+            bold = Style::new().bold(),
+            url = client.base_url()
+        );
+
+        let workspace_explorer_service = client.workspace_explorer_service();
+
+        let response = match (self.target, self.background) {
+            (GenerateSourcesTarget::Modified, _) => {
+                workspace_explorer_service
+                    .generate_modified_schema_sources()
+                    .await?
+            }
+            (GenerateSourcesTarget::Required, _) => {
+                workspace_explorer_service
+                    .generate_required_schemas_sources()
+                    .await?
+            }
+            (GenerateSourcesTarget::All, false) => {
+                workspace_explorer_service
+                    .generate_all_schemas_sources()
+                    .await?
+            }
+            (GenerateSourcesTarget::All, true) => {
+                let response = workspace_explorer_service
+                    .generate_all_schemas_sources_background()
+                    .await?;
+
+                if response.is_failed() {
+                    response
+                } else {
+                    progress.set_message(format!(
+                        "Waiting for background generation of all schemas sources at {bold}{url}{bold:#}",
+                        bold = Style::new().bold(),
+                        url = client.base_url()
+                    ));
+
+                    wait_for_background_generation(&client, self.timeout).await?;
+
+                    response
+                }
+            }
+        };
+
+        progress.suspend(|| print_build_response(&response, self.report_format))?;
+
+        progress.finish_with_message(format!(
+            "{green}Schemas sources ({target_str}) successfully generated at {green_bold}{url}{green_bold:#}{green}!{green:#}",
+            green = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green))),
+            green_bold = Style::new()
+                .fg_color(Some(Color::Ansi(AnsiColor::Green)))
+                .bold(),
+            url = client.base_url(),
+        ));
+
+        if self.compile {
+            app::compile::CompileCommand {
+                force_rebuild: false,
+                restart: false,
+                report_format: self.report_format,
+            }
+            .run(client)
+            .await
+            .map_err(GenerateSourcesCommandError::AppCompile)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Waits until background generation is finished.
+///
+/// Creatio does not expose the state of background generation, so the schemas marked as changed
+/// are polled through the SQL runner (read-only): generation is finished when none of them are left.
+/// Fails when the generation is not finished in `timeout_secs` or the schemas state can't be read.
+async fn wait_for_background_generation(
+    client: &CrtClient,
+    timeout_secs: u64,
+) -> Result<(), CommandDynError> {
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(timeout_secs);
+
+    loop {
+        tokio::time::sleep(BACKGROUND_POLLING_DELAY).await;
+
+        let pending_schemas = client
+            .sql_scripts()
+            .find_changed_schemas()
+            .await
+            .map_err(GenerateSourcesCommandError::BackgroundState)?;
+
+        if pending_schemas.is_empty() {
+            return Ok(());
+        }
+
+        if tokio::time::Instant::now() >= deadline {
+            return Err(GenerateSourcesCommandError::BackgroundTimeout(
+                timeout_secs,
+                format!("{} schema(s) pending", pending_schemas.len()),
+            )
+            .into());
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use crate::app::CrtClient;
+use crate::cmd::app;
+use crate::cmd::app::AppCommand;
+use crate::cmd::app::install_log::InstallLogPrinter;
+use crate::cmd::app::pkg::install_pkg::{
+    read_package_files, start_install_log_watcher, try_print_upload_package_chunk_size_hint,
+};
+use crate::cmd::cli::{CommandDynError, CommandResult};
+use anstyle::{AnsiColor, Color, Style};
+use clap::Args;
+use std::path::PathBuf;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Args, Debug)]
+pub struct InstallAppCommand {
+    /// Path to the application archive file (Use '@-' or '-' value to read data from stdin)
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    filepath: PathBuf,
+
+    /// Application code (default: archive filename without extension)
+    #[arg(long, value_hint = clap::ValueHint::Other)]
+    code: Option<String>,
+
+    /// Application name (default: application code)
+    #[arg(long, value_hint = clap::ValueHint::Other)]
+    name: Option<String>,
+
+    /// Restart the Creatio application after successful installation
+    #[arg(short, long)]
+    restart: bool,
+
+    /// Compile the Creatio application after successful installation
+    #[arg(short, long)]
+    compile: bool,
+
+    /// Disables the display of the installation log updates in real-time
+    #[arg(long)]
+    disable_install_log_polling: bool,
+
+    /// Print installation log as a stream of parsed JSON events (one JSON object per line)
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Error)]
+pub enum InstallAppCommandError {
+    #[error("failed to upload application archive: {0}")]
+    Upload(#[source] crate::app::CrtClientError),
+
+    #[error("failed to install application: {0}")]
+    Install(#[source] crate::app::CrtClientError),
+
+    #[error("failed to compile app: {0}")]
+    AppCompile(#[source] CommandDynError),
+
+    #[error("failed to restart app: {0}")]
+    AppRestart(#[source] CommandDynError),
+}
+
+impl AppCommand for InstallAppCommand {
+    async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
+        let (archive_content, archive_name) =
+            read_package_files(std::slice::from_ref(&self.filepath))?;
+
+        let code = match &self.code {
+            Some(code) => code.as_str(),
+            None => archive_name
+                .rsplit_once('.')
+                .map_or(archive_name.as_str(), |(name, _)| name),
+        };
+
+        let name = self.name.as_deref().unwrap_or(code);
+
+        let progress = Arc::new(spinner_precise!(
+            "Uploading {bold}{archive_name}{bold:#} application archive at {bold}{url}{bold:#}",
+            bold = Style::new().bold(),
+            url = client.base_url(),
+        ));
+
+        let package_installer_service = client.package_installer_service();
+
+        let mut chunked_upload_iter = package_installer_service
+            .start_upload_package_chunked(archive_content, archive_name.clone());
+
+        if let Some(chunk) = chunked_upload_iter.next() {
+            chunk
+                .await
+                .inspect_err(|_err| progress.suspend(try_print_upload_package_chunk_size_hint))
+                .map_err(InstallAppCommandError::Upload)?;
+        }
+
+        let log_printer = Arc::new(InstallLogPrinter::new(self.json));
+
+        let log_watcher = (!self.disable_install_log_polling)
+            .then(|| start_install_log_watcher(&client, &progress, &log_printer));
+
+        while let Some(chunk) = chunked_upload_iter.next() {
+            chunk.await.map_err(InstallAppCommandError::Upload)?;
+        }
+
+        progress.set_message(format!(
+            "Installing {bold}{name}{bold:#} application at {bold}{url}{bold:#}",
+            bold = Style::new().bold(),
+            url = client.base_url()
+        ));
+
+        let install_result = client
+            .app_installer_service()
+            .install_app_from_file(code, name, &archive_name)
+            .await
+            .map_err(InstallAppCommandError::Install);
+
+        if let Some(log_watcher) = log_watcher {
+            log_watcher.stop();
+            log_watcher.wait_until_stopped().await;
+
+            progress.suspend(|| log_printer.finish());
+        }
+
+        progress.finish_with_message(match install_result {
+            Ok(_) => format!(
+                "{green}Application {green_bold}{name}{green_bold:#}{green} successfully installed at {green_bold}{url}{green_bold:#}",
+                green = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green))),
+                green_bold = Style::new()
+                    .fg_color(Some(Color::Ansi(AnsiColor::Green)))
+                    .bold(),
+                url = client.base_url()
+            ),
+            Err(_) => format!(
+                "{red}Application {red_bold}{name}{red_bold:#}{red} installation failed at {red_bold}{url}{red_bold:#}",
+                red = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Red))),
+                red_bold = Style::new()
+                    .fg_color(Some(Color::Ansi(AnsiColor::Red)))
+                    .bold(),
+                url = client.base_url()
+            ),
+        });
+
+        install_result?;
+
+        if self.compile {
+            app::compile::CompileCommand {
+                force_rebuild: false,
+                restart: self.restart,
+                report_format: Default::default(),
+            }
+            .run(client)
+            .await
+            .map_err(InstallAppCommandError::AppCompile)?;
+        } else if self.restart {
+            app::restart::RestartCommand
+                .run(client)
+                .await
+                .map_err(InstallAppCommandError::AppRestart)?;
+        }
+
+        Ok(())
+    }
+}
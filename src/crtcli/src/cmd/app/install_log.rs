@@ -1,50 +1,182 @@
-use crate::app::{CrtClient, InstallLogWatcherBuilder, InstallLogWatcherEvent};
+use crate::app::{
+    CrtClient, InstallLogEvent, InstallLogParser, InstallLogSummary, InstallLogWatcherBuilder,
+    InstallLogWatcherEvent,
+};
 use crate::cmd::app::AppCommand;
 use crate::cmd::cli::CommandResult;
+use anstream::stdout;
 use anstyle::{AnsiColor, Color, Style};
 use clap::Args;
-use std::sync::Arc;
+use serde_json::json;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
 
 #[derive(Args, Debug)]
 pub struct InstallLogCommand {
     /// Watch for and display installation log updates in real-time
     #[arg(long)]
     watch: bool,
+
+    /// Print installation log as a stream of parsed JSON events (one JSON object per line)
+    #[arg(long)]
+    json: bool,
 }
 
 impl AppCommand for InstallLogCommand {
     async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
         if self.watch {
-            start_log_watcher_and_wait_forever(client).await;
+            start_log_watcher_and_wait_for_ctrl_c(client, self.json).await;
         } else {
             let log_file = client.package_installer_service().get_log_file().await?;
 
-            println!("{}", log_file.trim_end());
+            let printer = InstallLogPrinter::new(self.json);
+
+            printer.append(log_file.trim_end());
+            printer.finish();
         }
 
         Ok(())
     }
 }
 
-async fn start_log_watcher_and_wait_forever(client: Arc<CrtClient>) {
-    InstallLogWatcherBuilder::new_with_current_session(client)
-        .start(|event| match event {
-            InstallLogWatcherEvent::Clear => {
-                println!("----------------------------------------")
+/// Watches the installation log until Ctrl+C is pressed, then prints the summary of the watched log.
+async fn start_log_watcher_and_wait_for_ctrl_c(client: Arc<CrtClient>, json: bool) {
+    let printer = Arc::new(InstallLogPrinter::new(json));
+    let watcher_printer = Arc::clone(&printer);
+
+    let watcher =
+        InstallLogWatcherBuilder::new_with_current_session(client).start(
+            move |event| match event {
+                InstallLogWatcherEvent::Clear => watcher_printer.clear(),
+                InstallLogWatcherEvent::Append(text) => watcher_printer.append(text),
+                InstallLogWatcherEvent::FetchError(error) => {
+                    eprintln!(
+                        "{style}warning (log polling): {error}{style:#}",
+                        error = error,
+                        style = Style::new()
+                            .fg_color(Some(Color::Ansi(AnsiColor::BrightYellow)))
+                            .dimmed()
+                    )
+                }
+            },
+        );
+
+    tokio::select! {
+        _ = watcher.wait_until_stopped() => {},
+        _ = tokio::signal::ctrl_c() => {
+            watcher.stop();
+            watcher.wait_until_stopped().await;
+        }
+    }
+
+    printer.finish();
+}
+
+/// Prints the installation log as raw text or as JSON events and collects the summary of it.
+pub struct InstallLogPrinter {
+    json: bool,
+    parser: Mutex<InstallLogParser>,
+}
+
+impl InstallLogPrinter {
+    pub fn new(json: bool) -> Self {
+        Self {
+            json,
+            parser: Mutex::new(InstallLogParser::new()),
+        }
+    }
+
+    pub fn clear(&self) {
+        self.parser.lock().unwrap().reset();
+
+        if !self.json {
+            println!("----------------------------------------");
+        }
+    }
+
+    pub fn append(&self, text: &str) {
+        let events = self.parser.lock().unwrap().feed(text);
+
+        match self.json {
+            true => print_json_events(&events),
+            false => {
+                print!("{text}");
+                let _ = std::io::stdout().flush();
             }
-            InstallLogWatcherEvent::Append(text) => {
-                print!("{}", text)
+        }
+    }
+
+    /// Flushes the last log line and prints the summary.
+    pub fn finish(&self) {
+        let mut parser = self.parser.lock().unwrap();
+        let events = parser.finish();
+
+        match self.json {
+            true => {
+                print_json_events(&events);
+
+                println!(
+                    "{}",
+                    json!({
+                        "type": "summary",
+                        "summary": parser.summary()
+                    })
+                );
             }
-            InstallLogWatcherEvent::FetchError(error) => {
-                eprintln!(
-                    "{style}warning (log polling): {error}{style:#}",
-                    error = error,
-                    style = Style::new()
-                        .fg_color(Some(Color::Ansi(AnsiColor::BrightYellow)))
-                        .dimmed()
-                )
+            false => {
+                println!();
+                print_install_log_summary(parser.summary());
             }
-        })
-        .wait_until_stopped() // This will never be stopped, except for some signal like Ctrl+C, SIGKILL, etc.
-        .await;
+        }
+    }
+}
+
+fn print_json_events(events: &[InstallLogEvent]) {
+    let mut stdout = std::io::stdout().lock();
+
+    for event in events {
+        serde_json::to_writer(&mut stdout, event).unwrap();
+        writeln!(stdout).unwrap();
+    }
+}
+
+pub fn print_install_log_summary(summary: &InstallLogSummary) {
+    let bold = Style::new().bold();
+    let error_style = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Red)));
+    let warning_style = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Yellow)));
+
+    let mut stdout = stdout().lock();
+
+    for error in &summary.errors {
+        writeln!(stdout, "{error_style}error:{error_style:#} {error}").unwrap();
+    }
+
+    for warning in &summary.warnings {
+        writeln!(stdout, "{warning_style}warning:{warning_style:#} {warning}").unwrap();
+    }
+
+    let plural = |count: usize, one: &str, many: &str| match count {
+        1 => format!("{count} {one}"),
+        _ => format!("{count} {many}"),
+    };
+
+    writeln!(
+        stdout,
+        "{bold}Install log summary:{bold:#} {}, {}, {}, {} — {errors_style}{}{errors_style:#}, {warnings_style}{}{warnings_style:#}",
+        plural(summary.packages, "package", "packages"),
+        plural(summary.schemas, "schema", "schemas"),
+        plural(summary.sql_scripts, "SQL script", "SQL scripts"),
+        plural(summary.data_bindings, "data binding", "data bindings"),
+        plural(summary.errors.len(), "error", "errors"),
+        plural(summary.warnings.len(), "warning", "warnings"),
+        errors_style = match summary.errors.is_empty() {
+            true => Style::new(),
+            false => error_style.bold(),
+        },
+        warnings_style = match summary.warnings.is_empty() {
+            true => Style::new(),
+            false => warning_style.bold(),
+        },
+    )
+    .unwrap();
 }
@@ -0,0 +1,467 @@
+use crate::app::workspace_explorer::GetPackagesResponseItem;
+use crate::app::{CrtClient, CrtClientError};
+use anstyle::Style;
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+use thiserror::Error;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+use time::macros::format_description;
+use tokio::io::AsyncReadExt;
+
+const BACKUPS_FOLDER: &str = ".crtcli/backups";
+
+const BACKUP_MANIFEST_FILENAME: &str = "manifest.json";
+
+const BACKUP_ARCHIVE_FILENAME: &str = "Packages.zip";
+
+/// Information about packages backup stored in the `manifest.json` file of the backup folder.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PackagesBackupManifest {
+    pub id: String,
+
+    pub url: String,
+
+    /// RFC3339 time of the backup creation, stored in UTC
+    pub created_at: String,
+
+    /// Packages which were installed at the moment of backup and are stored in the archive
+    pub packages: Vec<PackagesBackupManifestItem>,
+
+    /// Packages which were not installed at the moment of backup
+    #[serde(default)]
+    pub new_packages: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PackagesBackupManifestItem {
+    pub name: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Error)]
+pub enum PackagesBackupError {
+    #[error("failed to get installed packages: {0}")]
+    GetPackages(#[source] CrtClientError),
+
+    #[error("failed to download packages: {0}")]
+    Download(#[source] CrtClientError),
+
+    #[error("failed to read packages archive: {0}")]
+    ReadArchive(#[source] std::io::Error),
+
+    #[error("failed to write backup to {0}: {1}")]
+    Write(PathBuf, #[source] std::io::Error),
+
+    #[error("failed to read backups folder {0}: {1}")]
+    ReadFolder(PathBuf, #[source] std::io::Error),
+
+    #[error("failed to read backup manifest {0}: {1}")]
+    ReadManifest(PathBuf, #[source] std::io::Error),
+
+    #[error("failed to parse backup manifest {0}: {1}")]
+    ParseManifest(PathBuf, #[source] serde_json::Error),
+
+    #[error("invalid backup id {0}: it must be a folder name without path separators")]
+    InvalidId(String),
+
+    #[error("backup {0} was not found in {1}")]
+    NotFound(String, PathBuf),
+
+    #[error("no backups were found in {0}")]
+    NoBackups(PathBuf),
+}
+
+pub struct PackagesBackup {
+    folder: PathBuf,
+    manifest: PackagesBackupManifest,
+}
+
+impl PackagesBackup {
+    pub fn manifest(&self) -> &PackagesBackupManifest {
+        &self.manifest
+    }
+
+    pub fn archive_path(&self) -> Option<PathBuf> {
+        self.manifest
+            .archive
+            .as_ref()
+            .map(|archive| self.folder.join(archive))
+    }
+}
+
+/// Returns the folder where backups of the Creatio instance are stored: `.crtcli/backups/<alias>`.
+///
+/// The alias is the app alias from .crtcli.toml the client was created for, if the app was specified by URL,
+/// the name is generated from the URL host and port.
+pub fn get_backups_folder(client: &CrtClient) -> PathBuf {
+    let alias = client.app_alias().map(String::from).unwrap_or_else(|| {
+        let base_url = client.base_url().trim_end_matches('/');
+
+        base_url
+            .split_once("://")
+            .map_or(base_url, |(_, rest)| rest)
+            .chars()
+            .map(
+                |c| match c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                    true => c,
+                    false => '_',
+                },
+            )
+            .collect()
+    });
+
+    PathBuf::from(BACKUPS_FOLDER).join(alias)
+}
+
+/// Downloads currently installed versions of the packages into a new timestamped backup folder.
+///
+/// Packages which are not installed yet are only recorded in the manifest.
+pub async fn backup_packages(
+    client: &CrtClient,
+    package_names: &[String],
+) -> Result<PackagesBackup, PackagesBackupError> {
+    let installed_packages = client
+        .workspace_explorer_service()
+        .get_packages()
+        .await
+        .map_err(PackagesBackupError::GetPackages)?;
+
+    let (packages, new_packages) = split_installed_packages(&installed_packages, package_names);
+
+    let archive = match packages.is_empty() {
+        true => None,
+        false => {
+            let mut content = vec![];
+
+            client
+                .package_installer_service()
+                .get_zip_packages(packages.iter().map(|p| &p.name).collect::<Vec<_>>())
+                .await
+                .map_err(PackagesBackupError::Download)?
+                .read_to_end(&mut content)
+                .await
+                .map_err(PackagesBackupError::ReadArchive)?;
+
+            Some(content)
+        }
+    };
+
+    write_backup(
+        &get_backups_folder(client),
+        client.base_url(),
+        OffsetDateTime::now_utc(),
+        packages,
+        new_packages,
+        archive,
+    )
+}
+
+/// Splits the requested packages into installed ones (with the name as it is installed)
+/// and not installed ones. Package names are compared case-insensitively.
+fn split_installed_packages(
+    installed_packages: &[GetPackagesResponseItem],
+    package_names: &[String],
+) -> (Vec<PackagesBackupManifestItem>, Vec<String>) {
+    let mut packages = vec![];
+    let mut new_packages = vec![];
+
+    for package_name in package_names {
+        match installed_packages
+            .iter()
+            .find(|p| p.name().eq_ignore_ascii_case(package_name))
+        {
+            Some(installed) => packages.push(PackagesBackupManifestItem {
+                name: installed.name().to_owned(),
+                version: installed.version().map(String::from),
+            }),
+            None => new_packages.push(package_name.clone()),
+        }
+    }
+
+    (packages, new_packages)
+}
+
+fn write_backup(
+    backups_folder: &Path,
+    url: &str,
+    created_at: OffsetDateTime,
+    packages: Vec<PackagesBackupManifestItem>,
+    new_packages: Vec<String>,
+    archive: Option<Vec<u8>>,
+) -> Result<PackagesBackup, PackagesBackupError> {
+    // Folder name uses the local time to be recognizable by user, ordering relies on created_at
+    let local_created_at = time::UtcOffset::current_local_offset()
+        .map_or(created_at, |offset| created_at.to_offset(offset));
+
+    let id = local_created_at
+        .format(format_description!(
+            "[year]-[month]-[day]_[hour].[minute].[second]"
+        ))
+        .expect("failed to format current time");
+
+    let folder = crate::cmd::utils::get_next_filename_if_exists(backups_folder.join(&id));
+
+    std::fs::create_dir_all(&folder)
+        .map_err(|err| PackagesBackupError::Write(folder.clone(), err))?;
+
+    let archive = match archive {
+        Some(content) => {
+            let archive_path = folder.join(BACKUP_ARCHIVE_FILENAME);
+
+            std::fs::write(&archive_path, content)
+                .map_err(|err| PackagesBackupError::Write(archive_path, err))?;
+
+            Some(BACKUP_ARCHIVE_FILENAME.to_owned())
+        }
+        None => None,
+    };
+
+    let manifest = PackagesBackupManifest {
+        id: folder
+            .file_name()
+            .map_or(id, |name| name.to_string_lossy().into_owned()),
+        url: url.to_owned(),
+        created_at: created_at
+            .to_offset(time::UtcOffset::UTC)
+            .format(&Rfc3339)
+            .expect("failed to format current time"),
+        packages,
+        new_packages,
+        archive,
+    };
+
+    let manifest_path = folder.join(BACKUP_MANIFEST_FILENAME);
+
+    std::fs::write(
+        &manifest_path,
+        serde_json::to_vec_pretty(&manifest).expect("failed to serialize backup manifest"),
+    )
+    .map_err(|err| PackagesBackupError::Write(manifest_path, err))?;
+
+    Ok(PackagesBackup { folder, manifest })
+}
+
+/// Lists backups of the Creatio instance ordered from oldest to newest.
+pub fn list_backups(client: &CrtClient) -> Result<Vec<PackagesBackup>, PackagesBackupError> {
+    list_backups_in(&get_backups_folder(client))
+}
+
+/// Finds backup by id or returns the latest backup if id is not specified.
+pub fn find_backup(
+    client: &CrtClient,
+    backup_id: Option<&str>,
+) -> Result<PackagesBackup, PackagesBackupError> {
+    find_backup_in(&get_backups_folder(client), backup_id)
+}
+
+fn list_backups_in(backups_folder: &Path) -> Result<Vec<PackagesBackup>, PackagesBackupError> {
+    if !backups_folder.exists() {
+        return Ok(vec![]);
+    }
+
+    let entries = std::fs::read_dir(backups_folder)
+        .map_err(|err| PackagesBackupError::ReadFolder(backups_folder.to_path_buf(), err))?;
+
+    let mut backups = vec![];
+
+    for entry in entries {
+        let entry = entry
+            .map_err(|err| PackagesBackupError::ReadFolder(backups_folder.to_path_buf(), err))?;
+        let manifest_path = entry.path().join(BACKUP_MANIFEST_FILENAME);
+
+        if !manifest_path.is_file() {
+            continue;
+        }
+
+        backups.push(read_backup(entry.path())?);
+    }
+
+    // Compare parsed time, as manifests may be created in different time zones
+    backups.sort_by_cached_key(|backup| {
+        (
+            OffsetDateTime::parse(&backup.manifest.created_at, &Rfc3339).ok(),
+            backup.manifest.id.clone(),
+        )
+    });
+
+    Ok(backups)
+}
+
+fn find_backup_in(
+    backups_folder: &Path,
+    backup_id: Option<&str>,
+) -> Result<PackagesBackup, PackagesBackupError> {
+    match backup_id {
+        Some(backup_id) => {
+            let mut components = Path::new(backup_id).components();
+
+            let is_folder_name = matches!(components.next(), Some(Component::Normal(_)))
+                && components.next().is_none()
+                && !backup_id.contains(['/', '\\']);
+
+            if !is_folder_name {
+                return Err(PackagesBackupError::InvalidId(backup_id.to_owned()));
+            }
+
+            let folder = backups_folder.join(backup_id);
+
+            if !folder.join(BACKUP_MANIFEST_FILENAME).is_file() {
+                return Err(PackagesBackupError::NotFound(
+                    backup_id.to_owned(),
+                    backups_folder.to_path_buf(),
+                ));
+            }
+
+            read_backup(folder)
+        }
+        None => list_backups_in(backups_folder)?
+            .pop()
+            .ok_or_else(|| PackagesBackupError::NoBackups(backups_folder.to_path_buf())),
+    }
+}
+
+fn read_backup(folder: PathBuf) -> Result<PackagesBackup, PackagesBackupError> {
+    let manifest_path = folder.join(BACKUP_MANIFEST_FILENAME);
+
+    let content = std::fs::read(&manifest_path)
+        .map_err(|err| PackagesBackupError::ReadManifest(manifest_path.clone(), err))?;
+
+    let manifest = serde_json::from_slice(&content)
+        .map_err(|err| PackagesBackupError::ParseManifest(manifest_path, err))?;
+
+    Ok(PackagesBackup { folder, manifest })
+}
+
+pub fn print_backup_created(backup: &PackagesBackup) {
+    let bold = Style::new().bold();
+
+    eprintln!(
+        "Backup {bold}{id}{bold:#} of {count} package(s) has been saved to {path}",
+        id = backup.manifest.id,
+        count = backup.manifest.packages.len(),
+        path = backup.folder.display(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::TempDir;
+
+    fn installed_package(name: &str, version: &str) -> GetPackagesResponseItem {
+        serde_json::from_value(serde_json::json!({
+            "uId": uuid::Uuid::new_v4().to_string(),
+            "name": name,
+            "version": version,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn splits_installed_packages_case_insensitively() {
+        let installed = vec![installed_package("UsrPackage", "1.0.0")];
+
+        let (packages, new_packages) = split_installed_packages(
+            &installed,
+            &["usrpackage".to_owned(), "UsrNewPackage".to_owned()],
+        );
+
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].name, "UsrPackage");
+        assert_eq!(packages[0].version.as_deref(), Some("1.0.0"));
+        assert_eq!(new_packages, vec!["UsrNewPackage"]);
+    }
+
+    #[test]
+    fn creates_lists_and_finds_backups() {
+        let dir = TempDir::new("backups");
+        let time = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+
+        let first = write_backup(
+            &dir,
+            "https://dev.local",
+            time,
+            vec![PackagesBackupManifestItem {
+                name: "UsrPackage".to_owned(),
+                version: None,
+            }],
+            vec!["UsrNewPackage".to_owned()],
+            Some(b"zip".to_vec()),
+        )
+        .unwrap();
+
+        let second = write_backup(
+            &dir,
+            "https://dev.local",
+            time + time::Duration::minutes(1),
+            vec![],
+            vec![],
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(first.manifest().created_at, "2023-11-14T22:13:20Z");
+        assert_eq!(
+            std::fs::read(first.archive_path().unwrap()).unwrap(),
+            b"zip"
+        );
+        assert_eq!(second.archive_path(), None);
+
+        // Manifest created in another time zone is ordered by the actual time
+        let manifest_path = first.folder.join(BACKUP_MANIFEST_FILENAME);
+        let mut manifest: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&manifest_path).unwrap()).unwrap();
+        manifest["created_at"] = "2023-11-14T23:13:20+01:00".into();
+        std::fs::write(&manifest_path, serde_json::to_vec(&manifest).unwrap()).unwrap();
+
+        let ids = list_backups_in(&dir)
+            .unwrap()
+            .into_iter()
+            .map(|b| b.manifest.id)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            ids,
+            vec![first.manifest().id.clone(), second.manifest().id.clone()]
+        );
+
+        assert_eq!(
+            find_backup_in(&dir, None).unwrap().manifest().id,
+            second.manifest().id
+        );
+
+        let found = find_backup_in(&dir, Some(&first.manifest().id)).unwrap();
+        assert_eq!(found.manifest().new_packages, vec!["UsrNewPackage"]);
+
+        assert!(matches!(
+            find_backup_in(&dir, Some("missing")),
+            Err(PackagesBackupError::NotFound(..))
+        ));
+    }
+
+    #[test]
+    fn rejects_backup_id_with_path() {
+        let dir = TempDir::new("backups");
+
+        for id in ["..", ".", "../other", "a/b", "a\\b", "/tmp", ""] {
+            assert!(
+                matches!(
+                    find_backup_in(&dir, Some(id)),
+                    Err(PackagesBackupError::InvalidId(_))
+                ),
+                "{id}"
+            );
+        }
+
+        assert!(matches!(
+            find_backup_in(&dir, None),
+            Err(PackagesBackupError::NoBackups(_))
+        ));
+    }
+}
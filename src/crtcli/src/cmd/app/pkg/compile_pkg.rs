@@ -1,18 +1,25 @@
 use crate::app::CrtClient;
+use crate::app::workspace_explorer::BaseResponse;
 use crate::cfg::WorkspaceConfig;
 use crate::cmd::app;
+use crate::cmd::app::compile::BuildReportFormat;
 use crate::cmd::app::{AppCommand, print_build_response};
 use crate::cmd::cli::CommandResult;
 use crate::cmd::pkg::WorkspaceConfigCmdPkgExt;
+use crate::pkg::json::{PkgJsonWrapper, PkgPackageDescriptorJsonWrapper};
+use crate::pkg::utils::sort_packages_by_depends_on;
 use anstyle::{AnsiColor, Color, Style};
 use clap::Args;
 use std::error::Error;
+use std::path::Path;
 use std::sync::Arc;
 use thiserror::Error;
 
 #[derive(Args, Debug)]
 pub struct CompilePkgCommand {
     /// A space-separated or comma-separated list of package names to compile (default: packages names from ./workspace.crtcli.toml or ./descriptor.json)
+    ///
+    /// Multiple packages are compiled one by one in the order of their dependencies (DependsOn in local package descriptors or in Creatio).
     #[arg(value_delimiter = ',', value_hint = clap::ValueHint::Other)]
     pub package_names: Vec<String>,
 
@@ -23,6 +30,10 @@ pub struct CompilePkgCommand {
     /// Restart the Creatio application after successful package compilation
     #[arg(short, long)]
     pub restart: bool,
+
+    /// Format of the compilation errors report
+    #[arg(long, value_enum, default_value_t)]
+    pub report_format: BuildReportFormat,
 }
 
 #[derive(Debug, Error)]
@@ -33,72 +44,100 @@ pub enum CompilePkgCommandError {
 
 impl AppCommand for CompilePkgCommand {
     async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
-        let package_names = if self.package_names.is_empty() {
-            &WorkspaceConfig::load_default_from_current_dir()?
+        let (package_names, workspace_config) = if self.package_names.is_empty() {
+            let workspace_config = WorkspaceConfig::load_default_from_current_dir()?;
+
+            let package_names = workspace_config
                 .packages_or_print_error()?
                 .iter()
                 .map(|p| p.package_name().map(|x| x.into_owned()))
-                .collect::<Result<Vec<String>, _>>()?
+                .collect::<Result<Vec<String>, _>>()?;
+
+            (package_names, Some(workspace_config))
+        } else if self.package_names.len() > 1 {
+            (
+                self.package_names.clone(),
+                WorkspaceConfig::load_default_from_current_dir().ok(),
+            )
         } else {
-            &self.package_names
+            (self.package_names.clone(), None)
         };
 
-        if package_names.len() > 1 {
-            eprintln!(
-                "{style}warning (pkg-compile): multiple packages are specified, crtcli prefer to use app compile in this case{style:#}",
-                style = Style::new()
-                    .fg_color(Some(Color::Ansi(AnsiColor::BrightYellow)))
-                    .dimmed()
-            );
+        let ordered_package_names = match package_names.len() {
+            0 | 1 => package_names,
+            _ => sort_packages_by_depends_on(
+                find_packages_depends_on(&client, workspace_config.as_ref(), package_names).await,
+            )?,
+        };
 
-            return app::compile::CompileCommand {
-                restart: self.restart,
-                force_rebuild: self.force_rebuild,
-            }
-            .run(client)
-            .await;
-        }
+        let mut responses = vec![];
 
-        let package_name = package_names[0].as_str();
+        for (i, package_name) in ordered_package_names.iter().enumerate() {
+            let progress = spinner_precise!(
+                "{operation_str} {bold}{package_name}{bold:#} package at {bold}{url}{bold:#}{counter}",
+                bold = Style::new().bold(),
+                operation_str = if self.force_rebuild {
+                    "Rebuilding"
+                } else {
+                    "Compiling"
+                },
+                url = client.base_url(),
+                counter = match ordered_package_names.len() {
+                    1 => String::new(),
+                    len => format!(" ({}/{len})", i + 1),
+                }
+            );
 
-        let progress = spinner_precise!(
-            "{operation_str} {bold}{package_name}{bold:#} package at {bold}{url}{bold:#}",
-            bold = Style::new().bold(),
-            operation_str = if self.force_rebuild {
-                "Rebuilding"
+            let response = if self.force_rebuild {
+                client
+                    .workspace_explorer_service()
+                    .rebuild_package(package_name)
+                    .await?
             } else {
-                "Compiling"
-            },
-            url = client.base_url(),
-        );
-
-        let response = if self.force_rebuild {
-            client
-                .workspace_explorer_service()
-                .rebuild_package(package_name)
-                .await?
-        } else {
-            client
-                .workspace_explorer_service()
-                .build_package(package_name)
-                .await?
-        };
+                client
+                    .workspace_explorer_service()
+                    .build_package(package_name)
+                    .await?
+            };
 
-        progress.suspend(|| print_build_response(&response))?;
+            let is_failed = response.is_failed();
 
-        progress.finish_with_message(format!(
-            "{green}Package {green_bold}{package_name}{green_bold:#}{green} successfully {operation_str} at {green_bold}{url}{green_bold:#}{green}!{green:#}",
-            green = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green))),
-            green_bold = Style::new()
-                .fg_color(Some(Color::Ansi(AnsiColor::Green)))
-                .bold(),
-            operation_str = if self.force_rebuild {
-                "rebuilt"
-            } else {
-                "compiled"
-            },
-            url = client.base_url(),
-        ));
+            responses.push((package_name.as_str(), response));
+
+            if is_failed {
+                progress.finish_and_clear();
+
+                let skipped = &ordered_package_names[i + 1..];
+
+                if !skipped.is_empty() {
+                    eprintln!(
+                        "{style}warning (pkg-compile): compilation of {package_name} failed, skipped packages: {}{style:#}",
+                        skipped.join(", "),
+                        style = Style::new()
+                            .fg_color(Some(Color::Ansi(AnsiColor::BrightYellow)))
+                            .dimmed()
+                    );
+                }
+
+                break;
+            }
+
+            progress.finish_with_message(format!(
+                "{green}Package {green_bold}{package_name}{green_bold:#}{green} successfully {operation_str} at {green_bold}{url}{green_bold:#}{green}!{green:#}",
+                green = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green))),
+                green_bold = Style::new()
+                    .fg_color(Some(Color::Ansi(AnsiColor::Green)))
+                    .bold(),
+                operation_str = if self.force_rebuild {
+                    "rebuilt"
+                } else {
+                    "compiled"
+                },
+                url = client.base_url(),
+            ));
+        }
+
+        print_build_response(&combine_build_responses(responses), self.report_format)?;
 
         if self.restart {
             app::restart::RestartCommand
@@ -110,3 +149,110 @@ impl AppCommand for CompilePkgCommand {
         Ok(())
     }
 }
+
+/// Returns DependsOn of the packages from local package descriptors of the workspace,
+/// packages which are not found locally are looked up in Creatio using sql.
+///
+/// If sql runner is not available, such packages are compiled in the specified order.
+async fn find_packages_depends_on(
+    client: &CrtClient,
+    workspace_config: Option<&WorkspaceConfig>,
+    package_names: Vec<String>,
+) -> Vec<(String, Vec<String>)> {
+    let mut remote_lookup_failed = false;
+    let mut result = vec![];
+
+    for name in package_names {
+        if let Some(depends_on) = workspace_config.and_then(|c| find_local_depends_on(c, &name)) {
+            result.push((name, depends_on));
+            continue;
+        }
+
+        if remote_lookup_failed {
+            result.push((name, vec![]));
+            continue;
+        }
+
+        match client.sql_scripts().find_package_dependencies(&name).await {
+            Ok(depends_on) => result.push((name, depends_on)),
+            Err(err) => {
+                eprintln!(
+                    "{style}warning (pkg-compile): failed to get dependencies of {name} from Creatio, packages which are not found locally are compiled in the specified order: {err}{style:#}",
+                    style = Style::new()
+                        .fg_color(Some(Color::Ansi(AnsiColor::BrightYellow)))
+                        .dimmed()
+                );
+
+                remote_lookup_failed = true;
+                result.push((name, vec![]));
+            }
+        }
+    }
+
+    result
+}
+
+fn find_local_depends_on(
+    workspace_config: &WorkspaceConfig,
+    package_name: &str,
+) -> Option<Vec<String>> {
+    let package_folder = workspace_config.packages().iter().find(|p| {
+        p.package_name()
+            .is_ok_and(|name| name.eq_ignore_ascii_case(package_name))
+    })?;
+
+    let descriptor = read_package_descriptor(package_folder.path())?;
+
+    Some(
+        descriptor
+            .depends_on()?
+            .iter()
+            .filter_map(|d| d.name().map(|x| x.to_owned()))
+            .collect(),
+    )
+}
+
+fn read_package_descriptor(package_folder: &Path) -> Option<PkgPackageDescriptorJsonWrapper> {
+    PkgJsonWrapper::from_file(&package_folder.join(crate::pkg::paths::PKG_DESCRIPTOR_FILE))
+        .ok()
+        .map(PkgPackageDescriptorJsonWrapper::from)
+}
+
+/// Combines build responses of sequentially compiled packages into a single report.
+fn combine_build_responses(responses: Vec<(&str, BaseResponse)>) -> BaseResponse {
+    let multiple = responses.len() > 1;
+
+    let mut combined = BaseResponse {
+        success: true,
+        message: None,
+        error_info: None,
+        errors: None,
+    };
+
+    let mut messages = vec![];
+
+    for (package_name, response) in responses {
+        combined.success &= response.success;
+
+        if combined.error_info.is_none() {
+            combined.error_info = response.error_info;
+        }
+
+        if let Some(errors) = response.errors {
+            combined.errors.get_or_insert_default().extend(errors);
+        }
+
+        if let Some(message) = response.message {
+            messages.push(match multiple {
+                true => format!("{package_name}: {message}"),
+                false => message,
+            });
+        }
+    }
+
+    if !messages.is_empty() {
+        combined.message = Some(messages.join("\n> "));
+    }
+
+    combined
+}
@@ -0,0 +1,224 @@
+use crate::app::CrtClient;
+use crate::app::workspace_explorer::GetPackagesResponseItem;
+use crate::cfg::WorkspaceConfig;
+use crate::cmd::app::pkg::install_pkg::{
+    InstallPkgCommandOptions, install_package_from_reader_command,
+    install_package_from_stream_command,
+};
+use crate::cmd::app::{AppCommand, AppCommands, AppConnectionArgs};
+use crate::cmd::cli::{CommandDynError, CommandResult};
+use crate::cmd::pkg::WorkspaceConfigCmdPkgExt;
+use anstyle::{AnsiColor, Color, Style};
+use clap::Args;
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::io::AsyncReadExt;
+
+#[derive(Args, Debug)]
+pub struct CopyPkgCommand {
+    /// A space-separated or comma-separated list of package names to copy. Example: "UsrPackage,UsrPackage2" (default: packages names from ./workspace.crtcli.toml or ./descriptor.json)
+    #[arg(value_delimiter = ',', value_hint = clap::ValueHint::Other)]
+    packages: Vec<String>,
+
+    /// Destination Creatio Base URL or App alias from .crtcli.toml
+    #[arg(long, value_name = "URL/APP", value_hint = clap::ValueHint::Url)]
+    to: String,
+
+    /// Print versions of the packages on both instances before transfer
+    #[arg(long)]
+    diff: bool,
+
+    #[command(flatten)]
+    install_pkg_options: InstallPkgCommandOptions,
+
+    #[arg(skip)]
+    connection_args: AppConnectionArgs,
+}
+
+#[derive(Debug, Error)]
+pub enum CopyPkgCommandError {
+    #[error("failed to setup destination app client: {0}")]
+    DestinationClient(#[source] CommandDynError),
+
+    #[error("source and destination are the same Creatio instance: {0}")]
+    SameInstance(String),
+
+    #[error("failed to download packages from source instance: {0}")]
+    Download(#[source] crate::app::CrtClientError),
+
+    #[error("failed to read packages archive from source instance: {0}")]
+    ReadArchive(#[source] std::io::Error),
+
+    #[error("failed to get packages list for versions diff: {0}")]
+    GetPackages(#[source] crate::app::CrtClientError),
+}
+
+impl CopyPkgCommand {
+    pub fn with_connection_args(self, connection_args: AppConnectionArgs) -> Self {
+        Self {
+            connection_args,
+            ..self
+        }
+    }
+}
+
+impl AppCommand for CopyPkgCommand {
+    async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
+        let packages = if self.packages.is_empty() {
+            &WorkspaceConfig::load_default_from_current_dir()?
+                .packages_or_print_error()?
+                .iter()
+                .map(|p| p.package_name().map(|x| x.into_owned()))
+                .collect::<Result<Vec<String>, _>>()?
+        } else {
+            &self.packages
+        };
+
+        let destination_client = Arc::new(
+            AppCommands::setup_client_by_url_or_alias(&self.to, &self.connection_args)
+                .map_err(CopyPkgCommandError::DestinationClient)?,
+        );
+
+        if destination_client
+            .base_url()
+            .trim_end_matches('/')
+            .eq_ignore_ascii_case(client.base_url().trim_end_matches('/'))
+        {
+            return Err(CopyPkgCommandError::SameInstance(client.base_url().to_owned()).into());
+        }
+
+        if self.diff {
+            print_packages_versions_diff(&client, &destination_client, packages).await?;
+        }
+
+        let progress = spinner!(
+            "Downloading {bold}{target}{bold:#} {target_label} from {bold}{url}{bold:#}",
+            target = packages.join(", "),
+            target_label = match packages.len() {
+                0 | 1 => "package",
+                _ => "packages",
+            },
+            bold = Style::new().bold(),
+            url = client.base_url()
+        );
+
+        let (mut package_reader, package_length) = client
+            .package_installer_service()
+            .get_zip_packages_with_length(packages)
+            .await
+            .map_err(CopyPkgCommandError::Download)?;
+
+        progress.finish_and_clear();
+
+        let package_name = crate::cmd::utils::generate_zip_package_filename(match packages.len() {
+            1 => &packages[0],
+            _ => "Packages",
+        });
+
+        match package_length {
+            Some(package_length) => {
+                install_package_from_reader_command(
+                    destination_client,
+                    package_reader,
+                    package_length as usize,
+                    package_name,
+                    &self.install_pkg_options,
+                )
+                .await?
+            }
+            // Chunked upload requires the total length, so the archive is buffered if the server does not report it
+            None => {
+                let mut package_content = vec![];
+
+                let progress = spinner!(
+                    "Reading packages archive from {bold}{url}{bold:#}",
+                    bold = Style::new().bold(),
+                    url = client.base_url()
+                );
+
+                package_reader
+                    .read_to_end(&mut package_content)
+                    .await
+                    .map_err(CopyPkgCommandError::ReadArchive)?;
+
+                progress.finish_and_clear();
+
+                install_package_from_stream_command(
+                    destination_client,
+                    package_content,
+                    package_name,
+                    &self.install_pkg_options,
+                )
+                .await?
+            }
+        }
+
+        Ok(())
+    }
+}
+
+async fn print_packages_versions_diff(
+    source_client: &CrtClient,
+    destination_client: &CrtClient,
+    packages: &[String],
+) -> Result<(), CopyPkgCommandError> {
+    let progress = spinner!(
+        "Fetching packages list from {bold}{source}{bold:#} and {bold}{destination}{bold:#}",
+        bold = Style::new().bold(),
+        source = source_client.base_url(),
+        destination = destination_client.base_url(),
+    );
+
+    let source_service = source_client.workspace_explorer_service();
+    let destination_service = destination_client.workspace_explorer_service();
+
+    let (source_packages, destination_packages) = tokio::try_join!(
+        source_service.get_packages(),
+        destination_service.get_packages()
+    )
+    .map_err(CopyPkgCommandError::GetPackages)?;
+
+    progress.finish_and_clear();
+
+    let find_version = |list: &[GetPackagesResponseItem], name: &str| {
+        list.iter()
+            .find(|p| p.name().eq_ignore_ascii_case(name))
+            .map(|p| p.version().unwrap_or("?").to_owned())
+    };
+
+    let bold = Style::new().bold();
+    let dimmed = Style::new().dimmed();
+    let green = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green)));
+    let yellow = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Yellow)));
+    let red = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Red)));
+
+    let max_name_len = packages.iter().map(|p| p.len()).max().unwrap_or(0);
+
+    eprintln!(
+        "{bold}Packages versions{bold:#} {dimmed}({source} → {destination}){dimmed:#}",
+        source = source_client.base_url(),
+        destination = destination_client.base_url(),
+    );
+
+    for package in packages {
+        let source_version = find_version(&source_packages, package);
+        let destination_version = find_version(&destination_packages, package);
+
+        let status = match (&source_version, &destination_version) {
+            (None, _) => format!("{red}missing in source{red:#}"),
+            (Some(_), None) => format!("{green}new{green:#}"),
+            (Some(s), Some(d)) if s == d => format!("{dimmed}same{dimmed:#}"),
+            (Some(_), Some(_)) => format!("{yellow}changed{yellow:#}"),
+        };
+
+        eprintln!(
+            "  {bold}{package:<max_name_len$}{bold:#}  {source} → {destination}  {status}",
+            source = source_version.as_deref().unwrap_or("—"),
+            destination = destination_version.as_deref().unwrap_or("—"),
+        );
+    }
+
+    eprintln!();
+
+    Ok(())
+}
@@ -0,0 +1,973 @@
+use crate::app::entity_schema::{DataValueKind, RuntimeEntitySchema, RuntimeEntitySchemaColumn};
+use crate::app::{CrtClient, CrtClientError};
+use crate::cfg::package::combine_apply_config_from_args_and_config;
+use crate::cfg::{PkgConfig, WorkspaceConfig};
+use crate::cmd::app::AppCommand;
+use crate::cmd::cli::CommandResult;
+use crate::pkg::json::*;
+use crate::pkg::transforms::{
+    CombinedPkgFileTransform, CombinedPkgFileTransformError, PkgApplyFeatures, PkgFileTransform,
+};
+use anstream::stdout;
+use anstyle::{AnsiColor, Color, Style};
+use clap::Args;
+use indexmap::IndexMap;
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use thiserror::Error;
+use time::format_description::well_known::Rfc3339;
+use time::macros::format_description;
+use time::{Date, OffsetDateTime, PrimitiveDateTime};
+
+const ID_COLUMN_NAME: &str = "Id";
+
+const DATA_DESCRIPTOR_FILE: &str = "descriptor.json";
+const DATA_DATA_FILE: &str = "data.json";
+const DATA_LOCALIZATION_FOLDER: &str = "Localization";
+
+/// Audit columns which are skipped when `--columns` option is not specified
+const SYSTEM_COLUMN_NAMES: [&str; 5] = [
+    "CreatedOn",
+    "CreatedBy",
+    "ModifiedOn",
+    "ModifiedBy",
+    "ProcessListeners",
+];
+
+#[derive(Args, Debug)]
+pub struct ExportPkgDataCommand {
+    /// Name of the package which will contain the data binding
+    #[arg(value_hint = clap::ValueHint::Other)]
+    package_name: String,
+
+    /// Name of the data binding (folder name inside the package Data folder)
+    #[arg(value_hint = clap::ValueHint::Other)]
+    binding_name: String,
+
+    /// Entity schema name to export data from. Example: "Contact"
+    #[arg(short, long, value_hint = clap::ValueHint::Other)]
+    entity: String,
+
+    /// SQL condition to filter exported records. Example: "Name" LIKE 'Usr%'
+    #[arg(short, long = "where", value_name = "CONDITION", value_hint = clap::ValueHint::Other)]
+    where_condition: Option<String>,
+
+    /// A comma-separated list of columns to export (default: all entity columns except system ones)
+    #[arg(short, long, value_delimiter = ',', value_hint = clap::ValueHint::Other)]
+    columns: Vec<String>,
+
+    /// A comma-separated list of key columns which are used to find existing records during installation
+    #[arg(short, long, value_delimiter = ',', default_value = ID_COLUMN_NAME, value_hint = clap::ValueHint::Other)]
+    key_columns: Vec<String>,
+
+    /// Package folder where the data binding will be saved (default: folder of the package from ./workspace.crtcli.toml or ./descriptor.json)
+    #[arg(short, long, value_hint = clap::ValueHint::DirPath)]
+    destination: Option<PathBuf>,
+
+    /// Do not export localized column values (Localization/data.<culture>.json files)
+    #[arg(long)]
+    skip_localizations: bool,
+
+    #[command(flatten)]
+    apply_features: Option<PkgApplyFeatures>,
+}
+
+#[derive(Debug, Error)]
+pub enum ExportPkgDataCommandError {
+    #[error("invalid {0} name '{1}', only latin letters, digits and underscores are allowed")]
+    InvalidName(&'static str, String),
+
+    #[error(
+        "cannot find folder of package '{0}' in ./workspace.crtcli.toml or ./descriptor.json, please specify the package folder with --destination option"
+    )]
+    PackageFolderNotFound(String),
+
+    #[error("failed to read package descriptor: {0}")]
+    ReadPackageDescriptor(#[from] PkgJsonWrapperCreateError),
+
+    #[error("package folder {0} contains package '{1}', but '{2}' was expected")]
+    PackageNameMismatch(PathBuf, String, String),
+
+    #[error("package descriptor.json was found, but the package uid value is null")]
+    PackageUidValueNull,
+
+    #[error("failed to get entity schema '{0}': {1}")]
+    GetEntitySchema(String, #[source] CrtClientError),
+
+    #[error("column '{0}' is not found in entity schema '{1}'")]
+    ColumnNotFound(String, String),
+
+    #[error("column '{0}' has unsupported data value type ({1})")]
+    UnsupportedColumn(String, u32),
+
+    #[error("failed to query entity data: {0}")]
+    QueryData(#[source] CrtClientError),
+
+    #[error("sql query was executed, but no table was returned")]
+    QueryDataNoTable,
+
+    #[error(
+        "failed to query localized values, use --skip-localizations to export data without them: {0}"
+    )]
+    QueryLocalizations(#[source] CrtClientError),
+
+    #[error("failed to convert value of column '{column}': {err}")]
+    ConvertValue { column: String, err: String },
+
+    #[error("failed to sort data binding rows: {0}")]
+    SortData(#[from] PkgDataDataSortingError),
+
+    #[error("failed to sort data binding columns: {0}")]
+    SortDescriptor(#[from] PkgDataDescriptorSortingError),
+
+    #[error("failed to serialize {0}: {1}")]
+    Serialize(PathBuf, #[source] PkgJsonWrapperSerializeError),
+
+    #[error("failed to apply transforms to {0}: {1}")]
+    ApplyTransforms(PathBuf, #[source] CombinedPkgFileTransformError),
+
+    #[error("failed to write {0}: {1}")]
+    Write(PathBuf, #[source] std::io::Error),
+}
+
+impl AppCommand for ExportPkgDataCommand {
+    async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
+        validate_name("entity", &self.entity)?;
+        validate_name("data binding", &self.binding_name)?;
+
+        let package_folder = self.resolve_package_folder()?;
+        let package_uid = self.read_package_uid(&package_folder)?;
+
+        let progress = spinner!(
+            "Exporting {bold}{entity}{bold:#} data from {bold}{url}{bold:#}",
+            bold = Style::new().bold(),
+            entity = self.entity,
+            url = client.base_url(),
+        );
+
+        let schema = client
+            .entity_schema_service()
+            .get_runtime_entity_schema(&self.entity)
+            .await
+            .map_err(|err| ExportPkgDataCommandError::GetEntitySchema(self.entity.clone(), err))?;
+
+        let columns = self.select_columns(&schema)?;
+        let key_columns = self
+            .key_columns
+            .iter()
+            .map(|name| find_column(&schema, name))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let records = self.query_records(&client, &columns).await?;
+
+        let rows = records
+            .iter()
+            .map(|record| build_data_row(&columns, record))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let localizations = if self.skip_localizations {
+            BTreeMap::new()
+        } else {
+            self.query_localizations(&client, &columns, &key_columns, &records)
+                .await?
+        };
+
+        progress.finish_and_clear();
+
+        let pkg_config = PkgConfig::from_package_folder(&package_folder)?;
+        let transform = combine_apply_config_from_args_and_config(
+            (self.apply_features.as_ref(), None),
+            pkg_config.as_ref().map(|x| x.apply()),
+        )
+        .unwrap_or_default()
+        .apply()
+        .build_combined_transform();
+
+        let binding_folder = PathBuf::from(crate::pkg::paths::DATA_FOLDER).join(&self.binding_name);
+        let mut stdout = stdout().lock();
+
+        let data_path = binding_folder.join(DATA_DATA_FILE);
+        let data = apply_transform(
+            &transform,
+            &data_path,
+            serialize_data_data(json!({ "PackageData": rows }))?,
+        )?;
+
+        // Existing file has transforms applied, so it is compared with the transformed content
+        let is_data_changed = std::fs::read(package_folder.join(&data_path))
+            .map_or(true, |existing| Some(&existing) != data.as_ref());
+
+        let descriptor_path = binding_folder.join(DATA_DESCRIPTOR_FILE);
+        let descriptor = apply_transform(
+            &transform,
+            &descriptor_path,
+            serialize_data_descriptor(build_data_descriptor(
+                &package_folder.join(&descriptor_path),
+                &self.binding_name,
+                &package_uid,
+                &schema,
+                &columns,
+                &key_columns,
+                is_data_changed,
+            ))?,
+        )?;
+
+        write_binding_file(&package_folder, &descriptor_path, descriptor, &mut stdout)?;
+
+        write_binding_file(&package_folder, &data_path, data, &mut stdout)?;
+
+        if !self.skip_localizations {
+            let localization_folder = binding_folder.join(DATA_LOCALIZATION_FOLDER);
+
+            for path in list_stale_localization_files(
+                &package_folder.join(&localization_folder),
+                &localizations,
+            ) {
+                write_binding_file(
+                    &package_folder,
+                    &localization_folder.join(path),
+                    None,
+                    &mut stdout,
+                )?;
+            }
+
+            for (culture, rows) in localizations {
+                let localization_path = localization_folder.join(format!("data.{culture}.json"));
+                let content = apply_transform(
+                    &transform,
+                    &localization_path,
+                    serialize_data_data(json!({
+                        "PackageData": rows.into_values().collect::<Vec<_>>()
+                    }))?,
+                )?;
+
+                write_binding_file(&package_folder, &localization_path, content, &mut stdout)?;
+            }
+        }
+
+        spinner!(
+            finished_in = progress.elapsed(),
+            "{green}Data binding {green_bold}{binding}{green_bold:#}{green} with {green_bold}{count}{green_bold:#}{green} record(s) successfully exported to {green_bold}{folder}{green_bold:#}{green}!{green:#}",
+            green = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green))),
+            green_bold = Style::new()
+                .fg_color(Some(Color::Ansi(AnsiColor::Green)))
+                .bold(),
+            binding = self.binding_name,
+            count = records.len(),
+            folder = package_folder.join(&binding_folder).display(),
+        );
+
+        Ok(())
+    }
+}
+
+impl ExportPkgDataCommand {
+    fn resolve_package_folder(&self) -> Result<PathBuf, ExportPkgDataCommandError> {
+        if let Some(destination) = &self.destination {
+            return Ok(destination.to_owned());
+        }
+
+        let workspace_config = WorkspaceConfig::load_default_from_current_dir().ok();
+
+        let workspace_package_folder = workspace_config
+            .iter()
+            .flat_map(|c| c.packages())
+            .find(|p| {
+                p.package_name()
+                    .is_ok_and(|name| name.eq_ignore_ascii_case(&self.package_name))
+            })
+            .map(|p| p.path().to_path_buf());
+
+        if let Some(package_folder) = workspace_package_folder {
+            return Ok(package_folder);
+        }
+
+        // The current directory is the package folder, the package name is checked by read_package_uid
+        if Path::new(crate::pkg::paths::PKG_DESCRIPTOR_FILE).is_file() {
+            return Ok(PathBuf::from("."));
+        }
+
+        Err(ExportPkgDataCommandError::PackageFolderNotFound(
+            self.package_name.clone(),
+        ))
+    }
+
+    fn read_package_uid(&self, package_folder: &Path) -> Result<String, ExportPkgDataCommandError> {
+        let descriptor = PkgPackageDescriptorJsonWrapper::from(PkgJsonWrapper::from_file(
+            &package_folder.join(crate::pkg::paths::PKG_DESCRIPTOR_FILE),
+        )?);
+
+        let descriptor_name = descriptor.name().unwrap_or_default();
+
+        if !descriptor_name.eq_ignore_ascii_case(&self.package_name) {
+            return Err(ExportPkgDataCommandError::PackageNameMismatch(
+                package_folder.to_path_buf(),
+                descriptor_name.to_owned(),
+                self.package_name.clone(),
+            ));
+        }
+
+        descriptor
+            .uid()
+            .map(|x| x.to_owned())
+            .ok_or(ExportPkgDataCommandError::PackageUidValueNull)
+    }
+
+    fn select_columns<'s>(
+        &self,
+        schema: &'s RuntimeEntitySchema,
+    ) -> Result<Vec<&'s RuntimeEntitySchemaColumn>, ExportPkgDataCommandError> {
+        let mut columns = if self.columns.is_empty() {
+            schema
+                .columns
+                .iter()
+                .filter(|c| c.value_kind() != DataValueKind::Unsupported)
+                .filter(|c| !SYSTEM_COLUMN_NAMES.contains(&c.name.as_str()))
+                .collect::<Vec<_>>()
+        } else {
+            self.columns
+                .iter()
+                .map(|name| find_column(schema, name))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        for key_column in &self.key_columns {
+            let key_column = find_column(schema, key_column)?;
+
+            if !columns.iter().any(|c| c.uid == key_column.uid) {
+                columns.insert(0, key_column);
+            }
+        }
+
+        if let Some(column) = columns
+            .iter()
+            .find(|c| c.value_kind() == DataValueKind::Unsupported)
+        {
+            return Err(ExportPkgDataCommandError::UnsupportedColumn(
+                column.name.clone(),
+                column.data_value_type,
+            ));
+        }
+
+        Ok(columns)
+    }
+
+    async fn query_records(
+        &self,
+        client: &CrtClient,
+        columns: &[&RuntimeEntitySchemaColumn],
+    ) -> Result<Vec<Map<String, Value>>, ExportPkgDataCommandError> {
+        let mut select_columns = columns
+            .iter()
+            .map(|c| c.db_column_name())
+            .collect::<Vec<_>>();
+
+        // Id column is always required to match localized values with records
+        if !select_columns.iter().any(|c| c == ID_COLUMN_NAME) {
+            select_columns.push(ID_COLUMN_NAME.to_owned());
+        }
+
+        let query = format!(
+            r#"SELECT {columns} FROM "{entity}"{where_clause};"#,
+            columns = select_columns
+                .iter()
+                .map(|c| format!(r#""{c}""#))
+                .collect::<Vec<_>>()
+                .join(", "),
+            entity = self.entity,
+            where_clause = self.format_where_clause(),
+        );
+
+        client
+            .sql(&query)
+            .await
+            .map_err(ExportPkgDataCommandError::QueryData)?
+            .table
+            .ok_or(ExportPkgDataCommandError::QueryDataNoTable)
+    }
+
+    async fn query_localizations(
+        &self,
+        client: &CrtClient,
+        columns: &[&RuntimeEntitySchemaColumn],
+        key_columns: &[&RuntimeEntitySchemaColumn],
+        records: &[Map<String, Value>],
+    ) -> Result<BTreeMap<String, IndexMap<String, Value>>, ExportPkgDataCommandError> {
+        let mut localizations: BTreeMap<String, IndexMap<String, Value>> = BTreeMap::new();
+
+        if records.is_empty() {
+            return Ok(localizations);
+        }
+
+        let query = format!(
+            r#"SELECT "c"."Name" AS "Culture", "l"."RecordId", "l"."ColumnUId", "l"."Value"
+            FROM "Sys{entity}Lcz" "l"
+            INNER JOIN "SysCulture" "c" ON "c"."Id" = "l"."SysCultureId"
+            WHERE "l"."RecordId" IN (SELECT "Id" FROM "{entity}"{where_clause});"#,
+            entity = self.entity,
+            where_clause = self.format_where_clause(),
+        );
+
+        let table = client
+            .sql(&query)
+            .await
+            .map_err(ExportPkgDataCommandError::QueryLocalizations)?
+            .table
+            .unwrap_or_default();
+
+        let records_by_id = records
+            .iter()
+            .filter_map(|r| {
+                get_record_value(r, ID_COLUMN_NAME)
+                    .and_then(|v| v.as_str())
+                    .map(|id| (id.to_lowercase(), r))
+            })
+            .collect::<IndexMap<_, _>>();
+
+        for lcz in &table {
+            let (Some(culture), Some(record_id), Some(column_uid)) = (
+                get_record_value(lcz, "Culture").and_then(|v| v.as_str()),
+                get_record_value(lcz, "RecordId").and_then(|v| v.as_str()),
+                get_record_value(lcz, "ColumnUId").and_then(|v| v.as_str()),
+            ) else {
+                continue;
+            };
+
+            let Some(column) = columns
+                .iter()
+                .find(|c| c.uid.eq_ignore_ascii_case(column_uid))
+            else {
+                continue;
+            };
+
+            let record_id = record_id.to_lowercase();
+
+            let Some(record) = records_by_id.get(&record_id) else {
+                continue;
+            };
+
+            let Some(value) = get_record_value(lcz, "Value")
+                .map(|v| convert_value(column.value_kind(), v))
+                .transpose()
+                .map_err(|err| ExportPkgDataCommandError::ConvertValue {
+                    column: column.name.clone(),
+                    err,
+                })?
+                .flatten()
+            else {
+                continue;
+            };
+
+            let row = match localizations
+                .entry(culture.to_owned())
+                .or_default()
+                .entry(record_id)
+            {
+                indexmap::map::Entry::Occupied(entry) => entry.into_mut(),
+                indexmap::map::Entry::Vacant(entry) => {
+                    entry.insert(build_data_row(key_columns, record)?)
+                }
+            };
+
+            row["Row"]
+                .as_array_mut()
+                .expect("data row is always an array")
+                .push(json!({
+                    "SchemaColumnUId": column.uid.to_lowercase(),
+                    "Value": value,
+                }));
+        }
+
+        Ok(localizations)
+    }
+
+    fn format_where_clause(&self) -> String {
+        match &self.where_condition {
+            Some(condition) if !condition.trim().is_empty() => format!(" WHERE {condition}"),
+            _ => String::new(),
+        }
+    }
+}
+
+fn validate_name(kind: &'static str, name: &str) -> Result<(), ExportPkgDataCommandError> {
+    if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        Ok(())
+    } else {
+        Err(ExportPkgDataCommandError::InvalidName(
+            kind,
+            name.to_owned(),
+        ))
+    }
+}
+
+fn find_column<'s>(
+    schema: &'s RuntimeEntitySchema,
+    column_name: &str,
+) -> Result<&'s RuntimeEntitySchemaColumn, ExportPkgDataCommandError> {
+    schema.find_column(column_name).ok_or_else(|| {
+        ExportPkgDataCommandError::ColumnNotFound(column_name.to_owned(), schema.name.clone())
+    })
+}
+
+fn get_record_value<'r>(record: &'r Map<String, Value>, column_name: &str) -> Option<&'r Value> {
+    record.get(column_name).or_else(|| {
+        record
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(column_name))
+            .map(|(_, v)| v)
+    })
+}
+
+fn build_data_row(
+    columns: &[&RuntimeEntitySchemaColumn],
+    record: &Map<String, Value>,
+) -> Result<Value, ExportPkgDataCommandError> {
+    let mut row = vec![];
+
+    for column in columns {
+        let value = get_record_value(record, &column.db_column_name())
+            .map(|v| convert_value(column.value_kind(), v))
+            .transpose()
+            .map_err(|err| ExportPkgDataCommandError::ConvertValue {
+                column: column.name.clone(),
+                err,
+            })?
+            .flatten();
+
+        // Null values are omitted, the same way as Creatio does it
+        if let Some(value) = value {
+            row.push(json!({
+                "SchemaColumnUId": column.uid.to_lowercase(),
+                "Value": value,
+            }));
+        }
+    }
+
+    Ok(json!({ "Row": row }))
+}
+
+fn convert_value(kind: DataValueKind, value: &Value) -> Result<Option<Value>, String> {
+    if value.is_null() {
+        return Ok(None);
+    }
+
+    match kind {
+        DataValueKind::Guid | DataValueKind::Lookup => match value {
+            Value::String(s) if s.is_empty() => Ok(None),
+            Value::String(s) => Ok(Some(Value::String(s.to_lowercase()))),
+            _ => Err(format!("expected guid string, but got {value}")),
+        },
+        DataValueKind::Text => match value {
+            Value::String(_) => Ok(Some(value.to_owned())),
+            _ => Ok(Some(Value::String(value.to_string()))),
+        },
+        DataValueKind::Integer | DataValueKind::Float => match value {
+            Value::Number(_) => Ok(Some(value.to_owned())),
+            Value::String(s) if s.is_empty() => Ok(None),
+            Value::String(s) => s
+                .trim()
+                .parse::<serde_json::Number>()
+                .map(|n| Some(Value::Number(n)))
+                .map_err(|_| format!("expected number, but got {s}")),
+            _ => Err(format!("expected number, but got {value}")),
+        },
+        DataValueKind::Boolean => match value {
+            Value::Bool(_) => Ok(Some(value.to_owned())),
+            Value::Number(n) => Ok(Some(Value::Bool(n.as_f64() != Some(0.0)))),
+            Value::String(s) if s.is_empty() => Ok(None),
+            Value::String(s) => match s.trim().to_lowercase().as_str() {
+                "true" | "1" => Ok(Some(Value::Bool(true))),
+                "false" | "0" => Ok(Some(Value::Bool(false))),
+                _ => Err(format!("expected boolean, but got {s}")),
+            },
+            _ => Err(format!("expected boolean, but got {value}")),
+        },
+        DataValueKind::DateTime => match value {
+            Value::String(s) if s.is_empty() => Ok(None),
+            Value::String(s) => to_ms_date(s.trim()).map(|d| Some(Value::String(d))),
+            _ => Err(format!("expected date/time string, but got {value}")),
+        },
+        DataValueKind::Unsupported => Err("unsupported data value type".to_owned()),
+    }
+}
+
+/// Converts the date/time value returned by sql runner to the `/Date(<ms>)/` format of data bindings.
+fn to_ms_date(value: &str) -> Result<String, String> {
+    if value.starts_with("/Date(") {
+        return Ok(value.to_owned());
+    }
+
+    let datetime = OffsetDateTime::parse(value, &Rfc3339)
+        .or_else(|_| {
+            PrimitiveDateTime::parse(
+                &value.replacen(' ', "T", 1),
+                format_description!(
+                    "[year]-[month]-[day]T[hour]:[minute]:[second][optional [.[subsecond]]]"
+                ),
+            )
+            .map(|x| x.assume_utc())
+        })
+        .or_else(|_| {
+            Date::parse(value, format_description!("[year]-[month]-[day]"))
+                .map(|x| x.midnight().assume_utc())
+        })
+        .map_err(|_| format!("unrecognized date/time format: {value}"))?;
+
+    Ok(format!(
+        "/Date({})/",
+        datetime.unix_timestamp_nanos() / 1_000_000
+    ))
+}
+
+fn build_data_descriptor(
+    existing_descriptor_path: &Path,
+    binding_name: &str,
+    package_uid: &str,
+    schema: &RuntimeEntitySchema,
+    columns: &[&RuntimeEntitySchemaColumn],
+    key_columns: &[&RuntimeEntitySchemaColumn],
+    is_data_changed: bool,
+) -> Value {
+    let existing = PkgJsonWrapper::from_file(existing_descriptor_path)
+        .ok()
+        .map(|mut x| x.value["Descriptor"].take());
+
+    let columns = columns
+        .iter()
+        .map(|c| {
+            json!({
+                "ColumnUId": c.uid.to_lowercase(),
+                "IsKey": key_columns.iter().any(|k| k.uid == c.uid),
+                "ColumnName": c.name,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let existing_value = |key: &str| {
+        existing
+            .as_ref()
+            .map(|x| &x[key])
+            .filter(|x| !x.is_null())
+            .cloned()
+    };
+
+    // Keep previous modification date to avoid insignificant changes if nothing was changed
+    let is_columns_changed = existing_value("Columns").is_none_or(|existing_columns| {
+        let mut existing_columns = existing_columns.as_array().cloned().unwrap_or_default();
+        let mut columns = columns.clone();
+
+        existing_columns.sort_by(|a, b| a["ColumnUId"].as_str().cmp(&b["ColumnUId"].as_str()));
+        columns.sort_by(|a, b| a["ColumnUId"].as_str().cmp(&b["ColumnUId"].as_str()));
+
+        existing_columns != columns
+    });
+
+    let modified_on_utc = existing_value("ModifiedOnUtc")
+        .filter(|_| !is_data_changed && !is_columns_changed)
+        .unwrap_or_else(|| {
+            Value::String(format!(
+                "/Date({})/",
+                OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000
+            ))
+        });
+
+    json!({
+        "Descriptor": {
+            "UId": existing_value("UId")
+                .unwrap_or_else(|| Value::String(uuid::Uuid::new_v4().to_string())),
+            "PackageUId": package_uid,
+            "EntitySchemaUId": schema.uid.to_lowercase(),
+            "ModifiedOnUtc": modified_on_utc,
+            "Name": binding_name,
+            "InstallType": existing_value("InstallType").unwrap_or(json!(0)),
+            "Columns": columns,
+        }
+    })
+}
+
+fn serialize_data_data(value: Value) -> Result<Vec<u8>, ExportPkgDataCommandError> {
+    let mut wrapper = PkgDataDataJsonWrapper::from(PkgJsonWrapper::from_value(value, true));
+    let mut content = vec![];
+
+    wrapper.apply_sorting()?;
+    wrapper
+        .serialize(&mut content)
+        .map_err(|err| ExportPkgDataCommandError::Serialize(PathBuf::from(DATA_DATA_FILE), err))?;
+
+    Ok(content)
+}
+
+fn serialize_data_descriptor(value: Value) -> Result<Vec<u8>, ExportPkgDataCommandError> {
+    let mut wrapper = PkgDataDescriptorJsonWrapper::from(PkgJsonWrapper::from_value(value, true));
+    let mut content = vec![];
+
+    wrapper.apply_sorting()?;
+    wrapper.serialize(&mut content).map_err(|err| {
+        ExportPkgDataCommandError::Serialize(PathBuf::from(DATA_DESCRIPTOR_FILE), err)
+    })?;
+
+    Ok(content)
+}
+
+fn list_stale_localization_files(
+    localization_folder: &Path,
+    localizations: &BTreeMap<String, IndexMap<String, Value>>,
+) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(localization_folder) else {
+        return vec![];
+    };
+
+    entries
+        .filter_map(|e| e.ok())
+        .map(|e| PathBuf::from(e.file_name()))
+        .filter(|filename| {
+            filename
+                .to_str()
+                .and_then(|f| f.strip_prefix("data."))
+                .and_then(|f| f.strip_suffix(".json"))
+                .is_some_and(|culture| !localizations.contains_key(culture))
+        })
+        .collect()
+}
+
+/// Applies the package transforms to the serialized binding file, as `pkg apply` would do.
+///
+/// Returns `None` if the file should not exist after the transforms.
+fn apply_transform(
+    transform: &CombinedPkgFileTransform,
+    relative_path: &Path,
+    content: Vec<u8>,
+) -> Result<Option<Vec<u8>>, ExportPkgDataCommandError> {
+    let relative_path_str = relative_path.to_string_lossy();
+
+    if !transform.is_applicable(&relative_path_str) {
+        return Ok(Some(content));
+    }
+
+    transform
+        .transform(&relative_path_str, content)
+        .map_err(|err| ExportPkgDataCommandError::ApplyTransforms(relative_path.to_path_buf(), err))
+}
+
+/// Writes already transformed content of the binding file, `None` deletes the file.
+fn write_binding_file(
+    package_folder: &Path,
+    relative_path: &Path,
+    content: Option<Vec<u8>>,
+    stdout: impl Write,
+) -> Result<bool, ExportPkgDataCommandError> {
+    let path = package_folder.join(relative_path);
+    let relative_path_str = relative_path.to_string_lossy();
+
+    if content.is_some()
+        && let Some(parent) = path.parent()
+    {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| ExportPkgDataCommandError::Write(path.clone(), err))?;
+    }
+
+    let existing_content = path.exists().then(|| std::fs::read(&path)).transpose();
+
+    let existing_content =
+        existing_content.map_err(|err| ExportPkgDataCommandError::Write(path.clone(), err))?;
+
+    crate::pkg::utils::cmp_file_content_and_apply_with_log(
+        &path,
+        &relative_path_str,
+        existing_content,
+        content,
+        false,
+        stdout,
+    )
+    .map_err(|err| ExportPkgDataCommandError::Write(path, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::TempDir;
+
+    fn column(uid: &str, name: &str, data_value_type: u32) -> RuntimeEntitySchemaColumn {
+        RuntimeEntitySchemaColumn {
+            uid: uid.to_owned(),
+            name: name.to_owned(),
+            data_value_type,
+        }
+    }
+
+    fn test_schema() -> RuntimeEntitySchema {
+        RuntimeEntitySchema {
+            uid: "25D7C1AB-1DE0-4501-B402-02E0E5A72D6E".to_owned(),
+            name: "UsrSettings".to_owned(),
+            columns: vec![
+                column("AE0E45CA-C495-4FE7-A39D-3AB7278E1617", "Id", 0),
+                column("736C30A7-C0EC-4FA9-B034-2552B319B633", "Name", 1),
+                column("3E0DF5B1-1E0C-4E1B-8C5B-2B8A8D0E4E55", "Type", 10),
+                column("F1B8F4A5-7A77-4A34-A5F1-9D4E1C6F2C4B", "IsActive", 12),
+            ],
+        }
+    }
+
+    #[test]
+    fn converts_values_by_data_value_kind() {
+        assert_eq!(
+            convert_value(
+                DataValueKind::Lookup,
+                &json!("3E0DF5B1-1E0C-4E1B-8C5B-2B8A8D0E4E55")
+            ),
+            Ok(Some(json!("3e0df5b1-1e0c-4e1b-8c5b-2b8a8d0e4e55")))
+        );
+        assert_eq!(convert_value(DataValueKind::Guid, &json!("")), Ok(None));
+        assert_eq!(convert_value(DataValueKind::Text, &json!(null)), Ok(None));
+        assert_eq!(
+            convert_value(DataValueKind::Text, &json!(42)),
+            Ok(Some(json!("42")))
+        );
+        assert_eq!(
+            convert_value(DataValueKind::Integer, &json!(" 15 ")),
+            Ok(Some(json!(15)))
+        );
+        assert_eq!(
+            convert_value(DataValueKind::Float, &json!(1.5)),
+            Ok(Some(json!(1.5)))
+        );
+        assert_eq!(
+            convert_value(DataValueKind::Boolean, &json!(0)),
+            Ok(Some(json!(false)))
+        );
+        assert_eq!(
+            convert_value(DataValueKind::Boolean, &json!("True")),
+            Ok(Some(json!(true)))
+        );
+        assert!(convert_value(DataValueKind::Boolean, &json!("yes")).is_err());
+        assert!(convert_value(DataValueKind::Guid, &json!(1)).is_err());
+        assert!(convert_value(DataValueKind::Unsupported, &json!("x")).is_err());
+    }
+
+    #[test]
+    fn converts_dates_to_ms_date() {
+        assert_eq!(
+            to_ms_date("2024-03-01T10:20:30Z"),
+            Ok("/Date(1709288430000)/".to_owned())
+        );
+        assert_eq!(
+            to_ms_date("2024-03-01 10:20:30.5"),
+            Ok("/Date(1709288430500)/".to_owned())
+        );
+        assert_eq!(
+            to_ms_date("2024-03-01"),
+            Ok("/Date(1709251200000)/".to_owned())
+        );
+        assert_eq!(
+            to_ms_date("/Date(1709251200000)/"),
+            Ok("/Date(1709251200000)/".to_owned())
+        );
+        assert!(to_ms_date("01.03.2024").is_err());
+    }
+
+    #[test]
+    fn builds_data_row_without_null_values() {
+        let schema = test_schema();
+        let columns = schema.columns.iter().collect::<Vec<_>>();
+
+        let record = json!({
+            "Id": "9A1F0D5C-2B8E-4C3F-8E4A-6D7B5C4A3B2C",
+            "Name": "Default",
+            "TypeId": null,
+            "IsActive": 1
+        });
+
+        let row = build_data_row(&columns, record.as_object().unwrap()).unwrap();
+
+        assert_eq!(
+            row,
+            json!({
+                "Row": [
+                    {
+                        "SchemaColumnUId": "ae0e45ca-c495-4fe7-a39d-3ab7278e1617",
+                        "Value": "9a1f0d5c-2b8e-4c3f-8e4a-6d7b5c4a3b2c"
+                    },
+                    { "SchemaColumnUId": "736c30a7-c0ec-4fa9-b034-2552b319b633", "Value": "Default" },
+                    { "SchemaColumnUId": "f1b8f4a5-7a77-4a34-a5f1-9d4e1c6f2c4b", "Value": true }
+                ]
+            })
+        );
+
+        let invalid = json!({ "Id": 5 });
+
+        assert!(matches!(
+            build_data_row(&columns, invalid.as_object().unwrap()),
+            Err(ExportPkgDataCommandError::ConvertValue { column, .. }) if column == "Id"
+        ));
+    }
+
+    #[test]
+    fn builds_data_descriptor_keeping_existing_values() {
+        let schema = test_schema();
+        let columns = schema.columns.iter().take(2).collect::<Vec<_>>();
+        let key_columns = vec![columns[0]];
+
+        let folder = TempDir::create("export-data");
+        let descriptor_path = folder.join(DATA_DESCRIPTOR_FILE);
+
+        let descriptor = build_data_descriptor(
+            &descriptor_path,
+            "UsrSettings",
+            "ae8519c2-2aac-4a00-aa61-b0ffaac99ea3",
+            &schema,
+            &columns,
+            &key_columns,
+            true,
+        );
+
+        assert_eq!(descriptor["Descriptor"]["Name"], "UsrSettings");
+        assert_eq!(
+            descriptor["Descriptor"]["EntitySchemaUId"],
+            "25d7c1ab-1de0-4501-b402-02e0e5a72d6e"
+        );
+        assert_eq!(
+            descriptor["Descriptor"]["Columns"],
+            json!([
+                { "ColumnUId": "ae0e45ca-c495-4fe7-a39d-3ab7278e1617", "IsKey": true, "ColumnName": "Id" },
+                { "ColumnUId": "736c30a7-c0ec-4fa9-b034-2552b319b633", "IsKey": false, "ColumnName": "Name" }
+            ])
+        );
+
+        let mut existing = descriptor.clone();
+        existing["Descriptor"]["ModifiedOnUtc"] = json!("/Date(1000)/");
+        existing["Descriptor"]["InstallType"] = json!(1);
+        std::fs::write(&descriptor_path, serde_json::to_vec(&existing).unwrap()).unwrap();
+
+        let unchanged = build_data_descriptor(
+            &descriptor_path,
+            "UsrSettings",
+            "ae8519c2-2aac-4a00-aa61-b0ffaac99ea3",
+            &schema,
+            &columns,
+            &key_columns,
+            false,
+        );
+
+        assert_eq!(
+            unchanged["Descriptor"]["UId"],
+            descriptor["Descriptor"]["UId"]
+        );
+        assert_eq!(unchanged["Descriptor"]["ModifiedOnUtc"], "/Date(1000)/");
+        assert_eq!(unchanged["Descriptor"]["InstallType"], 1);
+
+        let data_changed = build_data_descriptor(
+            &descriptor_path,
+            "UsrSettings",
+            "ae8519c2-2aac-4a00-aa61-b0ffaac99ea3",
+            &schema,
+            &columns,
+            &key_columns,
+            true,
+        );
+
+        assert_ne!(data_changed["Descriptor"]["ModifiedOnUtc"], "/Date(1000)/");
+    }
+}
@@ -0,0 +1,21 @@
+use crate::app::CrtClient;
+use crate::cmd::app::AppCommand;
+use crate::cmd::cli::CommandResult;
+use clap::Subcommand;
+use std::sync::Arc;
+
+mod export_data;
+
+#[derive(Debug, Subcommand)]
+pub enum PkgDataCommands {
+    /// Exports table data from Creatio into package data binding files (Data/<BINDING_NAME>/...)
+    Export(Box<export_data::ExportPkgDataCommand>),
+}
+
+impl AppCommand for PkgDataCommands {
+    async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
+        match self {
+            PkgDataCommands::Export(command) => command.run(client).await,
+        }
+    }
+}
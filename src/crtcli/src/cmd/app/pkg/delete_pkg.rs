@@ -0,0 +1,456 @@
+use crate::app::sql::SqlRunnerError;
+use crate::app::{CrtClient, CrtClientError};
+use crate::cmd::app::AppCommand;
+use crate::cmd::app::pkg::backup::{PackagesBackupError, backup_packages, print_backup_created};
+use crate::cmd::cli::CommandResult;
+use crate::pkg::utils::{PackageDependencyCycleError, sort_packages_by_depends_on};
+use anstyle::{AnsiColor, Color, Style};
+use clap::Args;
+use std::io::IsTerminal;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Args, Debug)]
+pub struct DeletePkgCommand {
+    /// A space-separated or comma-separated list of package names to delete. Example: "UsrPackage,UsrPackage2"
+    #[arg(value_delimiter = ',', required = true, value_hint = clap::ValueHint::Other)]
+    packages: Vec<String>,
+
+    /// Do not ask for confirmation before deleting packages
+    #[arg(short, long)]
+    yes: bool,
+
+    /// Download current versions of the packages into .crtcli/backups before deleting, they can be reinstalled later with `app pkg rollback`
+    #[arg(long)]
+    backup: bool,
+
+    /// Delete packages even if other installed packages depend on them
+    #[arg(long)]
+    ignore_deps: bool,
+
+    /// Restart the Creatio application after deleting packages
+    #[arg(short, long)]
+    restart: bool,
+}
+
+#[derive(Debug, Error)]
+pub enum DeletePkgCommandError {
+    #[error("failed to get installed packages: {0}")]
+    GetPackages(#[source] CrtClientError),
+
+    #[error("packages are not installed in Creatio: {}", .0.join(", "))]
+    NotInstalled(Vec<String>),
+
+    #[error(
+        "failed to find dependent packages, the check requires sql runner, use --ignore-deps to skip it: {0}"
+    )]
+    FindDependents(#[source] CrtClientError),
+
+    #[error(
+        "{0} package(s) are required by other installed packages, delete them too or use --ignore-deps"
+    )]
+    HasDependents(usize),
+
+    #[error("failed to order packages for deletion: {0}")]
+    DependencyCycle(#[from] PackageDependencyCycleError),
+
+    #[error(
+        "confirmation is required to delete packages, use --yes flag to run in non-interactive mode"
+    )]
+    ConfirmationRequired,
+
+    #[error("failed to read confirmation: {0}")]
+    Confirmation(#[source] std::io::Error),
+
+    #[error("failed to backup packages: {0}")]
+    Backup(#[from] PackagesBackupError),
+
+    #[error("failed to delete package {0}: {1}")]
+    DeleteService(String, #[source] CrtClientError),
+
+    #[error("failed to delete package {0} using service ({1}) and sql: {2}")]
+    Delete(String, #[source] Box<CrtClientError>, Box<CrtClientError>),
+
+    #[error("failed to check package {0} in database: {1}")]
+    CheckExists(String, #[source] CrtClientError),
+
+    #[error(
+        "failed to delete package {0}: package still exists after sql cleanup, probably other packages depend on it"
+    )]
+    NothingDeleted(String),
+
+    #[error("failed to restart app: {0}")]
+    Restart(#[source] CrtClientError),
+}
+
+impl AppCommand for DeletePkgCommand {
+    async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
+        let bold = Style::new().bold();
+        let yellow = Style::new()
+            .fg_color(Some(Color::Ansi(AnsiColor::BrightYellow)))
+            .dimmed();
+
+        let progress = spinner!(
+            "Checking {bold}{packages}{bold:#} packages in {bold}{url}{bold:#}",
+            packages = self.packages.join(", "),
+            url = client.base_url(),
+        );
+
+        let installed_packages = client
+            .workspace_explorer_service()
+            .get_packages()
+            .await
+            .map_err(DeletePkgCommandError::GetPackages)?;
+
+        let not_installed = self
+            .packages
+            .iter()
+            .filter(|name| {
+                !installed_packages
+                    .iter()
+                    .any(|p| p.name().eq_ignore_ascii_case(name))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if !not_installed.is_empty() {
+            progress.finish_and_clear();
+            return Err(DeletePkgCommandError::NotInstalled(not_installed).into());
+        }
+
+        let mut packages_dependents = vec![];
+
+        for package_name in &self.packages {
+            match client
+                .sql_scripts()
+                .find_package_dependents(package_name)
+                .await
+            {
+                Ok(dependents) => packages_dependents.push((package_name.clone(), dependents)),
+                Err(err) if self.ignore_deps => {
+                    progress.suspend(|| {
+                        eprintln!(
+                            "{yellow}warning: failed to find dependent packages, deleting packages in the specified order: {err}{yellow:#}"
+                        )
+                    });
+
+                    packages_dependents.clear();
+                    break;
+                }
+                Err(err) if is_sql_runner_not_found(&err) => {
+                    progress.suspend(|| {
+                        eprintln!(
+                            "{yellow}warning: dependent packages are not checked because sql runner is not installed, deleting packages in the specified order{yellow:#}"
+                        )
+                    });
+
+                    packages_dependents.clear();
+                    break;
+                }
+                Err(err) => {
+                    progress.finish_and_clear();
+                    return Err(DeletePkgCommandError::FindDependents(err).into());
+                }
+            }
+        }
+
+        progress.finish_and_clear();
+
+        let outside_dependents = find_outside_dependents(&self.packages, &packages_dependents);
+
+        for (package_name, dependents) in &outside_dependents {
+            eprintln!(
+                "{yellow}warning: package {package_name} is required by: {}{yellow:#}",
+                dependents.join(", "),
+            );
+        }
+
+        check_outside_dependents(&outside_dependents, self.ignore_deps)?;
+
+        let ordered_packages = order_packages_for_deletion(&self.packages, &packages_dependents)?;
+
+        if !self.yes {
+            if !std::io::stdin().is_terminal() {
+                return Err(DeletePkgCommandError::ConfirmationRequired.into());
+            }
+
+            let confirmed = crate::cmd::utils::confirm(&format!(
+                "Delete {bold}{packages}{bold:#} from {bold}{url}{bold:#}?",
+                packages = ordered_packages.join(", "),
+                url = client.base_url(),
+            ))
+            .map_err(DeletePkgCommandError::Confirmation)?;
+
+            if !confirmed {
+                eprintln!("Aborted, no packages were deleted");
+                return Ok(());
+            }
+        }
+
+        if self.backup {
+            let progress = spinner!(
+                "Creating backup of {bold}{packages}{bold:#} packages",
+                packages = ordered_packages.join(", "),
+            );
+
+            let backup = backup_packages(&client, &ordered_packages).await?;
+
+            progress.finish_and_clear();
+            print_backup_created(&backup);
+        }
+
+        for package_name in &ordered_packages {
+            delete_package(&client, package_name, self.ignore_deps).await?;
+        }
+
+        if self.restart {
+            client
+                .app_installer_service()
+                .restart_app()
+                .await
+                .map_err(DeletePkgCommandError::Restart)?;
+
+            crate::cmd::app::restart::print_app_restart_requested(&client);
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns packages which are required by installed packages that are not deleted too.
+fn find_outside_dependents(
+    packages: &[String],
+    packages_dependents: &[(String, Vec<String>)],
+) -> Vec<(String, Vec<String>)> {
+    let is_deleted = |name: &str| packages.iter().any(|p| p.eq_ignore_ascii_case(name));
+
+    packages_dependents
+        .iter()
+        .filter_map(|(package_name, dependents)| {
+            let outside_dependents = dependents
+                .iter()
+                .filter(|d| !is_deleted(d))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            (!outside_dependents.is_empty()).then(|| (package_name.clone(), outside_dependents))
+        })
+        .collect()
+}
+
+fn check_outside_dependents(
+    outside_dependents: &[(String, Vec<String>)],
+    ignore_deps: bool,
+) -> Result<(), DeletePkgCommandError> {
+    if !outside_dependents.is_empty() && !ignore_deps {
+        return Err(DeletePkgCommandError::HasDependents(
+            outside_dependents.len(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Orders packages so the ones which depend on other deleted packages are deleted first.
+fn order_packages_for_deletion(
+    packages: &[String],
+    packages_dependents: &[(String, Vec<String>)],
+) -> Result<Vec<String>, PackageDependencyCycleError> {
+    let depends_on = packages
+        .iter()
+        .map(|name| {
+            let depends_on = packages_dependents
+                .iter()
+                .filter(|(_, dependents)| dependents.iter().any(|d| d.eq_ignore_ascii_case(name)))
+                .map(|(target, _)| target.clone())
+                .collect();
+
+            (name.clone(), depends_on)
+        })
+        .collect();
+
+    let mut ordered_packages = sort_packages_by_depends_on(depends_on)?;
+    ordered_packages.reverse();
+
+    Ok(ordered_packages)
+}
+
+/// Returns true if the dependents check failed only because no sql runner package is installed,
+/// packages still can be deleted by the package deletion service in this case.
+fn is_sql_runner_not_found(err: &CrtClientError) -> bool {
+    matches!(err, CrtClientError::SqlRunner(err) if matches!(**err, SqlRunnerError::NotFound))
+}
+
+/// Returns true if the package deletion service is not available in the Creatio version,
+/// only in this case packages are deleted using sql.
+fn is_delete_service_unavailable(err: &CrtClientError) -> bool {
+    matches!(
+        err,
+        CrtClientError::Reqwest(err) if matches!(
+            err.status(),
+            Some(reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::METHOD_NOT_ALLOWED)
+        )
+    )
+}
+
+async fn delete_package(
+    client: &CrtClient,
+    package_name: &str,
+    ignore_deps: bool,
+) -> Result<(), DeletePkgCommandError> {
+    let progress = spinner!(
+        "Deleting {bold}{package_name}{bold:#} package",
+        bold = Style::new().bold(),
+    );
+
+    let service_result = client
+        .app_installer_service()
+        .delete_package(package_name)
+        .await;
+
+    match service_result {
+        Ok(()) => {}
+        Err(service_err) if is_delete_service_unavailable(&service_err) => {
+            progress.suspend(|| {
+                eprintln!(
+                    "{style}warning: package deletion service is not available ({service_err}), falling back to sql cleanup{style:#}",
+                    style = Style::new()
+                        .fg_color(Some(Color::Ansi(AnsiColor::BrightYellow)))
+                        .dimmed()
+                )
+            });
+
+            let package_exists = || async {
+                client
+                    .sql_scripts()
+                    .package_exists(package_name)
+                    .await
+                    .map_err(|err| DeletePkgCommandError::CheckExists(package_name.to_owned(), err))
+            };
+
+            if !package_exists().await? {
+                progress.finish_and_clear();
+                return Err(DeletePkgCommandError::NotInstalled(vec![
+                    package_name.to_owned(),
+                ]));
+            }
+
+            client
+                .sql_scripts()
+                .delete_package(package_name, ignore_deps)
+                .await
+                .map_err(|sql_err| {
+                    DeletePkgCommandError::Delete(
+                        package_name.to_owned(),
+                        Box::new(service_err),
+                        Box::new(sql_err),
+                    )
+                })?;
+
+            if package_exists().await? {
+                progress.finish_and_clear();
+                return Err(DeletePkgCommandError::NothingDeleted(
+                    package_name.to_owned(),
+                ));
+            }
+        }
+        Err(service_err) => {
+            progress.finish_and_clear();
+            return Err(DeletePkgCommandError::DeleteService(
+                package_name.to_owned(),
+                service_err,
+            ));
+        }
+    }
+
+    spinner!(
+        finished_in = progress.elapsed(),
+        "{green}Package {green_bold}{package_name}{green_bold:#}{green} successfully deleted{green:#}",
+        green = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green))),
+        green_bold = Style::new()
+            .fg_color(Some(Color::Ansi(AnsiColor::Green)))
+            .bold(),
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(values: &[&str]) -> Vec<String> {
+        values.iter().map(|x| x.to_string()).collect()
+    }
+
+    fn service_error(status: u16) -> CrtClientError {
+        let response = reqwest::Response::from(
+            tokio_tungstenite::tungstenite::http::Response::builder()
+                .status(status)
+                .body("")
+                .unwrap(),
+        );
+
+        response.error_for_status().unwrap_err().into()
+    }
+
+    #[test]
+    fn outside_dependents_exclude_deleted_packages() {
+        let packages = names(&["UsrBase", "UsrUi"]);
+        let packages_dependents = vec![
+            ("UsrBase".to_owned(), names(&["usrui", "UsrReports"])),
+            ("UsrUi".to_owned(), vec![]),
+        ];
+
+        let outside_dependents = find_outside_dependents(&packages, &packages_dependents);
+
+        assert_eq!(
+            outside_dependents,
+            vec![("UsrBase".to_owned(), names(&["UsrReports"]))]
+        );
+        assert!(matches!(
+            check_outside_dependents(&outside_dependents, false),
+            Err(DeletePkgCommandError::HasDependents(1))
+        ));
+        assert!(check_outside_dependents(&outside_dependents, true).is_ok());
+        assert!(check_outside_dependents(&[], false).is_ok());
+    }
+
+    #[test]
+    fn dependents_are_deleted_first() {
+        let packages = names(&["UsrBase", "UsrUi", "UsrReports"]);
+        let packages_dependents = vec![
+            ("UsrBase".to_owned(), names(&["UsrUi", "UsrReports"])),
+            ("UsrUi".to_owned(), names(&["UsrReports"])),
+            ("UsrReports".to_owned(), vec![]),
+        ];
+
+        assert_eq!(
+            order_packages_for_deletion(&packages, &packages_dependents).unwrap(),
+            names(&["UsrReports", "UsrUi", "UsrBase"])
+        );
+    }
+
+    #[test]
+    fn falls_back_to_sql_only_if_service_is_unavailable() {
+        assert!(is_delete_service_unavailable(&service_error(404)));
+        assert!(is_delete_service_unavailable(&service_error(405)));
+        assert!(!is_delete_service_unavailable(&service_error(401)));
+        assert!(!is_delete_service_unavailable(&service_error(500)));
+        assert!(!is_delete_service_unavailable(
+            &CrtClientError::Unauthorized
+        ));
+    }
+
+    #[test]
+    fn skips_dependents_check_only_if_sql_runner_is_not_found() {
+        assert!(is_sql_runner_not_found(&SqlRunnerError::NotFound.into()));
+        assert!(!is_sql_runner_not_found(
+            &SqlRunnerError::Execution {
+                err: "invalid object name".to_owned()
+            }
+            .into()
+        ));
+        assert!(!is_sql_runner_not_found(&service_error(500)));
+    }
+}
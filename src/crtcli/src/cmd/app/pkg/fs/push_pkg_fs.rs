@@ -56,6 +56,7 @@ impl AppCommand for PushPkgFsCommand {
                 package_names,
                 force_rebuild: false,
                 restart: self.restart,
+                report_format: Default::default(),
             }
             .run(client)
             .await?;
@@ -1,15 +1,25 @@
-use crate::app::{CrtClient, CrtClientError, InstallLogWatcherBuilder, InstallLogWatcherEvent};
+use crate::app::{
+    CrtClient, CrtClientError, InstallLogWatcherBuilder, InstallLogWatcherEvent,
+    InstallLogWatcherHandle,
+};
 use crate::cmd::app::AppCommand;
+use crate::cmd::app::install_log::InstallLogPrinter;
+use crate::cmd::app::pkg::backup::{PackagesBackupError, backup_packages, print_backup_created};
+use crate::cmd::app::pkg::validate_pkg::{
+    print_package_validation_result, validate_uploaded_package,
+};
 use crate::cmd::cli::{CommandDynError, CommandResult};
 use crate::cmd::utils::humanize_bytes;
 use anstyle::{AnsiColor, Color, Style};
 use clap::Args;
+use indicatif::ProgressBar;
 use std::borrow::Cow;
 use std::io::{Cursor, Read, Seek, Write, stdin};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use thiserror::Error;
-use tokio_util::bytes::Bytes;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio_util::bytes::{Buf, Bytes};
 use zip::result::ZipError;
 use zip::write::{FileOptions, SimpleFileOptions};
 use zip::{ZipArchive, ZipWriter};
@@ -24,7 +34,7 @@ pub struct InstallPkgCommand {
     install_pkg_options: InstallPkgCommandOptions,
 }
 
-#[derive(Debug, Default, Args)]
+#[derive(Debug, Default, Args, Clone)]
 pub struct InstallPkgCommandOptions {
     /// Restart the Creatio application after successful installation
     #[arg(short, long)]
@@ -49,6 +59,33 @@ pub struct InstallPkgCommandOptions {
     /// Disables the display of the installation log updates in real-time
     #[arg(long)]
     disable_install_log_polling: bool,
+
+    /// Validate the uploaded package archive before installation and abort the installation on validation errors
+    #[arg(long)]
+    validate: bool,
+
+    /// Skip the check that packages from DependsOn of the installing packages are installed in Creatio
+    #[arg(long)]
+    ignore_deps: bool,
+
+    /// Download currently installed versions of the packages into .crtcli/backups/<APP> folder before installation (check `app pkg rollback`)
+    #[arg(long)]
+    backup: bool,
+
+    /// Print installation log as a stream of parsed JSON events (one JSON object per line)
+    #[arg(long)]
+    json: bool,
+}
+
+impl InstallPkgCommandOptions {
+    /// Returns options to reinstall previously installed packages, without the dependency check and backup.
+    pub fn for_reinstall(&self) -> Self {
+        Self {
+            ignore_deps: true,
+            backup: false,
+            ..self.clone()
+        }
+    }
 }
 
 #[derive(Debug, Error)]
@@ -56,15 +93,35 @@ pub enum InstallPkgCommandError {
     #[error("failed to read package descriptors: {0}")]
     ReadDescriptor(#[from] crate::pkg::utils::GetPackageDescriptorFromReaderError),
 
+    #[error("failed to check package dependencies: {0}")]
+    DependencyCheck(#[source] CrtClientError),
+
+    #[error(
+        "package dependency check failed with {0} problem(s), installation aborted (use --ignore-deps to install anyway)"
+    )]
+    DependencyCheckFailed(usize),
+
+    #[error("failed to backup packages before install: {0}")]
+    Backup(#[source] PackagesBackupError),
+
     #[error("failed to apply SQL options before package install: {0}")]
     SqlBeforePackage(#[source] CrtClientError),
 
     #[error("package descriptor.json was found, but the package uid value is null")]
     PackageUidValueNull,
 
+    #[error("failed to read package archive stream: {0}")]
+    ReadStream(#[source] std::io::Error),
+
     #[error("failed to upload package: {0}")]
     Upload(#[source] CrtClientError),
 
+    #[error("failed to validate package: {0}")]
+    Validate(#[source] CrtClientError),
+
+    #[error("package validation failed with {0} error(s), installation aborted")]
+    ValidationFailed(usize),
+
     #[error("failed to install package: {0}")]
     Install(#[source] CrtClientError),
 
@@ -86,20 +143,7 @@ enum BeforeInstallPkgCombineError {
 
 impl AppCommand for InstallPkgCommand {
     async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
-        let (package_content, package_name) = if self.filepaths.len() == 1 {
-            let filepath = &self.filepaths[0];
-
-            if Some("@-") == filepath.to_str() || Some("@-") == filepath.to_str() {
-                read_package_input_from_stdin()?
-            } else {
-                (std::fs::read(filepath)?, path_to_filename_str(filepath)?)
-            }
-        } else {
-            (
-                combine_packages_to_single_zip(&self.filepaths)?,
-                "Packages.zip".to_owned(),
-            )
-        };
+        let (package_content, package_name) = read_package_files(&self.filepaths)?;
 
         install_package_from_stream_command(
             client,
@@ -109,70 +153,116 @@ impl AppCommand for InstallPkgCommand {
         )
         .await?;
 
-        return Ok(());
+        Ok(())
+    }
+}
 
-        fn read_package_input_from_stdin() -> Result<(Vec<u8>, String), CommandDynError> {
-            let mut data = vec![];
+/// Reads package archive files (or stdin for '@-' / '-' value) and combines them into a single archive if needed.
+pub fn read_package_files(filepaths: &[PathBuf]) -> Result<(Vec<u8>, String), CommandDynError> {
+    if filepaths.len() == 1 {
+        let filepath = &filepaths[0];
 
-            stdin().read_to_end(&mut data)?;
+        return if Some("@-") == filepath.to_str() || Some("-") == filepath.to_str() {
+            read_package_input_from_stdin()
+        } else {
+            Ok((std::fs::read(filepath)?, path_to_filename_str(filepath)?))
+        };
+    }
 
-            let mut reader = Cursor::new(data);
-            let filename = get_filename_for_package_reader(&mut reader)?;
+    return Ok((
+        combine_packages_to_single_zip(filepaths)?,
+        "Packages.zip".to_owned(),
+    ));
 
-            Ok((reader.into_inner(), filename))
-        }
+    fn read_package_input_from_stdin() -> Result<(Vec<u8>, String), CommandDynError> {
+        let mut data = vec![];
 
-        fn combine_packages_to_single_zip(
-            filepaths: &[impl AsRef<Path>],
-        ) -> Result<Vec<u8>, BeforeInstallPkgCombineError> {
-            let mut zip = ZipWriter::new(Cursor::new(vec![]));
-            let file_options =
-                SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
-
-            for filepath in filepaths {
-                process_file_combine(&mut zip, file_options, filepath).map_err(|e| {
-                    BeforeInstallPkgCombineError::ProcessFile(filepath.as_ref().to_path_buf(), e)
-                })?;
-            }
+        stdin().read_to_end(&mut data)?;
 
-            return Ok(zip.finish()?.into_inner());
+        let mut reader = Cursor::new(data);
+        let filename = get_filename_for_package_reader(&mut reader)?;
 
-            fn process_file_combine(
-                mut zip: &mut ZipWriter<impl Write + Seek>,
-                file_options: FileOptions<()>,
-                filepath: &impl AsRef<Path>,
-            ) -> Result<(), CommandDynError> {
-                let mut file = std::fs::File::open(filepath)?;
+        Ok((reader.into_inner(), filename))
+    }
 
-                if crate::pkg::utils::is_gzip_stream(&mut file)? {
-                    zip.start_file(path_to_filename_str(filepath.as_ref())?, file_options)?;
+    fn combine_packages_to_single_zip(
+        filepaths: &[impl AsRef<Path>],
+    ) -> Result<Vec<u8>, BeforeInstallPkgCombineError> {
+        let mut zip = ZipWriter::new(Cursor::new(vec![]));
+        let file_options =
+            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+
+        for filepath in filepaths {
+            process_file_combine(&mut zip, file_options, filepath).map_err(|e| {
+                BeforeInstallPkgCombineError::ProcessFile(filepath.as_ref().to_path_buf(), e)
+            })?;
+        }
 
-                    std::io::copy(&mut file, &mut zip)?;
-                } else {
-                    let mut zip_inner = ZipArchive::new(file)?;
+        return Ok(zip.finish()?.into_inner());
 
-                    for i in 0..zip_inner.len() {
-                        let mut file = zip_inner.by_index(i)?;
+        fn process_file_combine(
+            mut zip: &mut ZipWriter<impl Write + Seek>,
+            file_options: FileOptions<()>,
+            filepath: &impl AsRef<Path>,
+        ) -> Result<(), CommandDynError> {
+            let mut file = std::fs::File::open(filepath)?;
 
-                        zip.start_file(file.name(), file_options)?;
+            if crate::pkg::utils::is_gzip_stream(&mut file)? {
+                zip.start_file(path_to_filename_str(filepath.as_ref())?, file_options)?;
 
-                        std::io::copy(&mut file, &mut zip)?;
-                    }
-                }
+                std::io::copy(&mut file, &mut zip)?;
+            } else {
+                let mut zip_inner = ZipArchive::new(file)?;
 
-                Ok(())
+                for i in 0..zip_inner.len() {
+                    let mut file = zip_inner.by_index(i)?;
+
+                    zip.start_file(file.name(), file_options)?;
+
+                    std::io::copy(&mut file, &mut zip)?;
+                }
             }
-        }
 
-        fn path_to_filename_str(path: &Path) -> Result<String, CommandDynError> {
-            Ok(path
-                .file_name()
-                .ok_or("unable to get filename of specified path")?
-                .to_str()
-                .ok_or("unable to get filename str of specified path")?
-                .to_string())
+            Ok(())
         }
     }
+
+    fn path_to_filename_str(path: &Path) -> Result<String, CommandDynError> {
+        Ok(path
+            .file_name()
+            .ok_or("unable to get filename of specified path")?
+            .to_str()
+            .ok_or("unable to get filename str of specified path")?
+            .to_string())
+    }
+}
+
+/// Starts polling of the installation log, printing it through the printer while the progress is suspended.
+pub fn start_install_log_watcher(
+    client: &Arc<CrtClient>,
+    progress: &Arc<ProgressBar>,
+    log_printer: &Arc<InstallLogPrinter>,
+) -> InstallLogWatcherHandle {
+    let progress = Arc::clone(progress);
+    let log_printer = Arc::clone(log_printer);
+
+    InstallLogWatcherBuilder::new(Arc::clone(client))
+        .fetch_last_log_on_stop(true)
+        .start(move |event| match event {
+            InstallLogWatcherEvent::Clear => {}
+            InstallLogWatcherEvent::Append(text) => progress.suspend(|| log_printer.append(text)),
+            InstallLogWatcherEvent::FetchError(error) => {
+                progress.suspend(move || {
+                    eprintln!(
+                        "{style}warning (log polling): {error}{style:#}",
+                        error = error,
+                        style = Style::new()
+                            .fg_color(Some(Color::Ansi(AnsiColor::BrightYellow)))
+                            .dimmed()
+                    )
+                });
+            }
+        })
 }
 
 fn get_filename_for_package_reader(
@@ -210,7 +300,7 @@ pub async fn install_package_from_stream_command(
     )
     .map_err(InstallPkgCommandError::ReadDescriptor)?;
 
-    apply_options_before_install(&client, options, &descriptors).await?;
+    prepare_package_install(&client, options, &descriptors).await?;
 
     let progress = spinner_precise!(
         "Uploading {bold}{package_name}{bold:#} package archive at {bold}{url}{bold:#}",
@@ -232,34 +322,201 @@ pub async fn install_package_from_stream_command(
             .map_err(InstallPkgCommandError::Upload)?;
     }
 
-    let log_watcher = (!options.disable_install_log_polling).then(|| {
-        let progress_clone = Arc::clone(&progress);
+    let log_printer = Arc::new(InstallLogPrinter::new(options.json));
 
-        InstallLogWatcherBuilder::new(Arc::clone(&client))
-            .fetch_last_log_on_stop(true)
-            .start(move |event| match event {
-                InstallLogWatcherEvent::Clear => {}
-                InstallLogWatcherEvent::Append(text) => {
-                    progress_clone.suspend(move || print!("{}", text))
-                }
-                InstallLogWatcherEvent::FetchError(error) => {
-                    progress_clone.suspend(move || {
-                        eprintln!(
-                            "{style}warning (log polling): {error}{style:#}",
-                            error = error,
-                            style = Style::new()
-                                .fg_color(Some(Color::Ansi(AnsiColor::BrightYellow)))
-                                .dimmed()
-                        )
-                    });
-                }
-            })
-    });
+    let log_watcher = (!options.disable_install_log_polling)
+        .then(|| start_install_log_watcher(&client, &progress, &log_printer));
 
     while let Some(chunk) = chunked_upload_iter.next() {
         chunk.await.map_err(InstallPkgCommandError::Upload)?;
     }
 
+    install_uploaded_package(
+        client,
+        &descriptors,
+        &package_name,
+        options,
+        progress,
+        log_printer,
+        log_watcher,
+    )
+    .await
+}
+
+/// Same as [`install_package_from_stream_command`], but uploads the package zip archive chunk by chunk
+/// while it is being read, without buffering the whole archive in memory.
+///
+/// Package descriptors are read from the same stream during the upload,
+/// so the checks before installation are performed after the archive is uploaded.
+pub async fn install_package_from_reader_command(
+    client: Arc<CrtClient>,
+    mut reader: impl AsyncRead + Unpin,
+    package_bytes_total: usize,
+    package_name: impl Into<Cow<'static, str>>,
+    options: &InstallPkgCommandOptions,
+) -> Result<(), InstallPkgCommandError> {
+    let package_name = package_name.into();
+    let chunk_size = match crate::app::package_installer::upload_package_chunk_size() {
+        0 => package_bytes_total,
+        chunk_size => chunk_size,
+    };
+
+    let progress = spinner_precise!(
+        "Uploading {bold}{package_name}{bold:#} package archive at {bold}{url}{bold:#}",
+        bold = Style::new().bold(),
+        url = client.base_url(),
+    );
+
+    let (descriptors_sender, descriptors_receiver) = std::sync::mpsc::channel::<Bytes>();
+    let descriptors_reader = tokio::task::spawn_blocking(move || {
+        crate::pkg::utils::get_package_descriptors_from_zip_stream(&mut ChannelReader::new(
+            descriptors_receiver,
+        ))
+    });
+
+    let package_installer_service = client.package_installer_service();
+    let mut current_offset = 0;
+
+    while current_offset < package_bytes_total {
+        let mut chunk = Vec::with_capacity(chunk_size.min(package_bytes_total - current_offset));
+
+        (&mut reader)
+            .take(chunk.capacity() as u64)
+            .read_to_end(&mut chunk)
+            .await
+            .map_err(InstallPkgCommandError::ReadStream)?;
+
+        if chunk.is_empty() {
+            return Err(InstallPkgCommandError::ReadStream(
+                std::io::ErrorKind::UnexpectedEof.into(),
+            ));
+        }
+
+        let chunk = Bytes::from(chunk);
+        let chunk_len = chunk.len();
+
+        // Descriptors reader may stop earlier, the rest of the archive is not needed for it
+        let _ = descriptors_sender.send(chunk.clone());
+
+        package_installer_service
+            .upload_package_chunk(&package_name, chunk, current_offset, package_bytes_total)
+            .await
+            .inspect_err(|_err| progress.suspend(try_print_upload_package_chunk_size_hint))
+            .map_err(InstallPkgCommandError::Upload)?;
+
+        current_offset += chunk_len;
+    }
+
+    drop(descriptors_sender);
+
+    let descriptors = descriptors_reader
+        .await
+        .expect("package descriptors reader task panicked")
+        .map_err(InstallPkgCommandError::ReadDescriptor)?;
+
+    progress.finish_and_clear();
+
+    prepare_package_install(&client, options, &descriptors).await?;
+
+    let progress = Arc::new(spinner_precise!(
+        "Installing {bold}{package_name}{bold:#} package archive at {bold}{url}{bold:#}",
+        bold = Style::new().bold(),
+        url = client.base_url(),
+    ));
+
+    let log_printer = Arc::new(InstallLogPrinter::new(options.json));
+
+    let log_watcher = (!options.disable_install_log_polling)
+        .then(|| start_install_log_watcher(&client, &progress, &log_printer));
+
+    install_uploaded_package(
+        client,
+        &descriptors,
+        &package_name,
+        options,
+        progress,
+        log_printer,
+        log_watcher,
+    )
+    .await
+}
+
+async fn prepare_package_install(
+    client: &Arc<CrtClient>,
+    options: &InstallPkgCommandOptions,
+    descriptors: &[crate::pkg::json::PkgPackageDescriptorJsonWrapper],
+) -> Result<(), InstallPkgCommandError> {
+    if !options.ignore_deps {
+        check_remote_package_dependencies(client, descriptors).await?;
+    }
+
+    if options.backup {
+        let package_names = descriptors
+            .iter()
+            .filter_map(|d| d.name().map(String::from))
+            .collect::<Vec<_>>();
+
+        let progress = spinner!(
+            "Backing up installed {bold}{names}{bold:#} packages at {bold}{url}{bold:#}",
+            bold = Style::new().bold(),
+            names = package_names.join(", "),
+            url = client.base_url(),
+        );
+
+        let backup = backup_packages(client, &package_names)
+            .await
+            .map_err(InstallPkgCommandError::Backup)?;
+
+        progress.finish_and_clear();
+
+        print_backup_created(&backup);
+    }
+
+    apply_options_before_install(client, options, descriptors).await?;
+
+    Ok(())
+}
+
+async fn install_uploaded_package(
+    client: Arc<CrtClient>,
+    descriptors: &[crate::pkg::json::PkgPackageDescriptorJsonWrapper],
+    package_name: &str,
+    options: &InstallPkgCommandOptions,
+    progress: Arc<ProgressBar>,
+    log_printer: Arc<InstallLogPrinter>,
+    log_watcher: Option<InstallLogWatcherHandle>,
+) -> Result<(), InstallPkgCommandError> {
+    if options.validate {
+        progress.set_message(format!(
+            "Validating {bold}{package_name}{bold:#} package archive at {bold}{url}{bold:#}",
+            bold = Style::new().bold(),
+            url = client.base_url()
+        ));
+
+        let result = validate_uploaded_package(&client, package_name)
+            .await
+            .map_err(InstallPkgCommandError::Validate)?;
+
+        progress.suspend(|| match options.json {
+            true => println!(
+                "{}",
+                serde_json::json!({
+                    "type": "validation",
+                    "validation": result
+                })
+            ),
+            false => print_package_validation_result(&result, package_name),
+        });
+
+        if result.errors_count() > 0 {
+            progress.finish_and_clear();
+
+            return Err(InstallPkgCommandError::ValidationFailed(
+                result.errors_count(),
+            ));
+        }
+    }
+
     progress.set_message(format!(
         "Installing {bold}{package_name}{bold:#} package archive at {bold}{url}{bold:#}",
         bold = Style::new().bold(),
@@ -268,7 +525,7 @@ pub async fn install_package_from_stream_command(
 
     let install_result = client
         .package_installer_service()
-        .install_package(&package_name)
+        .install_package(package_name)
         .await
         .map_err(InstallPkgCommandError::Install);
 
@@ -293,6 +550,8 @@ pub async fn install_package_from_stream_command(
         );
 
         log_watcher.wait_until_stopped().await;
+
+        progress.suspend(|| log_printer.finish());
     }
 
     progress.finish_with_message(
@@ -316,12 +575,25 @@ pub async fn install_package_from_stream_command(
 
     if options.compile_package {
         crate::cmd::app::pkg::compile_pkg::CompilePkgCommand {
-            package_names: descriptors
-                .iter()
-                .filter_map(|d| d.name().map(|s| s.to_owned()))
-                .collect(),
+            package_names: crate::pkg::utils::sort_packages_by_depends_on(
+                descriptors
+                    .iter()
+                    .filter_map(|d| {
+                        let depends_on = d
+                            .depends_on()
+                            .unwrap_or_default()
+                            .iter()
+                            .filter_map(|x| x.name().map(|s| s.to_owned()))
+                            .collect();
+
+                        d.name().map(|s| (s.to_owned(), depends_on))
+                    })
+                    .collect(),
+            )
+            .map_err(|err| InstallPkgCommandError::PkgCompile(err.into()))?,
             force_rebuild: false,
             restart: options.restart,
+            report_format: Default::default(),
         }
         .run(client)
         .await
@@ -333,91 +605,162 @@ pub async fn install_package_from_stream_command(
             .map_err(InstallPkgCommandError::AppRestart)?
     }
 
-    return Ok(());
-
-    async fn apply_options_before_install(
-        client: &Arc<CrtClient>,
-        options: &InstallPkgCommandOptions,
-        descriptors: &Vec<crate::pkg::json::PkgPackageDescriptorJsonWrapper>,
-    ) -> Result<(), InstallPkgCommandError> {
-        if options.force || options.force_and_clear_localizations {
-            for descriptor in descriptors {
-                let rows_affected = client
-                    .sql_scripts()
-                    .mark_package_as_not_changed(
-                        descriptor
-                            .uid()
-                            .ok_or(InstallPkgCommandError::PackageUidValueNull)?,
-                    )
-                    .await
-                    .map_err(InstallPkgCommandError::SqlBeforePackage)?;
-
-                eprintln!(
-                    "Package content {} has been marked as not changed, affected {} rows",
-                    descriptor.name().unwrap_or("_"),
-                    rows_affected
-                );
-            }
+    Ok(())
+}
+
+async fn check_remote_package_dependencies(
+    client: &CrtClient,
+    descriptors: &[crate::pkg::json::PkgPackageDescriptorJsonWrapper],
+) -> Result<(), InstallPkgCommandError> {
+    let progress = spinner!(
+        "Checking package dependencies at {bold}{url}{bold:#}",
+        bold = Style::new().bold(),
+        url = client.base_url(),
+    );
+
+    let installed = client
+        .workspace_explorer_service()
+        .get_packages()
+        .await
+        .map_err(InstallPkgCommandError::DependencyCheck)?
+        .iter()
+        .map(|p| (p.name().to_owned(), p.version().map(String::from)))
+        .collect::<Vec<_>>();
+
+    progress.finish_and_clear();
+
+    let problems = crate::pkg::utils::check_package_dependencies(descriptors, &installed);
+
+    if problems.is_empty() {
+        return Ok(());
+    }
+
+    let red = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Red)));
+
+    for problem in &problems {
+        eprintln!("{red}error (pkg-deps): {problem}{red:#}");
+    }
+
+    Err(InstallPkgCommandError::DependencyCheckFailed(
+        problems.len(),
+    ))
+}
+
+async fn apply_options_before_install(
+    client: &Arc<CrtClient>,
+    options: &InstallPkgCommandOptions,
+    descriptors: &[crate::pkg::json::PkgPackageDescriptorJsonWrapper],
+) -> Result<(), InstallPkgCommandError> {
+    if options.force || options.force_and_clear_localizations {
+        for descriptor in descriptors {
+            let rows_affected = client
+                .sql_scripts()
+                .mark_package_as_not_changed(
+                    descriptor
+                        .uid()
+                        .ok_or(InstallPkgCommandError::PackageUidValueNull)?,
+                )
+                .await
+                .map_err(InstallPkgCommandError::SqlBeforePackage)?;
+
+            eprintln!(
+                "Package content {} has been marked as not changed, affected {} rows",
+                descriptor.name().unwrap_or("_"),
+                rows_affected
+            );
         }
+    }
 
-        if options.force_and_clear_localizations {
-            for descriptor in descriptors {
-                let rows_affected = client
-                    .sql_scripts()
-                    .delete_package_localizations(
-                        descriptor
-                            .uid()
-                            .ok_or(InstallPkgCommandError::PackageUidValueNull)?,
-                    )
-                    .await
-                    .map_err(InstallPkgCommandError::SqlBeforePackage)?;
-
-                eprintln!(
-                    "Package localizations {} has been deleted, affected {} rows",
-                    descriptor.name().unwrap_or("_"),
-                    rows_affected
-                );
-            }
+    if options.force_and_clear_localizations {
+        for descriptor in descriptors {
+            let rows_affected = client
+                .sql_scripts()
+                .delete_package_localizations(
+                    descriptor
+                        .uid()
+                        .ok_or(InstallPkgCommandError::PackageUidValueNull)?,
+                )
+                .await
+                .map_err(InstallPkgCommandError::SqlBeforePackage)?;
+
+            eprintln!(
+                "Package localizations {} has been deleted, affected {} rows",
+                descriptor.name().unwrap_or("_"),
+                rows_affected
+            );
         }
+    }
 
-        if options.clear_schemas_content {
-            for descriptor in descriptors {
-                let rows_affected = client
-                    .sql_scripts()
-                    .clear_schema_content(
-                        descriptor
-                            .uid()
-                            .ok_or(InstallPkgCommandError::PackageUidValueNull)?,
-                    )
-                    .await
-                    .map_err(InstallPkgCommandError::SqlBeforePackage)?;
-
-                eprintln!(
-                    "Schema content has been reset for package {}, affected {} rows",
-                    descriptor.name().unwrap_or("_"),
-                    rows_affected
-                );
-            }
+    if options.clear_schemas_content {
+        for descriptor in descriptors {
+            let rows_affected = client
+                .sql_scripts()
+                .clear_schema_content(
+                    descriptor
+                        .uid()
+                        .ok_or(InstallPkgCommandError::PackageUidValueNull)?,
+                )
+                .await
+                .map_err(InstallPkgCommandError::SqlBeforePackage)?;
+
+            eprintln!(
+                "Schema content has been reset for package {}, affected {} rows",
+                descriptor.name().unwrap_or("_"),
+                rows_affected
+            );
         }
+    }
 
-        Ok(())
+    Ok(())
+}
+
+/// Blocking reader over the chunks received from the channel, ends when the sender is dropped.
+struct ChannelReader {
+    receiver: std::sync::mpsc::Receiver<Bytes>,
+    current: Bytes,
+}
+
+impl ChannelReader {
+    fn new(receiver: std::sync::mpsc::Receiver<Bytes>) -> Self {
+        Self {
+            receiver,
+            current: Bytes::new(),
+        }
     }
+}
 
-    fn try_print_upload_package_chunk_size_hint() {
-        let current_chunk_size =
-            std::env::var(crate::app::package_installer::UPLOAD_PACKAGE_CHUNK_SIZE_ENV_KEY)
-                .ok()
-                .and_then(|x| x.parse::<usize>().ok())
-                .unwrap_or(crate::app::package_installer::UPLOAD_PACKAGE_CHUNK_SIZE_DEFAULT);
-
-        eprintln!(
-            "{style}warning: package upload failed. For large package archives, try adjusting the upload chunk size (current: {cur}) by setting the {green}{env}{style:#}{style} environment variable. Set to 0 for a single-part upload.{style:#}",
-            env = crate::app::package_installer::UPLOAD_PACKAGE_CHUNK_SIZE_ENV_KEY,
-            cur = humanize_bytes(current_chunk_size as u64),
-            style = Style::new()
-                .fg_color(Some(Color::Ansi(AnsiColor::BrightYellow)))
-                .dimmed(),
-            green = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green)))
-        );
+impl Read for ChannelReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.current.is_empty() {
+            match self.receiver.recv() {
+                Ok(chunk) => self.current = chunk,
+                Err(_) => return Ok(0),
+            }
+        }
+
+        let len = buf.len().min(self.current.len());
+
+        buf[..len].copy_from_slice(&self.current[..len]);
+        self.current.advance(len);
+
+        Ok(len)
     }
 }
+
+pub fn try_print_upload_package_chunk_size_hint() {
+    let current_chunk_size =
+        std::env::var(crate::app::package_installer::UPLOAD_PACKAGE_CHUNK_SIZE_ENV_KEY)
+            .ok()
+            .and_then(|x| x.parse::<usize>().ok())
+            .unwrap_or(crate::app::package_installer::UPLOAD_PACKAGE_CHUNK_SIZE_DEFAULT);
+
+    eprintln!(
+        "{style}warning: package upload failed. For large package archives, try adjusting the upload chunk size (current: {cur}) by setting the {green}{env}{style:#}{style} environment variable. Set to 0 for a single-part upload.{style:#}",
+        env = crate::app::package_installer::UPLOAD_PACKAGE_CHUNK_SIZE_ENV_KEY,
+        cur = humanize_bytes(current_chunk_size as u64),
+        style = Style::new()
+            .fg_color(Some(Color::Ansi(AnsiColor::BrightYellow)))
+            .dimmed(),
+        green = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green)))
+    );
+}
@@ -1,18 +1,26 @@
 use crate::app::CrtClient;
-use crate::cmd::app::AppCommand;
+use crate::cmd::app::{AppCommand, AppConnectionArgs};
 use crate::cmd::cli::CommandResult;
 use crate::pkg::utils::GetPackageNameFromFolderError;
 use clap::Subcommand;
 use std::sync::Arc;
 use thiserror::Error;
 
+mod backup;
+
 mod fs;
 
-mod compile_pkg;
+pub mod compile_pkg;
+
+mod copy_pkg;
+
+mod data;
+
+mod delete_pkg;
 
 mod download_pkg;
 
-mod install_pkg;
+pub mod install_pkg;
 
 mod get_uid_pkg;
 
@@ -20,15 +28,33 @@ mod pull_pkg;
 
 mod push_pkg;
 
+mod rollback_pkg;
+
 mod lock_pkg;
 
 mod unlock_pkg;
 
+mod validate_pkg;
+
 #[derive(Debug, Subcommand)]
 pub enum PkgCommands {
     /// Compiles a specific package within the Creatio instance
     Compile(compile_pkg::CompilePkgCommand),
 
+    /// Downloads packages from one Creatio instance and installs them into another one
+    #[clap(visible_alias = "cp")]
+    Copy(copy_pkg::CopyPkgCommand),
+
+    /// Commands to manipulate with package data bindings (Data folder) using Creatio database
+    Data {
+        #[command(subcommand)]
+        command: data::PkgDataCommands,
+    },
+
+    /// Deletes packages from the Creatio instance
+    #[clap(visible_alias = "rm")]
+    Delete(delete_pkg::DeletePkgCommand),
+
     /// Downloads packages from the Creatio instance as a zip archive
     #[clap(visible_aliases = &["d", "dl"])]
     Download(download_pkg::DownloadPkgCommand),
@@ -55,14 +81,34 @@ pub enum PkgCommands {
     /// Packs packages from source folders and installs it into the Creatio instance
     Push(push_pkg::PushPkgCommand),
 
+    /// Reinstalls packages from the backup created by `--backup` option of the install commands
+    Rollback(rollback_pkg::RollbackPkgCommand),
+
     /// Execute SQL to make packages unlocked if it is locked in Creatio
     Unlock(unlock_pkg::UnlockPkgCommand),
+
+    /// Uploads packages into the Creatio instance and validates them without installation
+    Validate(validate_pkg::ValidatePkgCommand),
+}
+
+impl PkgCommands {
+    pub fn with_connection_args(self, connection_args: AppConnectionArgs) -> Self {
+        match self {
+            PkgCommands::Copy(command) => {
+                PkgCommands::Copy(command.with_connection_args(connection_args))
+            }
+            command => command,
+        }
+    }
 }
 
 impl AppCommand for PkgCommands {
     async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
         match self {
             PkgCommands::Compile(command) => command.run(client).await,
+            PkgCommands::Copy(command) => command.run(client).await,
+            PkgCommands::Data { command } => command.run(client).await,
+            PkgCommands::Delete(command) => command.run(client).await,
             PkgCommands::Download(command) => command.run(client).await,
             PkgCommands::Fs { command } => command.run(client).await,
             PkgCommands::Install(command) => command.run(client).await,
@@ -70,7 +116,9 @@ impl AppCommand for PkgCommands {
             PkgCommands::Lock(command) => command.run(client).await,
             PkgCommands::Pull(command) => command.run(client).await,
             PkgCommands::Push(command) => command.run(client).await,
+            PkgCommands::Rollback(command) => command.run(client).await,
             PkgCommands::Unlock(command) => command.run(client).await,
+            PkgCommands::Validate(command) => command.run(client).await,
         }
     }
 }
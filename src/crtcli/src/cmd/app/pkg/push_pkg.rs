@@ -51,10 +51,7 @@ impl AppCommand for PushPkgCommand {
             &self.source_folders
         };
 
-        let (package_filename, package_content) = match source_folder.len() {
-            1 => pack_folder_as_gzip(source_folder.iter().next().unwrap())?,
-            _ => pack_folders_as_zip(source_folder)?,
-        };
+        let (package_filename, package_content) = pack_package_folders(source_folder)?;
 
         install_package_from_stream_command(
             client,
@@ -65,42 +62,50 @@ impl AppCommand for PushPkgCommand {
         .await
         .map_err(PushPkgCommandError::InstallPackage)?;
 
-        return Ok(());
+        Ok(())
+    }
+}
 
-        fn pack_folder_as_gzip(folder: &Path) -> Result<(String, Vec<u8>), CommandDynError> {
-            let package_name = get_package_name_from_folder(folder)?;
-            let mut package_gzip = vec![];
+/// Packs package folders into a single gzip package or a zip archive with multiple packages.
+pub fn pack_package_folders(folders: &[PathBuf]) -> Result<(String, Vec<u8>), CommandDynError> {
+    return match folders.len() {
+        1 => pack_folder_as_gzip(&folders[0]),
+        _ => pack_folders_as_zip(folders),
+    };
+
+    fn pack_folder_as_gzip(folder: &Path) -> Result<(String, Vec<u8>), CommandDynError> {
+        let package_name = get_package_name_from_folder(folder)?;
+        let mut package_gzip = vec![];
+
+        pack_gzip_package_from_folder(
+            folder,
+            &mut package_gzip,
+            &GZipPackageFromFolderPackerConfig {
+                compression: Some(Compression::fast()),
+            },
+        )
+        .map_err(PushPkgCommandError::PackGzipPackage)?;
 
-            pack_gzip_package_from_folder(
-                folder,
-                &mut package_gzip,
-                &GZipPackageFromFolderPackerConfig {
+        Ok((format!("{package_name}.gz"), package_gzip))
+    }
+
+    fn pack_folders_as_zip(
+        source_folders: &[PathBuf],
+    ) -> Result<(String, Vec<u8>), CommandDynError> {
+        let mut package_zip_cursor = Cursor::new(vec![]);
+
+        pack_zip_package_from_folders(
+            source_folders,
+            &mut package_zip_cursor,
+            &ZipPackageFromFolderPackerConfig {
+                gzip_config: GZipPackageFromFolderPackerConfig {
                     compression: Some(Compression::fast()),
                 },
-            )
-            .map_err(PushPkgCommandError::PackGzipPackage)?;
-
-            Ok((format!("{package_name}.gz"), package_gzip))
-        }
-
-        fn pack_folders_as_zip(
-            source_folders: &[PathBuf],
-        ) -> Result<(String, Vec<u8>), CommandDynError> {
-            let mut package_zip_cursor = Cursor::new(vec![]);
-
-            pack_zip_package_from_folders(
-                source_folders,
-                &mut package_zip_cursor,
-                &ZipPackageFromFolderPackerConfig {
-                    gzip_config: GZipPackageFromFolderPackerConfig {
-                        compression: Some(Compression::fast()),
-                    },
-                    zip_compression_method: Some(zip::CompressionMethod::Stored),
-                },
-            )
-            .map_err(PushPkgCommandError::PackZipPackage)?;
+                zip_compression_method: Some(zip::CompressionMethod::Stored),
+            },
+        )
+        .map_err(PushPkgCommandError::PackZipPackage)?;
 
-            Ok(("Packages.zip".to_owned(), package_zip_cursor.into_inner()))
-        }
+        Ok(("Packages.zip".to_owned(), package_zip_cursor.into_inner()))
     }
 }
@@ -0,0 +1,155 @@
+use crate::app::CrtClient;
+use crate::cmd::app::AppCommand;
+use crate::cmd::app::pkg::backup::{find_backup, get_backups_folder, list_backups};
+use crate::cmd::app::pkg::install_pkg::{
+    InstallPkgCommandOptions, install_package_from_stream_command,
+};
+use crate::cmd::cli::CommandResult;
+use anstyle::{AnsiColor, Color, Style};
+use clap::{Args, Subcommand};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Args, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct RollbackPkgCommand {
+    #[command(subcommand)]
+    command: Option<RollbackPkgCommands>,
+
+    /// Identifier of the backup to reinstall (default: the latest backup)
+    #[arg(value_name = "BACKUP_ID", value_hint = clap::ValueHint::Other)]
+    backup_id: Option<String>,
+
+    #[command(flatten)]
+    install_pkg_options: InstallPkgCommandOptions,
+}
+
+#[derive(Debug, Subcommand)]
+enum RollbackPkgCommands {
+    /// Lists available packages backups of the Creatio instance
+    List {
+        /// Display the output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Debug, Error)]
+pub enum RollbackPkgCommandError {
+    #[error("backup {0} does not contain packages archive, nothing to reinstall")]
+    EmptyBackup(String),
+
+    #[error("failed to read backup archive: {0}")]
+    ReadArchive(#[source] std::io::Error),
+}
+
+impl AppCommand for RollbackPkgCommand {
+    async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
+        match &self.command {
+            Some(RollbackPkgCommands::List { json }) => print_backups_list(&client, *json),
+            None => self.rollback(client).await,
+        }
+    }
+}
+
+impl RollbackPkgCommand {
+    async fn rollback(&self, client: Arc<CrtClient>) -> CommandResult {
+        let backup = find_backup(&client, self.backup_id.as_deref())?;
+        let manifest = backup.manifest();
+
+        let Some(archive_path) = backup.archive_path() else {
+            return Err(RollbackPkgCommandError::EmptyBackup(manifest.id.clone()).into());
+        };
+
+        let yellow = Style::new()
+            .fg_color(Some(Color::Ansi(AnsiColor::BrightYellow)))
+            .dimmed();
+
+        eprintln!(
+            "Restoring backup {bold}{id}{bold:#} ({created_at}): {packages}",
+            bold = Style::new().bold(),
+            id = manifest.id,
+            created_at = manifest.created_at,
+            packages = manifest
+                .packages
+                .iter()
+                .map(|p| p.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
+
+        if !manifest.new_packages.is_empty() {
+            eprintln!(
+                "{yellow}warning: packages which were not installed at the moment of backup will remain installed: {}{yellow:#}",
+                manifest.new_packages.join(", "),
+            );
+        }
+
+        let content = tokio::fs::read(&archive_path)
+            .await
+            .map_err(RollbackPkgCommandError::ReadArchive)?;
+
+        install_package_from_stream_command(
+            client,
+            content,
+            format!("Backup_{}.zip", manifest.id),
+            &self.install_pkg_options.for_reinstall(),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+fn print_backups_list(client: &CrtClient, json: bool) -> CommandResult {
+    let backups = list_backups(client)?;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!(backups.iter().map(|b| b.manifest()).collect::<Vec<_>>())
+        );
+
+        return Ok(());
+    }
+
+    let bold = Style::new().bold();
+    let dimmed = Style::new().dimmed();
+
+    if backups.is_empty() {
+        eprintln!(
+            "{dimmed}No backups found in {}{dimmed:#}",
+            get_backups_folder(client).display()
+        );
+
+        return Ok(());
+    }
+
+    for backup in backups.iter().rev() {
+        let manifest = backup.manifest();
+
+        println!(
+            "{bold}{id}{bold:#}  {dimmed}{created_at}{dimmed:#}",
+            id = manifest.id,
+            created_at = manifest.created_at,
+        );
+
+        for package in &manifest.packages {
+            println!(
+                "  {name}{version}",
+                name = package.name,
+                version = package
+                    .version
+                    .as_ref()
+                    .map(|v| format!(" ({v})"))
+                    .unwrap_or_default()
+            );
+        }
+
+        for package in &manifest.new_packages {
+            println!("  {dimmed}{package} (not installed){dimmed:#}");
+        }
+    }
+
+    Ok(())
+}
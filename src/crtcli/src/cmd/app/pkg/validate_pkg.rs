@@ -0,0 +1,145 @@
+use crate::app::CrtClient;
+use crate::app::package_installer::{PackageValidationProblemLevel, PackageValidationResult};
+use crate::cmd::app::AppCommand;
+use crate::cmd::app::pkg::install_pkg::read_package_files;
+use crate::cmd::app::pkg::push_pkg::pack_package_folders;
+use crate::cmd::cli::CommandResult;
+use anstream::stdout;
+use anstyle::{AnsiColor, Color, Style};
+use clap::Args;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Args, Debug)]
+pub struct ValidatePkgCommand {
+    /// Paths to the package archive files (.zip or .gz) or package folders to validate (Use single '@-' or '-' value to read archive from stdin)
+    #[arg(required = true, value_hint = clap::ValueHint::AnyPath)]
+    paths: Vec<PathBuf>,
+
+    /// Print validation result as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Error)]
+pub enum ValidatePkgCommandError {
+    #[error(
+        "cannot mix package folders and package archive files, please specify only folders or only files"
+    )]
+    MixedPaths,
+
+    #[error("package validation failed with {0} error(s)")]
+    ValidationFailed(usize),
+}
+
+impl AppCommand for ValidatePkgCommand {
+    async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
+        let folders_count = self.paths.iter().filter(|p| p.is_dir()).count();
+
+        let (package_content, package_name) = match folders_count {
+            0 => read_package_files(&self.paths)?,
+            n if n == self.paths.len() => {
+                let (package_name, package_content) = pack_package_folders(&self.paths)?;
+                (package_content, package_name)
+            }
+            _ => return Err(ValidatePkgCommandError::MixedPaths.into()),
+        };
+
+        let progress = spinner_precise!(
+            "Uploading {bold}{package_name}{bold:#} package archive at {bold}{url}{bold:#}",
+            bold = Style::new().bold(),
+            url = client.base_url(),
+        );
+
+        let package_installer_service = client.package_installer_service();
+
+        let mut chunked_upload_iter = package_installer_service
+            .start_upload_package_chunked(package_content, package_name.clone());
+
+        while let Some(chunk) = chunked_upload_iter.next() {
+            chunk.await?;
+        }
+
+        progress.set_message(format!(
+            "Validating {bold}{package_name}{bold:#} package archive at {bold}{url}{bold:#}",
+            bold = Style::new().bold(),
+            url = client.base_url(),
+        ));
+
+        let result = validate_uploaded_package(&client, &package_name).await?;
+
+        progress.finish_and_clear();
+
+        match self.json {
+            true => {
+                serde_json::to_writer_pretty(std::io::stdout().lock(), &result)?;
+                println!();
+            }
+            false => print_package_validation_result(&result, &package_name),
+        }
+
+        match result.errors_count() {
+            0 => Ok(()),
+            errors => Err(ValidatePkgCommandError::ValidationFailed(errors).into()),
+        }
+    }
+}
+
+/// Validates the package archive which is already uploaded to Creatio.
+pub async fn validate_uploaded_package(
+    client: &CrtClient,
+    package_filename: &str,
+) -> Result<PackageValidationResult, crate::app::CrtClientError> {
+    let code = package_filename
+        .rsplit_once('.')
+        .map_or(package_filename, |(name, _)| name);
+
+    client
+        .package_installer_service()
+        .validate_package(code, package_filename)
+        .await
+}
+
+pub fn print_package_validation_result(result: &PackageValidationResult, package_name: &str) {
+    let mut stdout = stdout().lock();
+
+    let error_style = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Red)));
+    let warning_style = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Yellow)));
+    let bold = Style::new().bold();
+
+    for problem in &result.problems {
+        let (label, style) = match problem.level {
+            PackageValidationProblemLevel::Error => ("error", error_style),
+            PackageValidationProblemLevel::Warning => ("warning", warning_style),
+            PackageValidationProblemLevel::Info => ("info", Style::new().dimmed()),
+        };
+
+        write!(stdout, "{style}{label}:{style:#} ", style = style.bold()).unwrap();
+
+        if let Some(package) = &problem.package {
+            write!(stdout, "{bold}{package}{bold:#}: ").unwrap();
+        }
+
+        writeln!(stdout, "{style}{}{style:#}", problem.message).unwrap();
+    }
+
+    match result.errors_count() {
+        0 => writeln!(
+            stdout,
+            "{green}Package archive {green_bold}{package_name}{green_bold:#}{green} passed validation ({} warning(s)){green:#}",
+            result.warnings_count(),
+            green = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green))),
+            green_bold = Style::new()
+                .fg_color(Some(Color::Ansi(AnsiColor::Green)))
+                .bold(),
+        ),
+        errors => writeln!(
+            stdout,
+            "{error_style}Package archive {bold}{package_name}{bold:#}{error_style} failed validation: {errors} error(s), {} warning(s){error_style:#}",
+            result.warnings_count(),
+        ),
+    }
+    .unwrap();
+}
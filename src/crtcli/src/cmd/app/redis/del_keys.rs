@@ -0,0 +1,72 @@
+use crate::app::CrtClient;
+use crate::app::redis::RedisClient;
+use crate::cmd::app::redis::RedisCommandError;
+use crate::cmd::cli::CommandResult;
+use anstyle::Style;
+use clap::Args;
+use std::io::IsTerminal;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+const DEL_BATCH_SIZE: usize = 500;
+
+#[derive(Args, Debug)]
+pub struct DelKeysCommand {
+    /// Glob-style pattern of the keys to delete, e.g. "*Session*" or the exact key name
+    #[arg(value_hint = clap::ValueHint::Other)]
+    pattern: String,
+
+    /// Do not ask for confirmation before deleting keys
+    #[arg(short, long)]
+    yes: bool,
+}
+
+impl DelKeysCommand {
+    pub async fn run<S>(&self, client: &CrtClient, redis: &mut RedisClient<S>) -> CommandResult
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let bold = Style::new().bold();
+
+        let keys = redis
+            .scan(&self.pattern)
+            .await
+            .map_err(RedisCommandError::Redis)?;
+
+        if keys.is_empty() {
+            eprintln!(
+                "No keys match {bold}{pattern}{bold:#}, nothing was deleted",
+                pattern = self.pattern
+            );
+            return Ok(());
+        }
+
+        if !self.yes {
+            if !std::io::stdin().is_terminal() {
+                return Err(RedisCommandError::ConfirmationRequired.into());
+            }
+
+            let confirmed = crate::cmd::utils::confirm(&format!(
+                "Delete {bold}{count}{bold:#} key(s) matching {bold}{pattern}{bold:#} from {bold}{url}{bold:#} redis?",
+                count = keys.len(),
+                pattern = self.pattern,
+                url = client.base_url(),
+            ))
+            .map_err(RedisCommandError::Confirmation)?;
+
+            if !confirmed {
+                eprintln!("Aborted, no keys were deleted");
+                return Ok(());
+            }
+        }
+
+        let mut deleted = 0;
+
+        for batch in keys.chunks(DEL_BATCH_SIZE) {
+            deleted += redis.del(batch).await.map_err(RedisCommandError::Redis)?;
+        }
+
+        eprintln!("✓ Deleted {bold}{deleted}{bold:#} key(s)");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use crate::app::redis::{RedisClient, RespValue};
+use crate::cmd::app::redis::RedisCommandError;
+use crate::cmd::cli::CommandResult;
+use clap::Args;
+use std::io::{IsTerminal, Write};
+use tokio::io::{AsyncRead, AsyncWrite};
+
+#[derive(Args, Debug)]
+pub struct GetKeyCommand {
+    /// Key name
+    #[arg(value_hint = clap::ValueHint::Other)]
+    key: String,
+}
+
+impl GetKeyCommand {
+    pub async fn run<S>(&self, redis: &mut RedisClient<S>) -> CommandResult
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let key = self.key.as_bytes();
+
+        let key_type = redis
+            .key_type(&self.key)
+            .await
+            .map_err(RedisCommandError::Redis)?;
+
+        let (args, pairs): (&[&[u8]], bool) = match key_type.as_str() {
+            "none" => return Err(RedisCommandError::KeyNotFound(self.key.clone()).into()),
+            "string" => (&[b"GET", key], false),
+            "hash" => (&[b"HGETALL", key], true),
+            "list" => (&[b"LRANGE", key, b"0", b"-1"], false),
+            "set" => (&[b"SMEMBERS", key], false),
+            "zset" => (&[b"ZRANGE", key, b"0", b"-1", b"WITHSCORES"], true),
+            _ => {
+                return Err(
+                    RedisCommandError::UnsupportedKeyType(self.key.clone(), key_type).into(),
+                );
+            }
+        };
+
+        let value = redis
+            .command(args)
+            .await
+            .map_err(RedisCommandError::Redis)?;
+
+        let mut stdout = std::io::stdout().lock();
+
+        match value {
+            // Raw bytes are written as is, so binary values can be redirected to a file
+            RespValue::BulkString(Some(data)) => {
+                stdout.write_all(&data)?;
+
+                if stdout.is_terminal() {
+                    writeln!(stdout)?;
+                }
+            }
+            RespValue::Array(Some(items)) => {
+                let items: Vec<_> = items.iter().map(display_value).collect();
+
+                match pairs {
+                    true => {
+                        for pair in items.chunks(2) {
+                            writeln!(stdout, "{}", pair.join("\t"))?;
+                        }
+                    }
+                    false => {
+                        for item in items {
+                            writeln!(stdout, "{item}")?;
+                        }
+                    }
+                }
+            }
+            value => writeln!(stdout, "{}", display_value(&value))?,
+        }
+
+        Ok(())
+    }
+}
+
+fn display_value(value: &RespValue) -> String {
+    match value {
+        RespValue::SimpleString(value) => value.clone(),
+        RespValue::Integer(value) => value.to_string(),
+        RespValue::BulkString(Some(data)) => String::from_utf8_lossy(data).into_owned(),
+        RespValue::BulkString(None) | RespValue::Array(None) => "(nil)".to_owned(),
+        RespValue::Array(Some(items)) => items
+            .iter()
+            .map(display_value)
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
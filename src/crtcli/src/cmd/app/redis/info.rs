@@ -0,0 +1,28 @@
+use crate::app::redis::RedisClient;
+use crate::cmd::app::redis::RedisCommandError;
+use crate::cmd::cli::CommandResult;
+use clap::Args;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+#[derive(Args, Debug)]
+pub struct InfoCommand {
+    /// Section of the information, e.g. "memory", "keyspace" or "all"
+    #[arg(value_hint = clap::ValueHint::Other)]
+    section: Option<String>,
+}
+
+impl InfoCommand {
+    pub async fn run<S>(&self, redis: &mut RedisClient<S>) -> CommandResult
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let info = redis
+            .info(self.section.as_deref())
+            .await
+            .map_err(RedisCommandError::Redis)?;
+
+        print!("{}", info.replace("\r\n", "\n"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+use crate::app::redis::RedisClient;
+use crate::cmd::app::redis::RedisCommandError;
+use crate::cmd::cli::CommandResult;
+use clap::Args;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+#[derive(Args, Debug)]
+pub struct KeysCommand {
+    /// Glob-style pattern of the keys, e.g. "*Session*"
+    #[arg(default_value = "*", value_hint = clap::ValueHint::Other)]
+    pattern: String,
+}
+
+impl KeysCommand {
+    pub async fn run<S>(&self, redis: &mut RedisClient<S>) -> CommandResult
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let keys = redis
+            .scan(&self.pattern)
+            .await
+            .map_err(RedisCommandError::Redis)?;
+
+        for key in keys {
+            println!("{key}");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+use crate::app::redis::{RedisClient, RedisError};
+use crate::app::{CrtClient, CrtClientError};
+use crate::cmd::app::AppCommand;
+use crate::cmd::app::tunnel::connection_string::ConnectionString;
+use crate::cmd::cli::{CommandDynError, CommandResult};
+use clap::{Args, Subcommand};
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+mod del_keys;
+
+mod get_key;
+
+mod info;
+
+mod keys;
+
+mod ttl;
+
+#[derive(Args, Debug)]
+pub struct RedisCommand {
+    /// Name of the Redis connection string in the Creatio configuration
+    #[arg(
+        long,
+        global = true,
+        default_value = "redis",
+        value_name = "NAME",
+        value_hint = clap::ValueHint::Other
+    )]
+    connection_string: String,
+
+    #[command(subcommand)]
+    command: RedisCommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum RedisCommands {
+    /// Lists keys matching the glob-style pattern (uses SCAN, so the server is not blocked)
+    Keys(keys::KeysCommand),
+
+    /// Prints the value of the key (strings, hashes, lists, sets and sorted sets are supported)
+    Get(get_key::GetKeyCommand),
+
+    /// Deletes keys matching the glob-style pattern
+    Del(del_keys::DelKeysCommand),
+
+    /// Prints the remaining time to live of the key in seconds, -1 if the key has no expiration
+    Ttl(ttl::TtlCommand),
+
+    /// Prints information and statistics about the Redis server
+    Info(info::InfoCommand),
+}
+
+#[derive(Debug, Error)]
+pub enum RedisCommandError {
+    #[error("redis connection string {0} is not defined in Creatio, available: {1}")]
+    ConnectionStringNotFound(String, String),
+
+    #[error("failed to find server address in connection string {0}")]
+    ServerAddressNotFound(String),
+
+    #[error("invalid redis database number in connection string: {0}")]
+    InvalidDb(String),
+
+    #[error("failed to connect to redis via tunnel: {0}")]
+    Connect(#[source] CrtClientError),
+
+    #[error("{0}")]
+    Redis(#[from] RedisError),
+
+    #[error("key {0} does not exist")]
+    KeyNotFound(String),
+
+    #[error("key {0} has unsupported type: {1}")]
+    UnsupportedKeyType(String, String),
+
+    #[error(
+        "confirmation is required to delete keys, use --yes flag to run in non-interactive mode"
+    )]
+    ConfirmationRequired,
+
+    #[error("failed to read confirmation: {0}")]
+    Confirmation(#[source] std::io::Error),
+}
+
+impl AppCommand for RedisCommand {
+    async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
+        let mut redis = connect_redis(&client, &self.connection_string).await?;
+
+        match &self.command {
+            RedisCommands::Keys(command) => command.run(&mut redis).await,
+            RedisCommands::Get(command) => command.run(&mut redis).await,
+            RedisCommands::Del(command) => command.run(&client, &mut redis).await,
+            RedisCommands::Ttl(command) => command.run(&mut redis).await,
+            RedisCommands::Info(command) => command.run(&mut redis).await,
+        }
+    }
+}
+
+/// Connects to the Redis server from the Creatio connection string through the tunneling websocket,
+/// authenticates and selects the database if they are specified in the connection string.
+async fn connect_redis(
+    client: &CrtClient,
+    connection_string_name: &str,
+) -> Result<RedisClient<impl AsyncRead + AsyncWrite + Unpin>, CommandDynError> {
+    let connection_strings = client
+        .crtcli_tunneling_service()
+        .get_connection_strings()
+        .await?;
+
+    let connection_string = connection_strings
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(connection_string_name))
+        .map(|(_, value)| ConnectionString::parse(value))
+        .ok_or_else(|| {
+            RedisCommandError::ConnectionStringNotFound(
+                connection_string_name.to_owned(),
+                connection_strings
+                    .keys()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+        })?;
+
+    let endpoint = connection_string.endpoint().ok_or_else(|| {
+        RedisCommandError::ServerAddressNotFound(connection_string_name.to_owned())
+    })?;
+
+    let db = match connection_string.get("db").filter(|db| !db.is_empty()) {
+        Some(db) => db
+            .parse::<u32>()
+            .map_err(|_| RedisCommandError::InvalidDb(db.to_owned()))?,
+        None => 0,
+    };
+
+    let stream = client
+        .crtcli_tunneling_service()
+        .connect_stream(&endpoint.host, endpoint.port)
+        .await
+        .map_err(RedisCommandError::Connect)?;
+
+    let mut redis = RedisClient::new(stream);
+
+    if let Some(password) = connection_string
+        .get("password")
+        .filter(|password| !password.is_empty())
+    {
+        let username = connection_string
+            .get("user")
+            .or_else(|| connection_string.get("username"))
+            .filter(|username| !username.is_empty());
+
+        redis
+            .auth(username, password)
+            .await
+            .map_err(RedisCommandError::Redis)?;
+    }
+
+    if db != 0 {
+        redis.select(db).await.map_err(RedisCommandError::Redis)?;
+    }
+
+    Ok(redis)
+}
@@ -0,0 +1,32 @@
+use crate::app::redis::RedisClient;
+use crate::cmd::app::redis::RedisCommandError;
+use crate::cmd::cli::CommandResult;
+use clap::Args;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+#[derive(Args, Debug)]
+pub struct TtlCommand {
+    /// Key name
+    #[arg(value_hint = clap::ValueHint::Other)]
+    key: String,
+}
+
+impl TtlCommand {
+    pub async fn run<S>(&self, redis: &mut RedisClient<S>) -> CommandResult
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let ttl = redis
+            .ttl(&self.key)
+            .await
+            .map_err(RedisCommandError::Redis)?;
+
+        if ttl == -2 {
+            return Err(RedisCommandError::KeyNotFound(self.key.clone()).into());
+        }
+
+        println!("{ttl}");
+
+        Ok(())
+    }
+}
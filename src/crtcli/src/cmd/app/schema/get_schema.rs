@@ -0,0 +1,58 @@
+use crate::app::CrtClient;
+use crate::cmd::app::AppCommand;
+use crate::cmd::app::schema::resolve_source_code_schema;
+use crate::cmd::cli::CommandResult;
+use anstyle::Style;
+use clap::Args;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[derive(Args, Debug)]
+pub struct GetSchemaCommand {
+    /// Name of the source code schema. Example: "UsrMyService"
+    #[arg(value_hint = clap::ValueHint::Other)]
+    schema_name: String,
+
+    /// Name of the package containing the schema (required when schema exists in multiple packages)
+    #[arg(short, long, value_hint = clap::ValueHint::Other)]
+    package: Option<String>,
+
+    /// Output file path where the schema source code will be saved (default: print to stdout)
+    #[arg(short, long, value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+    output: Option<PathBuf>,
+}
+
+impl AppCommand for GetSchemaCommand {
+    async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
+        let schema_info =
+            resolve_source_code_schema(&client, &self.schema_name, self.package.as_deref()).await?;
+
+        let schema = client
+            .source_code_schema_designer_service()
+            .get_schema(&schema_info.schema_uid, &schema_info.package_uid)
+            .await?;
+
+        match &self.output {
+            Some(output) => {
+                std::fs::write(output, schema.body())?;
+
+                eprintln!(
+                    "Schema {bold}{schema_name}{bold:#} ({package_name}) saved to {bold}{output}{bold:#}",
+                    bold = Style::new().bold(),
+                    schema_name = self.schema_name,
+                    package_name = schema_info.package_name,
+                    output = output.display()
+                );
+            }
+            None => {
+                let mut stdout = std::io::stdout().lock();
+
+                stdout.write_all(schema.body().as_bytes())?;
+                stdout.flush()?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+use crate::app::CrtClient;
+use crate::app::source_code_schema::SourceCodeSchemaInfo;
+use crate::cmd::app::AppCommand;
+use crate::cmd::cli::CommandResult;
+use anstyle::{AnsiColor, Color, Style};
+use clap::Subcommand;
+use std::sync::Arc;
+use thiserror::Error;
+
+mod get_schema;
+
+mod set_schema;
+
+#[derive(Debug, Subcommand)]
+pub enum SchemaCommands {
+    /// Downloads the source code (body) of a source code schema
+    Get(get_schema::GetSchemaCommand),
+
+    /// Uploads the source code (body) of a source code schema from a file
+    Set(set_schema::SetSchemaCommand),
+}
+
+impl AppCommand for SchemaCommands {
+    async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
+        match self {
+            SchemaCommands::Get(command) => command.run(client).await,
+            SchemaCommands::Set(command) => command.run(client).await,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ResolveSchemaError {
+    #[error("invalid schema name '{0}', only latin letters, digits and underscores are allowed")]
+    InvalidSchemaName(String),
+
+    #[error("source code schema '{0}' was not found")]
+    NotFound(String),
+
+    #[error("source code schema '{0}' was not found in package '{1}'")]
+    NotFoundInPackage(String, String),
+
+    #[error(
+        "source code schema '{0}' exists in multiple packages ({1}), please specify the package using --package option"
+    )]
+    Ambiguous(String, String),
+}
+
+/// Finds schemas using designer and workspace explorer services,
+/// sql runner is used as a fallback if the services fail or do not find the schema.
+async fn find_source_code_schemas(
+    client: &CrtClient,
+    schema_name: &str,
+) -> Result<Vec<SourceCodeSchemaInfo>, crate::cmd::cli::CommandDynError> {
+    let service_result = client
+        .source_code_schema_designer_service()
+        .find_schemas(schema_name)
+        .await;
+
+    match service_result {
+        Ok(schemas) if !schemas.is_empty() => Ok(schemas),
+        Ok(schemas) => Ok(client
+            .sql_scripts()
+            .find_source_code_schemas(schema_name)
+            .await
+            .unwrap_or(schemas)),
+        Err(service_err) => {
+            eprintln!(
+                "{style}warning: failed to find schema using workspace explorer service, falling back to sql: {service_err}{style:#}",
+                style = Style::new()
+                    .fg_color(Some(Color::Ansi(AnsiColor::BrightYellow)))
+                    .dimmed()
+            );
+
+            Ok(client
+                .sql_scripts()
+                .find_source_code_schemas(schema_name)
+                .await?)
+        }
+    }
+}
+
+async fn resolve_source_code_schema(
+    client: &CrtClient,
+    schema_name: &str,
+    package_name: Option<&str>,
+) -> Result<SourceCodeSchemaInfo, crate::cmd::cli::CommandDynError> {
+    if schema_name.is_empty()
+        || !schema_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return Err(ResolveSchemaError::InvalidSchemaName(schema_name.to_owned()).into());
+    }
+
+    let mut schemas = find_source_code_schemas(client, schema_name).await?;
+
+    if let Some(package_name) = package_name {
+        schemas.retain(|s| s.package_name.eq_ignore_ascii_case(package_name));
+
+        if schemas.is_empty() {
+            return Err(ResolveSchemaError::NotFoundInPackage(
+                schema_name.to_owned(),
+                package_name.to_owned(),
+            )
+            .into());
+        }
+    }
+
+    match schemas.len() {
+        0 => Err(ResolveSchemaError::NotFound(schema_name.to_owned()).into()),
+        1 => Ok(schemas.remove(0)),
+        _ => Err(ResolveSchemaError::Ambiguous(
+            schema_name.to_owned(),
+            schemas
+                .iter()
+                .map(|s| s.package_name.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
+        .into()),
+    }
+}
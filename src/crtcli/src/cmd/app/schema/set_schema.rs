@@ -0,0 +1,89 @@
+use crate::app::CrtClient;
+use crate::cmd::app::AppCommand;
+use crate::cmd::app::pkg::compile_pkg::CompilePkgCommand;
+use crate::cmd::app::schema::resolve_source_code_schema;
+use crate::cmd::cli::CommandResult;
+use anstyle::{AnsiColor, Color, Style};
+use clap::Args;
+use std::io::{IsTerminal, Read, stdin};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[derive(Args, Debug)]
+pub struct SetSchemaCommand {
+    /// Name of the source code schema. Example: "UsrMyService"
+    #[arg(value_hint = clap::ValueHint::Other)]
+    schema_name: String,
+
+    /// Path to the file with new schema source code (Use '-' value to read data from stdin)
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    file: PathBuf,
+
+    /// Name of the package containing the schema (required when schema exists in multiple packages)
+    #[arg(short, long, value_hint = clap::ValueHint::Other)]
+    package: Option<String>,
+
+    /// Compile the package containing the schema after successful upload
+    #[arg(short, long)]
+    compile: bool,
+}
+
+impl AppCommand for SetSchemaCommand {
+    async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
+        let body = if self.file.as_os_str() == "-" {
+            if stdin().is_terminal() {
+                return Err("stdin is a terminal, please pipe the schema source code or specify a file path".into());
+            }
+
+            let mut data = String::new();
+            stdin().lock().read_to_string(&mut data)?;
+            data
+        } else {
+            std::fs::read_to_string(&self.file)?
+        };
+
+        let schema_info =
+            resolve_source_code_schema(&client, &self.schema_name, self.package.as_deref()).await?;
+
+        let progress = spinner!(
+            "Uploading schema {bold}{schema_name}{bold:#} ({package_name}) to {bold}{url}{bold:#}",
+            bold = Style::new().bold(),
+            schema_name = self.schema_name,
+            package_name = schema_info.package_name,
+            url = client.base_url()
+        );
+
+        let designer_service = client.source_code_schema_designer_service();
+
+        let mut schema = designer_service
+            .get_schema(&schema_info.schema_uid, &schema_info.package_uid)
+            .await?;
+
+        schema.set_body(body);
+
+        designer_service.save_schema(&schema).await?;
+
+        progress.finish_with_message(format!(
+            "{green}Schema {green_bold}{schema_name}{green_bold:#}{green} successfully saved in package {green_bold}{package_name}{green_bold:#}",
+            green = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green))),
+            green_bold = Style::new()
+                .fg_color(Some(Color::Ansi(AnsiColor::Green)))
+                .bold(),
+            schema_name = self.schema_name,
+            package_name = schema_info.package_name,
+        ));
+
+        if self.compile {
+            CompilePkgCommand {
+                package_names: vec![schema_info.package_name],
+                force_rebuild: false,
+                restart: false,
+                report_format: Default::default(),
+            }
+            .run(client)
+            .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use crate::app::{CrtClient, CrtSession};
+use crate::cmd::app::AppCommand;
+use crate::cmd::app::session::{SessionCommandError, get_current_session};
+use crate::cmd::cli::CommandResult;
+use clap::{Args, ValueEnum};
+use std::sync::Arc;
+
+#[derive(Args, Debug)]
+pub struct ExportSessionCommand {
+    /// Output format of the session
+    #[arg(long, short, value_enum, default_value_t)]
+    format: SessionExportFormat,
+}
+
+#[derive(Debug, Default, Clone, Copy, ValueEnum)]
+enum SessionExportFormat {
+    /// curl config with authentication headers, use it with `curl -K <file>`
+    #[default]
+    Curl,
+
+    /// Netscape HTTP Cookie File, can be imported into browsers and Postman
+    NetscapeCookies,
+
+    /// Shell environment variables with the URL and authentication headers
+    Env,
+}
+
+impl AppCommand for ExportSessionCommand {
+    async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
+        let entry = get_current_session(&client).await?;
+
+        let headers = match &entry.session {
+            CrtSession::Cookie(cookie) => vec![
+                ("Cookie", cookie.to_cookie_value()),
+                ("BPMCSRF", cookie.bpmcsrf().to_owned()),
+            ],
+            CrtSession::OAuthSession(oauth) => vec![(
+                "Authorization",
+                format!("{} {}", oauth.token_type(), oauth.access_token()),
+            )],
+        };
+
+        match self.format {
+            SessionExportFormat::Curl => {
+                for (name, value) in headers {
+                    println!(
+                        "header = \"{}\"",
+                        escape_curl_config(&format!("{name}: {value}"))
+                    );
+                }
+            }
+            SessionExportFormat::NetscapeCookies => {
+                let CrtSession::Cookie(cookie) = &entry.session else {
+                    return Err(SessionCommandError::OAuthCookies.into());
+                };
+
+                let url = reqwest::Url::parse(&entry.url).map_err(|err| {
+                    SessionCommandError::InvalidUrl(entry.url.clone(), err.to_string())
+                })?;
+
+                let domain = url.host_str().unwrap_or_default();
+                let path = match url.path() {
+                    "" => "/",
+                    path => path,
+                };
+                let secure = match url.scheme() {
+                    "https" => "TRUE",
+                    _ => "FALSE",
+                };
+                let expires = entry.expires_at.unix_timestamp();
+
+                println!("# Netscape HTTP Cookie File");
+
+                for (name, value) in cookie.cookies() {
+                    println!("{domain}\tFALSE\t{path}\t{secure}\t{expires}\t{name}\t{value}");
+                }
+            }
+            SessionExportFormat::Env => {
+                println!("export CRTCLI_SESSION_URL={}", quote_shell(&entry.url));
+
+                for (name, value) in headers {
+                    println!(
+                        "export CRTCLI_SESSION_{}={}",
+                        name.to_uppercase(),
+                        quote_shell(&value)
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn escape_curl_config(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn quote_shell(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
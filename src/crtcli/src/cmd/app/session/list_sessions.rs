@@ -0,0 +1,46 @@
+use crate::app::session_cache;
+use crate::cmd::app::session::{format_relative_time, session_kind};
+use crate::cmd::cli::CommandResult;
+use anstyle::Style;
+use clap::Args;
+use time::OffsetDateTime;
+
+#[derive(Args, Debug)]
+pub struct ListSessionsCommand;
+
+impl ListSessionsCommand {
+    pub fn run(&self) -> CommandResult {
+        let bold = Style::new().bold();
+        let dim = Style::new().dimmed();
+
+        let entries = session_cache::create_default_session_cache().entries();
+        let now = OffsetDateTime::now_utc();
+
+        let max_url_len = entries.iter().map(|e| e.url.len()).max().unwrap_or(0);
+        let max_user_len = entries.iter().map(|e| e.user.len()).max().unwrap_or(0);
+
+        for entry in &entries {
+            let expires = match entry.expires_at > now {
+                true => format!("expires {}", format_relative_time(entry.expires_at)),
+                false => format!("expired {}", format_relative_time(entry.expires_at)),
+            };
+
+            println!(
+                "{bold}{url:<max_url_len$}{bold:#}  {user:<max_user_len$}  {kind:<6}  {dim}created {created}, {expires}{dim:#}",
+                url = entry.url,
+                user = entry.user,
+                kind = session_kind(&entry.session),
+                created = format_relative_time(entry.created_at),
+            );
+        }
+
+        if entries.is_empty() {
+            eprintln!(
+                "{italic}— No cached sessions —{italic:#}",
+                italic = Style::new().italic(),
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use crate::app::session_cache::CrtSessionCacheEntryInfo;
+use crate::app::{CrtClient, CrtSession};
+use crate::cmd::app::AppCommand;
+use crate::cmd::cli::CommandResult;
+use crate::cmd::utils::humanize_duration;
+use clap::Subcommand;
+use std::sync::Arc;
+use thiserror::Error;
+use time::OffsetDateTime;
+
+mod export_session;
+
+mod list_sessions;
+
+mod revoke_session;
+
+mod show_session;
+
+#[derive(Debug, Subcommand)]
+pub enum SessionCommands {
+    /// Lists cached sessions of all Creatio instances with URL, user and age
+    List(list_sessions::ListSessionsCommand),
+
+    /// Prints the current session cookies or OAuth token and its expiry
+    Show(show_session::ShowSessionCommand),
+
+    /// Exports the current session to reuse it in a browser, Postman or curl scripts
+    Export(export_session::ExportSessionCommand),
+
+    /// Removes the cached session of the Creatio instance, so the next command logs in again
+    Revoke(revoke_session::RevokeSessionCommand),
+}
+
+impl SessionCommands {
+    /// Runs commands which work with the session cache only and do not require the current app.
+    pub fn run_without_client(&self) -> Option<CommandResult> {
+        match self {
+            SessionCommands::List(command) => Some(command.run()),
+            SessionCommands::Revoke(command) => Some(command.run()),
+            SessionCommands::Show(_) | SessionCommands::Export(_) => None,
+        }
+    }
+}
+
+impl AppCommand for SessionCommands {
+    async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
+        match self {
+            SessionCommands::List(command) => command.run(),
+            SessionCommands::Show(command) => command.run(client).await,
+            SessionCommands::Export(command) => command.run(client).await,
+            SessionCommands::Revoke(command) => command.run(),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SessionCommandError {
+    #[error("session of {0} was not found in the session cache")]
+    NotCached(String),
+
+    #[error("netscape-cookies format is not supported for OAuth sessions, use curl or env format")]
+    OAuthCookies,
+
+    #[error("invalid Creatio URL {0}: {1}")]
+    InvalidUrl(String, String),
+}
+
+/// Returns the session of the current app, logging in if there is no valid cached session.
+async fn get_current_session(
+    client: &CrtClient,
+) -> Result<CrtSessionCacheEntryInfo, crate::cmd::cli::CommandDynError> {
+    client
+        .session_info()
+        .await?
+        .ok_or_else(|| SessionCommandError::NotCached(client.base_url().to_owned()).into())
+}
+
+fn session_kind(session: &CrtSession) -> &'static str {
+    match session {
+        CrtSession::Cookie(_) => "cookie",
+        CrtSession::OAuthSession(_) => "oauth",
+    }
+}
+
+/// Formats the time relative to now, e.g. "5m 3s ago" or "in 54m 57s".
+fn format_relative_time(at: OffsetDateTime) -> String {
+    let duration = at - OffsetDateTime::now_utc();
+    let humanized = humanize_duration(duration.unsigned_abs());
+
+    match duration.is_negative() {
+        true => format!("{humanized} ago"),
+        false => format!("in {humanized}"),
+    }
+}
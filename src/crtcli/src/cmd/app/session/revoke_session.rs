@@ -0,0 +1,36 @@
+use crate::app::session_cache;
+use crate::cmd::app::AppCommands;
+use crate::cmd::cli::CommandResult;
+use anstyle::Style;
+use clap::Args;
+
+#[derive(Args, Debug)]
+pub struct RevokeSessionCommand {
+    /// Creatio Base URL or App alias from .crtcli.toml of the session to revoke
+    #[arg(value_name = "URL/APP", value_hint = clap::ValueHint::Other)]
+    app: String,
+}
+
+impl RevokeSessionCommand {
+    pub fn run(&self) -> CommandResult {
+        let bold = Style::new().bold();
+
+        let credentials = AppCommands::credentials_by_url_or_alias(&self.app)?;
+        let removed = session_cache::create_default_session_cache().remove_entry(&credentials);
+
+        match removed {
+            true => eprintln!(
+                "✓ Session of {bold}{user}{bold:#} at {bold}{url}{bold:#} was removed from the cache",
+                user = credentials.user(),
+                url = credentials.url(),
+            ),
+            false => eprintln!(
+                "No cached session of {bold}{user}{bold:#} at {bold}{url}{bold:#}",
+                user = credentials.user(),
+                url = credentials.url(),
+            ),
+        }
+
+        Ok(())
+    }
+}
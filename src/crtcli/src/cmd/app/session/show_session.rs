@@ -0,0 +1,67 @@
+use crate::app::{CrtClient, CrtSession};
+use crate::cmd::app::AppCommand;
+use crate::cmd::app::session::{format_relative_time, get_current_session, session_kind};
+use crate::cmd::cli::CommandResult;
+use crate::cmd::utils::humanize_duration;
+use anstyle::Style;
+use clap::Args;
+use std::sync::Arc;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+#[derive(Args, Debug)]
+pub struct ShowSessionCommand;
+
+impl AppCommand for ShowSessionCommand {
+    async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
+        let bold = Style::new().bold();
+        let dim = Style::new().dimmed();
+
+        let entry = get_current_session(&client).await?;
+
+        println!("{bold}URL:{bold:#}      {}", entry.url);
+        println!("{bold}User:{bold:#}     {}", entry.user);
+        println!("{bold}Type:{bold:#}     {}", session_kind(&entry.session));
+        println!(
+            "{bold}Created:{bold:#}  {} {dim}({}){dim:#}",
+            entry.created_at.format(&Rfc3339)?,
+            format_relative_time(entry.created_at),
+        );
+        println!(
+            "{bold}Expires:{bold:#}  {} {dim}({}){dim:#}",
+            entry.expires_at.format(&Rfc3339)?,
+            format_relative_time(entry.expires_at),
+        );
+
+        match &entry.session {
+            CrtSession::Cookie(cookie) => {
+                println!("{bold}Cookies:{bold:#}");
+
+                for (name, value) in cookie.cookies() {
+                    println!("  {name}={value}");
+                }
+            }
+            CrtSession::OAuthSession(oauth) => {
+                let remaining = oauth.expires_at() - OffsetDateTime::now_utc();
+
+                match remaining.is_positive() {
+                    true => println!(
+                        "{bold}Valid:{bold:#}    {} remaining {dim}(refreshed automatically before expiry){dim:#}",
+                        humanize_duration(remaining.unsigned_abs())
+                    ),
+                    false => println!(
+                        "{bold}Valid:{bold:#}    expired {dim}(new token is requested on the next command){dim:#}"
+                    ),
+                }
+
+                println!(
+                    "{bold}Token:{bold:#}    {} {}",
+                    oauth.token_type(),
+                    oauth.access_token()
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
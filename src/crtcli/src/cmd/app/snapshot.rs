@@ -0,0 +1,349 @@
+use crate::app::{CrtClient, CrtClientError};
+use crate::cfg::package::combine_apply_config_from_args_and_config;
+use crate::cfg::{PkgConfig, WorkspaceConfig};
+use crate::cmd::app::AppCommand;
+use crate::cmd::cli::CommandResult;
+use crate::pkg::bundling::extractor::*;
+use crate::pkg::transforms::post::PkgFolderPostTransform;
+use anstream::stderr;
+use anstyle::{AnsiColor, Color, Style};
+use clap::Args;
+use regex::{Regex, RegexBuilder};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::io::AsyncReadExt;
+
+const WORKSPACE_CONFIG_FILENAME: &str = "workspace.crtcli.toml";
+
+#[derive(Args, Debug)]
+pub struct SnapshotCommand {
+    /// Destination folder where packages will be unpacked, each into the `<DESTINATION>/<PackageName>` folder
+    #[arg(value_hint = clap::ValueHint::DirPath)]
+    destination: PathBuf,
+
+    /// A comma-separated list of package maintainers to exclude from the snapshot (vendor packages)
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "Terrasoft,Creatio",
+        value_hint = clap::ValueHint::Other
+    )]
+    exclude_maintainer: Vec<String>,
+
+    /// Regular expression of package names to exclude from the snapshot (case-insensitive), can be specified multiple times
+    ///
+    /// Example: --exclude "^Crt" --exclude "^Usr(Test|Demo)"
+    #[arg(long, value_name = "REGEX", value_parser = parse_package_name_regex)]
+    exclude: Vec<Regex>,
+
+    /// Number of packages downloaded from Creatio in a single request
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u16).range(1..))]
+    batch_size: u16,
+
+    #[command(flatten)]
+    apply_features: Option<crate::pkg::transforms::PkgApplyFeatures>,
+
+    #[command(flatten)]
+    apply_post_features: Option<crate::pkg::transforms::post::PkgApplyPostFeatures>,
+}
+
+#[derive(Debug, Error)]
+pub enum SnapshotCommandError {
+    #[error("failed to get packages list: {0}")]
+    GetPackages(#[source] CrtClientError),
+
+    #[error(
+        "Creatio does not return package maintainers, so --exclude-maintainer can't exclude vendor packages, use --exclude to filter packages by name or pass --exclude-maintainer \"\" to snapshot all packages"
+    )]
+    MaintainersNotAvailable,
+
+    #[error("cannot download packages from remote: {0}")]
+    DownloadPackages(#[source] CrtClientError),
+
+    #[error("cannot read packages archive: {0}")]
+    ReadPackages(#[source] std::io::Error),
+
+    #[error("failed to read package config: {0}")]
+    PkgConfig(#[from] crate::cfg::package::PkgConfigError),
+
+    #[error("cannot unpack package: {0}")]
+    ExtractPackage(#[from] ExtractSingleZipPackageError),
+
+    #[error("failed to execute post apply: {0}")]
+    PostApply(#[from] crate::pkg::transforms::post::CombinedPkgFolderPostTransformError),
+
+    #[error("failed to read workspace config: {0}")]
+    ReadWorkspaceConfig(#[from] crate::cfg::workspace::WorkspaceConfigLoadFileError),
+
+    #[error("failed to write {0}: {1}")]
+    WriteWorkspaceConfig(PathBuf, #[source] std::io::Error),
+}
+
+fn parse_package_name_regex(value: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(value).case_insensitive(true).build()
+}
+
+impl AppCommand for SnapshotCommand {
+    async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
+        let progress = spinner!(
+            "Fetching packages list from {bold}{url}{bold:#}",
+            bold = Style::new().bold(),
+            url = client.base_url(),
+        );
+
+        let packages = client
+            .workspace_explorer_service()
+            .get_packages()
+            .await
+            .map_err(SnapshotCommandError::GetPackages)?;
+
+        progress.finish_and_clear();
+
+        let has_maintainer_filter = self.exclude_maintainer.iter().any(|m| !m.trim().is_empty());
+
+        // Without maintainers vendor packages can't be told apart, so the whole instance would be downloaded
+        if has_maintainer_filter && packages.iter().all(|p| p.maintainer().is_none()) {
+            if self.exclude.is_empty() {
+                return Err(SnapshotCommandError::MaintainersNotAvailable.into());
+            }
+
+            eprintln!(
+                "{style}warning: Creatio does not return package maintainers, --exclude-maintainer filter is not applied{style:#}",
+                style = Style::new()
+                    .fg_color(Some(Color::Ansi(AnsiColor::BrightYellow)))
+                    .dimmed()
+            );
+        }
+
+        let mut package_names = packages
+            .iter()
+            .filter(|p| {
+                !p.maintainer().is_some_and(|m| {
+                    self.exclude_maintainer
+                        .iter()
+                        .any(|x| x.trim().eq_ignore_ascii_case(m.trim()))
+                })
+            })
+            .filter(|p| !self.exclude.iter().any(|r| r.is_match(p.name())))
+            .map(|p| p.name().to_owned())
+            .collect::<Vec<_>>();
+
+        package_names.sort_by_key(|name| name.to_lowercase());
+
+        if package_names.is_empty() {
+            return Err("no packages left to snapshot after applying exclude filters".into());
+        }
+
+        eprintln!(
+            "Taking snapshot of {bold}{count}{bold:#} packages ({excluded} excluded) from {bold}{url}{bold:#} into {bold}{destination}{bold:#}",
+            bold = Style::new().bold(),
+            count = package_names.len(),
+            excluded = packages.len() - package_names.len(),
+            url = client.base_url(),
+            destination = self.destination.display(),
+        );
+
+        let batches = package_names
+            .chunks(self.batch_size as usize)
+            .collect::<Vec<_>>();
+
+        for (i, batch) in batches.iter().enumerate() {
+            self.snapshot_batch(&client, batch, (i + 1, batches.len()))
+                .await?;
+        }
+
+        update_workspace_config(&self.destination, &package_names)?;
+
+        spinner!(
+            finished_in = progress.elapsed(),
+            "{green}Snapshot of {green_bold}{count}{green_bold:#}{green} packages successfully taken from {green_bold}{url}{green_bold:#}{green}!{green:#}",
+            green = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green))),
+            green_bold = Style::new()
+                .fg_color(Some(Color::Ansi(AnsiColor::Green)))
+                .bold(),
+            count = package_names.len(),
+            url = client.base_url()
+        );
+
+        Ok(())
+    }
+}
+
+impl SnapshotCommand {
+    async fn snapshot_batch(
+        &self,
+        client: &CrtClient,
+        package_names: &[String],
+        (batch_index, batches_count): (usize, usize),
+    ) -> Result<(), SnapshotCommandError> {
+        let progress = spinner!(
+            "Downloading {bold}{packages}{bold:#} packages ({batch_index}/{batches_count})",
+            bold = Style::new().bold(),
+            packages = package_names.join(", "),
+        );
+
+        let mut package_data = vec![];
+
+        client
+            .package_installer_service()
+            .get_zip_packages(package_names)
+            .await
+            .map_err(SnapshotCommandError::DownloadPackages)?
+            .read_to_end(&mut package_data)
+            .await
+            .map_err(SnapshotCommandError::ReadPackages)?;
+
+        progress.finish_and_clear();
+
+        for package_name in package_names {
+            eprintln!(
+                "  Unpacking {bold}{package_name}{bold:#} package...",
+                bold = Style::new().bold()
+            );
+
+            let destination_folder = self.destination.join(package_name);
+
+            let pkg_config = PkgConfig::from_package_folder(&destination_folder)?;
+
+            let apply_config = combine_apply_config_from_args_and_config(
+                (
+                    self.apply_features.as_ref(),
+                    self.apply_post_features.as_ref(),
+                ),
+                pkg_config.as_ref().map(|x| x.apply()),
+            )
+            .unwrap_or_default();
+
+            let extract_config = PackageToFolderExtractorConfig::default()
+                .with_files_already_exists_in_folder_strategy(
+                    FilesAlreadyExistsInFolderStrategy::SmartMerge,
+                )
+                .print_merge_log(true)
+                .with_transform(apply_config.apply().build_combined_transform());
+
+            extract_single_zip_package_to_folder(
+                std::io::Cursor::new(&package_data),
+                &destination_folder,
+                Some(package_name),
+                &extract_config,
+            )?;
+
+            apply_config
+                .apply_post()
+                .build_combined_transform()
+                .transform(&destination_folder, false, stderr())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Creates or updates `workspace.crtcli.toml` in the snapshot folder.
+///
+/// Existing file content is preserved, only missing packages are appended.
+/// Packages listed in the file but absent in the snapshot are reported as a warning.
+fn update_workspace_config(
+    destination: &Path,
+    package_names: &[String],
+) -> Result<(), SnapshotCommandError> {
+    let config_path = destination.join(WORKSPACE_CONFIG_FILENAME);
+
+    let (mut content, existing_paths) = match config_path.exists() {
+        true => {
+            let config = WorkspaceConfig::from_filepath(&config_path)?;
+
+            let content = std::fs::read_to_string(&config_path).map_err(|err| {
+                SnapshotCommandError::WriteWorkspaceConfig(config_path.clone(), err)
+            })?;
+
+            let paths = config
+                .packages()
+                .iter()
+                .map(|p| p.path().to_path_buf())
+                .collect::<Vec<_>>();
+
+            (content, paths)
+        }
+        false => (String::new(), vec![]),
+    };
+
+    let missing = package_names
+        .iter()
+        .filter(|name| {
+            !existing_paths
+                .iter()
+                .any(|p| is_same_path(p, Path::new(name)))
+        })
+        .collect::<Vec<_>>();
+
+    let stale = existing_paths
+        .iter()
+        .filter(|p| {
+            !package_names
+                .iter()
+                .any(|name| is_same_path(p, Path::new(name)))
+        })
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>();
+
+    if !stale.is_empty() {
+        eprintln!(
+            "{style}warning: packages from {WORKSPACE_CONFIG_FILENAME} are not included in the snapshot: {}{style:#}",
+            stale.join(", "),
+            style = Style::new()
+                .fg_color(Some(Color::Ansi(AnsiColor::BrightYellow)))
+                .dimmed()
+        );
+    }
+
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    for name in missing {
+        if !content.is_empty() && !content.ends_with("\n\n") {
+            content.push_str(if content.ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            });
+        }
+
+        content.push_str(&format!(
+            "[[packages]]\npath = {}\n",
+            toml::Value::String(name.clone())
+        ));
+    }
+
+    std::fs::write(&config_path, content)
+        .map_err(|err| SnapshotCommandError::WriteWorkspaceConfig(config_path, err))
+}
+
+/// Compares workspace package paths ignoring `.` components, so `./UsrPkg` equals `UsrPkg`.
+fn is_same_path(left: &Path, right: &Path) -> bool {
+    let normalize = |path| Path::components(path).filter(|c| !matches!(c, Component::CurDir));
+
+    normalize(left).eq(normalize(right))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::TempDir;
+
+    #[test]
+    fn does_not_duplicate_existing_workspace_packages() {
+        let dir = TempDir::create("snapshot");
+
+        let config_path = dir.join(WORKSPACE_CONFIG_FILENAME);
+        std::fs::write(&config_path, "[[packages]]\npath = \"./UsrPkg\"\n").unwrap();
+
+        update_workspace_config(&dir, &["UsrPkg".to_owned(), "UsrOther".to_owned()]).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&config_path).unwrap(),
+            "[[packages]]\npath = \"./UsrPkg\"\n\n[[packages]]\npath = \"UsrOther\"\n"
+        );
+    }
+}
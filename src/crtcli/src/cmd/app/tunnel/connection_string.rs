@@ -0,0 +1,407 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+static ORACLE_DESCRIPTOR_HOST_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)(\(\s*HOST\s*=\s*)([^)\s]+)(\s*\))").unwrap());
+
+static ORACLE_DESCRIPTOR_PORT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)(\(\s*PORT\s*=\s*)(\d+)(\s*\))").unwrap());
+
+/// Database or cache server address found in the Creatio connection string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionStringEndpoint {
+    pub host: String,
+    pub port: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionStringKind {
+    /// `Data Source=host[\instance][,port];Initial Catalog=...`
+    MsSql,
+
+    /// `Server=host;Port=5432;Database=...`
+    Postgres,
+
+    /// `Data Source=(DESCRIPTION=(ADDRESS=(HOST=host)(PORT=1521))...)` or `Data Source=host:1521/service`
+    Oracle,
+
+    /// `host=host;db=0;port=6379`
+    Redis,
+}
+
+impl ConnectionStringKind {
+    fn default_port(&self) -> u16 {
+        match self {
+            ConnectionStringKind::MsSql => 1433,
+            ConnectionStringKind::Postgres => 5432,
+            ConnectionStringKind::Oracle => 1521,
+            ConnectionStringKind::Redis => 6379,
+        }
+    }
+}
+
+const HOST_KEYS: &[&str] = &[
+    "data source",
+    "datasource",
+    "server",
+    "host",
+    "address",
+    "addr",
+    "network address",
+];
+
+const PORT_KEY: &str = "port";
+
+/// Creatio connection string in the `key=value;key=value` format with the original parts preserved.
+#[derive(Debug, Clone)]
+pub struct ConnectionString {
+    parts: Vec<ConnectionStringPart>,
+}
+
+#[derive(Debug, Clone)]
+struct ConnectionStringPart {
+    key: String,
+    value: String,
+}
+
+impl ConnectionString {
+    pub fn parse(value: &str) -> Self {
+        let parts = value
+            .split(';')
+            .filter(|part| !part.trim().is_empty())
+            .map(|part| match part.split_once('=') {
+                Some((key, value)) => ConnectionStringPart {
+                    key: key.to_owned(),
+                    value: value.to_owned(),
+                },
+                None => ConnectionStringPart {
+                    key: part.to_owned(),
+                    value: String::new(),
+                },
+            })
+            .collect();
+
+        Self { parts }
+    }
+
+    fn find(&self, key: &str) -> Option<&ConnectionStringPart> {
+        self.parts
+            .iter()
+            .find(|part| part.key.trim().eq_ignore_ascii_case(key))
+    }
+
+    fn find_host_part(&self) -> Option<&ConnectionStringPart> {
+        HOST_KEYS.iter().find_map(|key| self.find(key))
+    }
+
+    /// Returns the trimmed value of the parameter, keys are case-insensitive.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.find(key).map(|part| part.value.trim())
+    }
+
+    pub fn kind(&self) -> ConnectionStringKind {
+        let has = |key: &str| self.find(key).is_some();
+
+        let data_source = self
+            .find("data source")
+            .or_else(|| self.find("datasource"))
+            .map(|part| part.value.trim());
+
+        // Oracle connection strings have no database name, so Initial Catalog means MSSQL
+        if has("initial catalog") {
+            ConnectionStringKind::MsSql
+        } else if data_source.is_some_and(is_oracle_data_source) {
+            ConnectionStringKind::Oracle
+        } else if has("data source") || has("datasource") {
+            ConnectionStringKind::MsSql
+        } else if has("db") || (has("host") && !has("database")) {
+            ConnectionStringKind::Redis
+        } else {
+            ConnectionStringKind::Postgres
+        }
+    }
+
+    pub fn endpoint(&self) -> Option<ConnectionStringEndpoint> {
+        let kind = self.kind();
+        let host_value = self.find_host_part()?.value.trim();
+
+        let (host, inline_port) = match kind {
+            ConnectionStringKind::MsSql => parse_mssql_data_source(host_value),
+            ConnectionStringKind::Oracle => parse_oracle_data_source(host_value)?,
+            _ => (host_value.to_owned(), None),
+        };
+
+        if host.is_empty() {
+            return None;
+        }
+
+        let port = match inline_port {
+            Some(port) => port,
+            None => match self.find(PORT_KEY) {
+                Some(part) => part.value.trim().parse().ok()?,
+                None => kind.default_port(),
+            },
+        };
+
+        Some(ConnectionStringEndpoint { host, port })
+    }
+
+    /// Replaces the server address with the specified one, keeping other parameters as is.
+    pub fn with_endpoint(&self, host: &str, port: u16) -> Self {
+        let kind = self.kind();
+        let host_key = self.find_host_part().map(|part| part.key.clone());
+        let has_port_key = self.find(PORT_KEY).is_some();
+
+        let mut parts = self.parts.clone();
+
+        for part in &mut parts {
+            if Some(&part.key) == host_key.as_ref() {
+                part.value = match kind {
+                    ConnectionStringKind::MsSql => format!("{host},{port}"),
+                    ConnectionStringKind::Oracle => {
+                        rewrite_oracle_data_source(&part.value, host, port)
+                    }
+                    _ => host.to_owned(),
+                };
+            } else if part.key.trim().eq_ignore_ascii_case(PORT_KEY) {
+                part.value = port.to_string();
+            }
+        }
+
+        if !matches!(
+            kind,
+            ConnectionStringKind::MsSql | ConnectionStringKind::Oracle
+        ) && !has_port_key
+        {
+            parts.push(ConnectionStringPart {
+                key: "port".to_owned(),
+                value: port.to_string(),
+            });
+        }
+
+        Self { parts }
+    }
+}
+
+impl std::fmt::Display for ConnectionString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for part in &self.parts {
+            write!(f, "{}={};", part.key, part.value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses MSSQL `[tcp:]host[\instance][,port]` data source value.
+fn parse_mssql_data_source(value: &str) -> (String, Option<u16>) {
+    let value = value
+        .strip_prefix("tcp:")
+        .or_else(|| value.strip_prefix("TCP:"))
+        .unwrap_or(value);
+
+    let (address, port) = match value.split_once(',') {
+        Some((address, port)) => (address, port.trim().parse().ok()),
+        None => (value, None),
+    };
+
+    let host = address
+        .split_once('\\')
+        .map_or(address, |(host, _instance)| host);
+
+    (host.trim().to_owned(), port)
+}
+
+/// Oracle data source is either a connect descriptor `(DESCRIPTION=...)`
+/// or an Easy Connect string `[//]host[:port][/service]`.
+fn is_oracle_data_source(value: &str) -> bool {
+    let is_mssql_protocol_prefix = ["tcp:", "np:", "lpc:", "admin:"]
+        .iter()
+        .any(|prefix| value.to_ascii_lowercase().starts_with(prefix));
+
+    is_oracle_connect_descriptor(value)
+        || value.starts_with("//")
+        || (!is_mssql_protocol_prefix && (value.contains('/') || value.contains(':')))
+}
+
+fn is_oracle_connect_descriptor(value: &str) -> bool {
+    value
+        .strip_prefix('(')
+        .map(str::trim_start)
+        .and_then(|rest| rest.get(..11))
+        .is_some_and(|keyword| keyword.eq_ignore_ascii_case("description"))
+}
+
+fn parse_oracle_data_source(value: &str) -> Option<(String, Option<u16>)> {
+    if is_oracle_connect_descriptor(value) {
+        let host = ORACLE_DESCRIPTOR_HOST_REGEX.captures(value)?[2].to_owned();
+        let port = ORACLE_DESCRIPTOR_PORT_REGEX
+            .captures(value)
+            .and_then(|captures| captures[2].parse().ok());
+
+        return Some((host, port));
+    }
+
+    let address = value.trim_start_matches("//");
+    let address = address
+        .split_once('/')
+        .map_or(address, |(address, _)| address);
+
+    Some(match address.rsplit_once(':') {
+        Some((host, port)) => (host.to_owned(), Some(port.parse().ok()?)),
+        None => (address.to_owned(), None),
+    })
+}
+
+fn rewrite_oracle_data_source(value: &str, host: &str, port: u16) -> String {
+    let trimmed = value.trim();
+
+    if is_oracle_connect_descriptor(trimmed) {
+        let value = ORACLE_DESCRIPTOR_HOST_REGEX.replace(trimmed, format!("${{1}}{host}${{3}}"));
+
+        return match ORACLE_DESCRIPTOR_PORT_REGEX.is_match(&value) {
+            true => ORACLE_DESCRIPTOR_PORT_REGEX
+                .replace(&value, format!("${{1}}{port}${{3}}"))
+                .into_owned(),
+            false => ORACLE_DESCRIPTOR_HOST_REGEX
+                .replace(&value, format!("${{1}}{host}${{3}}(PORT={port})"))
+                .into_owned(),
+        };
+    }
+
+    let (prefix, address) = match trimmed.strip_prefix("//") {
+        Some(address) => ("//", address),
+        None => ("", trimmed),
+    };
+
+    let service = address.split_once('/').map(|(_, service)| service);
+
+    match service {
+        Some(service) => format!("{prefix}{host}:{port}/{service}"),
+        None => format!("{prefix}{host}:{port}"),
+    }
+}
+
+/// Returns the environment variable name for the connection string, e.g. `db` -> `CRTCLI_CS_DB`.
+pub fn connection_string_env_var_name(name: &str) -> String {
+    let name = name
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c.to_ascii_uppercase(),
+            false => '_',
+        })
+        .collect::<String>();
+
+    format!("CRTCLI_CS_{name}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoint(host: &str, port: u16) -> Option<ConnectionStringEndpoint> {
+        Some(ConnectionStringEndpoint {
+            host: host.to_owned(),
+            port,
+        })
+    }
+
+    #[test]
+    fn parses_and_rewrites_postgres_connection_string() {
+        let cs = ConnectionString::parse(
+            "Server=db;Port=5432;Database=creatio;User ID=postgres;password=postgres;Timeout=500;",
+        );
+
+        assert_eq!(cs.kind(), ConnectionStringKind::Postgres);
+        assert_eq!(cs.endpoint(), endpoint("db", 5432));
+        assert_eq!(
+            cs.with_endpoint("127.0.0.1", 2222).to_string(),
+            "Server=127.0.0.1;Port=2222;Database=creatio;User ID=postgres;password=postgres;Timeout=500;"
+        );
+    }
+
+    #[test]
+    fn parses_and_rewrites_mssql_connection_string() {
+        let cs = ConnectionString::parse(
+            r"Data Source=tcp:mssql\SQLEXPRESS,1434;Initial Catalog=creatio;User ID=sa;Password=p",
+        );
+
+        assert_eq!(cs.kind(), ConnectionStringKind::MsSql);
+        assert_eq!(cs.endpoint(), endpoint("mssql", 1434));
+        assert_eq!(
+            cs.with_endpoint("127.0.0.1", 51433).to_string(),
+            "Data Source=127.0.0.1,51433;Initial Catalog=creatio;User ID=sa;Password=p;"
+        );
+
+        assert_eq!(
+            ConnectionString::parse("Data Source=mssql;Initial Catalog=creatio").endpoint(),
+            endpoint("mssql", 1433)
+        );
+    }
+
+    #[test]
+    fn parses_and_rewrites_redis_connection_string() {
+        let cs = ConnectionString::parse("host=redis;db=0");
+
+        assert_eq!(cs.kind(), ConnectionStringKind::Redis);
+        assert_eq!(cs.endpoint(), endpoint("redis", 6379));
+        assert_eq!(
+            cs.with_endpoint("127.0.0.1", 16379).to_string(),
+            "host=127.0.0.1;db=0;port=16379;"
+        );
+    }
+
+    #[test]
+    fn parses_and_rewrites_oracle_connection_string() {
+        let cs = ConnectionString::parse(
+            "Data Source=(DESCRIPTION=(ADDRESS=(PROTOCOL=TCP)(HOST=oracle)(PORT=1522))(CONNECT_DATA=(SERVICE_NAME=creatio)));User Id=crt;Password=p",
+        );
+
+        assert_eq!(cs.kind(), ConnectionStringKind::Oracle);
+        assert_eq!(cs.endpoint(), endpoint("oracle", 1522));
+        assert_eq!(
+            cs.with_endpoint("127.0.0.1", 11521).to_string(),
+            "Data Source=(DESCRIPTION=(ADDRESS=(PROTOCOL=TCP)(HOST=127.0.0.1)(PORT=11521))(CONNECT_DATA=(SERVICE_NAME=creatio)));User Id=crt;Password=p;"
+        );
+
+        let cs = ConnectionString::parse("Data Source=oracle/creatio;User Id=crt;Password=p");
+
+        assert_eq!(cs.kind(), ConnectionStringKind::Oracle);
+        assert_eq!(cs.endpoint(), endpoint("oracle", 1521));
+        assert_eq!(
+            cs.with_endpoint("127.0.0.1", 11521).to_string(),
+            "Data Source=127.0.0.1:11521/creatio;User Id=crt;Password=p;"
+        );
+    }
+
+    #[test]
+    fn detects_mssql_data_source_in_parentheses() {
+        let kind = |value: &str| ConnectionString::parse(value).kind();
+
+        assert_eq!(
+            kind(r"Data Source=(localdb)\MSSQLLocalDB;Initial Catalog=creatio"),
+            ConnectionStringKind::MsSql
+        );
+        assert_eq!(
+            kind("Data Source=(local);Integrated Security=true"),
+            ConnectionStringKind::MsSql
+        );
+        assert_eq!(
+            kind("Data Source=mssql:1433/x;Initial Catalog=creatio"),
+            ConnectionStringKind::MsSql
+        );
+        assert_eq!(
+            kind("Data Source=( description=(ADDRESS=(HOST=oracle)));User Id=crt"),
+            ConnectionStringKind::Oracle
+        );
+    }
+
+    #[test]
+    fn connection_string_env_var_name_is_sanitized() {
+        assert_eq!(connection_string_env_var_name("db"), "CRTCLI_CS_DB");
+        assert_eq!(
+            connection_string_env_var_name("redis-cache.1"),
+            "CRTCLI_CS_REDIS_CACHE_1"
+        );
+    }
+}
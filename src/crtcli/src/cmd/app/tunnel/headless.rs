@@ -0,0 +1,194 @@
+use clap::ValueEnum;
+use serde_json::json;
+use std::net::SocketAddr;
+use std::time::Duration;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, ValueEnum)]
+pub enum TunnelLogFormat {
+    /// Human-readable lines printed to stderr
+    #[default]
+    Plain,
+
+    /// One json object per line printed to stdout
+    Json,
+}
+
+/// Event of the tunneling session logged in headless mode.
+pub enum TunnelEvent<'a> {
+    Listening {
+        listener: SocketAddr,
+        target: &'a str,
+    },
+    Open {
+        listener: SocketAddr,
+        client: SocketAddr,
+        target: &'a str,
+    },
+    Close {
+        listener: SocketAddr,
+        client: SocketAddr,
+        target: Option<&'a str>,
+        tx_bytes: u64,
+        rx_bytes: u64,
+        duration: Duration,
+        error: Option<&'a str>,
+    },
+    Reconnect {
+        listener: SocketAddr,
+        target: &'a str,
+        attempt: u32,
+        delay: Duration,
+        error: &'a str,
+    },
+    SessionCheckFailed {
+        error: &'a str,
+    },
+    Stopped {
+        reason: &'a str,
+        duration: Duration,
+    },
+}
+
+impl TunnelLogFormat {
+    pub fn log(&self, event: TunnelEvent) {
+        let time = OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .expect("failed to format current time");
+
+        match self {
+            TunnelLogFormat::Plain => eprintln!("{time} {}", format_plain(&event)),
+            TunnelLogFormat::Json => {
+                let mut value = format_json(&event);
+                value["time"] = json!(time);
+
+                println!("{value}");
+            }
+        }
+    }
+}
+
+fn format_plain(event: &TunnelEvent) -> String {
+    match event {
+        TunnelEvent::Listening { listener, target } => {
+            format!("listening {listener} -> {target}")
+        }
+        TunnelEvent::Open {
+            listener,
+            client,
+            target,
+        } => format!("open {client} via {listener} -> {target}"),
+        TunnelEvent::Close {
+            listener,
+            client,
+            target,
+            tx_bytes,
+            rx_bytes,
+            duration,
+            error,
+        } => format!(
+            "close {client} via {listener} -> {target} tx={tx_bytes} rx={rx_bytes} duration={duration}ms{error}",
+            target = target.unwrap_or("-"),
+            duration = duration.as_millis(),
+            error = error.map(|e| format!(" error=\"{e}\"")).unwrap_or_default(),
+        ),
+        TunnelEvent::Reconnect {
+            listener,
+            target,
+            attempt,
+            delay,
+            error,
+        } => format!(
+            "reconnect via {listener} -> {target} attempt={attempt} delay={delay}ms error=\"{error}\"",
+            delay = delay.as_millis(),
+        ),
+        TunnelEvent::SessionCheckFailed { error } => {
+            format!("session check failed error=\"{error}\"")
+        }
+        TunnelEvent::Stopped { reason, duration } => format!(
+            "stopped reason={reason} duration={duration}ms",
+            duration = duration.as_millis()
+        ),
+    }
+}
+
+fn format_json(event: &TunnelEvent) -> serde_json::Value {
+    match event {
+        TunnelEvent::Listening { listener, target } => json!({
+            "event": "listening",
+            "listener": listener.to_string(),
+            "target": target,
+        }),
+        TunnelEvent::Open {
+            listener,
+            client,
+            target,
+        } => json!({
+            "event": "open",
+            "listener": listener.to_string(),
+            "client": client.to_string(),
+            "target": target,
+        }),
+        TunnelEvent::Close {
+            listener,
+            client,
+            target,
+            tx_bytes,
+            rx_bytes,
+            duration,
+            error,
+        } => json!({
+            "event": "close",
+            "listener": listener.to_string(),
+            "client": client.to_string(),
+            "target": target,
+            "tx_bytes": tx_bytes,
+            "rx_bytes": rx_bytes,
+            "duration_ms": duration.as_millis() as u64,
+            "error": error,
+        }),
+        TunnelEvent::Reconnect {
+            listener,
+            target,
+            attempt,
+            delay,
+            error,
+        } => json!({
+            "event": "reconnect",
+            "listener": listener.to_string(),
+            "target": target,
+            "attempt": attempt,
+            "delay_ms": delay.as_millis() as u64,
+            "error": error,
+        }),
+        TunnelEvent::SessionCheckFailed { error } => json!({
+            "event": "session_check_failed",
+            "error": error,
+        }),
+        TunnelEvent::Stopped { reason, duration } => json!({
+            "event": "stopped",
+            "reason": reason,
+            "duration_ms": duration.as_millis() as u64,
+        }),
+    }
+}
+
+/// Waits for Ctrl+C or SIGTERM (on Unix) and returns the name of the received signal.
+pub async fn wait_for_shutdown_signal() -> &'static str {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        if let Ok(mut sigterm) = signal(SignalKind::terminate()) {
+            return tokio::select! {
+                _ = tokio::signal::ctrl_c() => "SIGINT",
+                _ = sigterm.recv() => "SIGTERM",
+            };
+        }
+    }
+
+    let _ = tokio::signal::ctrl_c().await;
+
+    "SIGINT"
+}
@@ -0,0 +1,1291 @@
+use crate::CommandHandledError;
+use crate::app::{CrtClient, CrtClientError};
+use crate::cmd::app::AppCommand;
+use crate::cmd::cli::{CommandDynError, CommandResult};
+use crate::cmd::utils::{humanize_bytes, humanize_duration_time_precise};
+use crate::utils::forward_mapping::{ForwardMappingArg, ForwardMappingArgParsingError};
+use anstyle::{AnsiColor, Color, Style};
+use clap::Args;
+use clap::builder::{ValueParser, ValueParserFactory};
+use crossterm::event::EventStream;
+use crossterm::{QueueableCommand, cursor, event, style, terminal};
+use futures::stream::{SplitSink, SplitStream};
+use futures::{SinkExt, StreamExt};
+use std::collections::HashMap;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use tokio_util::bytes::Bytes;
+use tokio_util::sync::CancellationToken;
+
+pub mod connection_string;
+mod headless;
+mod socks5;
+
+use connection_string::{
+    ConnectionString, ConnectionStringEndpoint, connection_string_env_var_name,
+};
+use headless::{TunnelEvent, TunnelLogFormat};
+
+#[derive(Args, Debug)]
+pub struct TunnelCommand {
+    /// Names of the tunnels to start from `[apps.<app>.tunnels]` section of .crtcli.toml
+    #[arg(value_name = "TUNNELS", value_hint = clap::ValueHint::Other)]
+    tunnel_names: Vec<String>,
+
+    /// Print defined connection strings in the Creatio configuration
+    #[arg(long)]
+    connection_strings: bool,
+
+    /// Local port forwarding rule(s) in the format [bind_address:]port:host:host_port
+    #[arg(short = 'L', value_name = "[bind_address:]port:host:host_port")]
+    local_forward: Vec<ForwardMappingArg>,
+
+    /// Dynamic port forwarding rule(s) in the format [bind_address:]port, starts a local SOCKS5 proxy to reach any host available from Creatio server
+    #[arg(short = 'D', value_name = "[bind_address:]port")]
+    dynamic_forward: Vec<DynamicForwardArg>,
+
+    /// Comma-separated names of Creatio connection strings to forward automatically to free local ports, e.g. "db,redis"
+    ///
+    /// MSSQL, PostgreSQL, Oracle and Redis connection strings are supported, the rewritten local connection strings are printed.
+    #[arg(long, value_delimiter = ',', value_name = "NAMES", value_hint = clap::ValueHint::Other)]
+    auto: Vec<String>,
+
+    /// Run without the terminal dashboard and log connection events instead, e.g. in CI or containers
+    ///
+    /// In this mode the session is checked periodically and re-authenticated when expired,
+    /// failed tunnel connections are retried with backoff, and the command exits on Ctrl+C or SIGTERM.
+    #[arg(long)]
+    headless: bool,
+
+    /// Format of the connection events log in headless mode
+    #[arg(long, value_enum, default_value_t, requires = "headless")]
+    log_format: TunnelLogFormat,
+
+    /// Run the command after `--` with the forwards attached, then stop the tunnel and exit with the command exit code
+    ///
+    /// Use port 0 to pick a free local port. Creatio connection strings pointing to forwarded hosts are rewritten
+    /// to local addresses and passed to the command as `CRTCLI_CS_<NAME>` environment variables, e.g. `CRTCLI_CS_DB`.
+    #[arg(long, requires = "command")]
+    exec: bool,
+
+    /// Command with arguments to run in `--exec` mode
+    #[arg(last = true, value_name = "COMMAND", requires = "exec")]
+    command: Vec<String>,
+
+    #[arg(skip)]
+    tunnels: HashMap<String, String>,
+}
+
+#[derive(Error, Debug)]
+enum TunnelCommandError {
+    #[error("binding tcp listener to {0} failed: {1}")]
+    TcpListenerBind(SocketAddr, #[source] std::io::Error),
+
+    #[error(
+        "sorry, seems like your terminal is not supported (use --headless to run without dashboard): {0}"
+    )]
+    TerminalNotSupported(#[source] std::io::Error),
+
+    #[error("sorry, failed to validate your Creatio permissions: {0}")]
+    TunnelingPermission(String),
+
+    #[error("failed to read local address of tcp listener: {0}")]
+    TcpListenerLocalAddr(#[source] std::io::Error),
+
+    #[error("failed to run command {0}: {1}")]
+    ExecSpawn(String, #[source] std::io::Error),
+
+    #[error("connection string {0} is not defined in Creatio, available: {1}")]
+    AutoConnectionStringNotFound(String, String),
+
+    #[error("failed to find server address in connection string {0}")]
+    AutoConnectionStringUnsupported(String),
+
+    #[error("tunnel {0} is not defined in .crtcli.toml for this app, available: {1}")]
+    TunnelNotFound(String, String),
+
+    #[error("invalid tunnel {0} rule: {1}")]
+    TunnelParse(String, #[source] ForwardMappingArgParsingError),
+}
+
+/// Local address of the SOCKS5 proxy, destination host is requested by the proxy client.
+#[derive(Debug, Clone)]
+struct DynamicForwardArg {
+    bind_address: SocketAddr,
+}
+
+impl TryFrom<&str> for DynamicForwardArg {
+    type Error = ForwardMappingArgParsingError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (bind_address, port_str) = match value.strip_prefix('[') {
+            Some(rest) => rest
+                .split_once("]:")
+                .ok_or(ForwardMappingArgParsingError::Format)?,
+            None => match value.rsplit_once(':') {
+                Some(("", port_str)) => ("0.0.0.0", port_str),
+                Some((bind_address, port_str)) => (bind_address, port_str),
+                None => ("127.0.0.1", value),
+            },
+        };
+
+        let port = port_str
+            .parse::<u16>()
+            .map_err(|_| ForwardMappingArgParsingError::Port(port_str.to_string()))?;
+
+        let bind_address = SocketAddr::new(
+            bind_address
+                .parse()
+                .map_err(|_| ForwardMappingArgParsingError::IpAddr(bind_address.to_string()))?,
+            port,
+        );
+
+        Ok(DynamicForwardArg { bind_address })
+    }
+}
+
+impl ValueParserFactory for DynamicForwardArg {
+    type Parser = ValueParser;
+
+    fn value_parser() -> Self::Parser {
+        ValueParser::new(|s: &str| DynamicForwardArg::try_from(s).map_err(|e| e.to_string()))
+    }
+}
+
+#[derive(Debug, Clone)]
+enum ListenerKind {
+    Local(ForwardMappingArg),
+    Dynamic(DynamicForwardArg),
+}
+
+impl ListenerKind {
+    fn bind_address(&self) -> SocketAddr {
+        match self {
+            ListenerKind::Local(forward_mapping) => forward_mapping.bind_address,
+            ListenerKind::Dynamic(dynamic_forward) => dynamic_forward.bind_address,
+        }
+    }
+
+    fn target_display(&self) -> String {
+        match self {
+            ListenerKind::Local(forward_mapping) => {
+                format!("{}:{}", forward_mapping.host, forward_mapping.host_port)
+            }
+            ListenerKind::Dynamic(_) => "SOCKS5 (dynamic)".to_owned(),
+        }
+    }
+
+    fn with_bind_address(self, bind_address: SocketAddr) -> Self {
+        match self {
+            ListenerKind::Local(forward_mapping) => ListenerKind::Local(ForwardMappingArg {
+                bind_address,
+                ..forward_mapping
+            }),
+            ListenerKind::Dynamic(_) => ListenerKind::Dynamic(DynamicForwardArg { bind_address }),
+        }
+    }
+}
+
+impl TunnelCommand {
+    pub fn with_tunnels(self, tunnels: HashMap<String, String>) -> Self {
+        Self { tunnels, ..self }
+    }
+
+    fn get_named_forward_mappings(&self) -> Result<Vec<ForwardMappingArg>, TunnelCommandError> {
+        self.tunnel_names
+            .iter()
+            .map(|name| {
+                let tunnel = self.tunnels.get(name).ok_or_else(|| {
+                    let mut available = self.tunnels.keys().map(String::as_str).collect::<Vec<_>>();
+                    available.sort();
+
+                    TunnelCommandError::TunnelNotFound(name.clone(), available.join(", "))
+                })?;
+
+                ForwardMappingArg::try_from(tunnel.as_str())
+                    .map_err(|err| TunnelCommandError::TunnelParse(name.clone(), err))
+            })
+            .collect()
+    }
+}
+
+impl AppCommand for TunnelCommand {
+    async fn run(&self, client: Arc<CrtClient>) -> CommandResult {
+        ensure_has_creatio_permissions(&client).await?;
+
+        if self.connection_strings {
+            return print_connection_strings(&client).await;
+        }
+
+        if self.local_forward.is_empty()
+            && self.dynamic_forward.is_empty()
+            && self.auto.is_empty()
+            && self.tunnel_names.is_empty()
+        {
+            print_forward_mappings_not_specified();
+            return Err(CommandHandledError(ExitCode::FAILURE).into());
+        }
+
+        let mut listener_contexts = vec![];
+        let mut join_handles = vec![];
+
+        let auto_connection_strings = match self.auto.is_empty() {
+            true => indexmap::IndexMap::new(),
+            false => get_auto_connection_strings(&client, &self.auto).await?,
+        };
+
+        let auto_forwards = get_auto_forward_mappings(&auto_connection_strings)?;
+        let named_forwards = self.get_named_forward_mappings()?;
+
+        let listener_kinds = self
+            .local_forward
+            .iter()
+            .cloned()
+            .chain(named_forwards)
+            .chain(auto_forwards)
+            .map(ListenerKind::Local)
+            .chain(
+                self.dynamic_forward
+                    .iter()
+                    .cloned()
+                    .map(ListenerKind::Dynamic),
+            );
+
+        let log_format = self.headless.then_some(self.log_format);
+
+        for listener_kind in listener_kinds {
+            let (listener_context, join_handle) =
+                start_tcp_listener_forwarding(client.clone(), listener_kind, log_format).await?;
+
+            listener_context.log(TunnelEvent::Listening {
+                listener: listener_context.kind.bind_address(),
+                target: &listener_context.kind.target_display(),
+            });
+
+            listener_contexts.push(listener_context);
+            join_handles.push(join_handle);
+        }
+
+        if !auto_connection_strings.is_empty() {
+            print_auto_connection_strings(
+                &auto_connection_strings,
+                &listener_contexts,
+                log_format == Some(TunnelLogFormat::Json),
+            );
+        }
+
+        let start_time = Instant::now();
+
+        if self.exec {
+            let result = run_exec(&client, &listener_contexts, &self.command).await;
+
+            join_handles.iter().for_each(JoinHandle::abort);
+
+            return result;
+        }
+
+        if let Some(log_format) = log_format {
+            let session_check_handle = start_session_check(Arc::clone(&client), log_format);
+
+            let reason = headless::wait_for_shutdown_signal().await;
+
+            session_check_handle.abort();
+            join_handles.iter().for_each(JoinHandle::abort);
+
+            log_format.log(TunnelEvent::Stopped {
+                reason,
+                duration: start_time.elapsed(),
+            });
+
+            return Ok(());
+        }
+
+        let tunneling_dashboard_result =
+            show_tunneling_dashboard(Arc::clone(&client), listener_contexts)
+                .await
+                .map_err(TunnelCommandError::TerminalNotSupported);
+
+        hide_tunneling_dashboard();
+
+        tunneling_dashboard_result?;
+
+        eprintln!(
+            "Tunneling session via {url} stopped — {time}",
+            url = client.base_url(),
+            time = humanize_duration_time_precise(start_time.elapsed()),
+        );
+
+        return Ok(());
+
+        async fn ensure_has_creatio_permissions(client: &CrtClient) -> CommandResult {
+            let response = client.crtcli_tunneling_service().get_status().await?;
+
+            if response.allowed {
+                Ok(())
+            } else {
+                Err(
+                    TunnelCommandError::TunnelingPermission(
+                        response
+                            .error
+                            .expect("CheckPermissionsResponse.Error is null, but CheckPermissionsResponse.Result is true")
+                            .message
+                    )
+                    .into()
+                )
+            }
+        }
+
+        async fn print_connection_strings(client: &CrtClient) -> CommandResult {
+            let connection_strings = client
+                .crtcli_tunneling_service()
+                .get_connection_strings()
+                .await?;
+
+            eprintln!(
+                "{bold}Listing connection strings at {base_url}:{bold:#}",
+                base_url = client.base_url(),
+                bold = Style::new().bold()
+            );
+
+            for (i, (key, value)) in connection_strings.iter().enumerate() {
+                println!(
+                    "{underline}{key}{underline:#}",
+                    underline = Style::new().underline()
+                );
+                println!("{italic}{value}{italic:#}", italic = Style::new().italic());
+
+                if i < connection_strings.len() - 1 {
+                    println!();
+                }
+            }
+
+            Ok(())
+        }
+
+        async fn get_auto_connection_strings(
+            client: &CrtClient,
+            names: &[String],
+        ) -> Result<indexmap::IndexMap<String, String>, CommandDynError> {
+            let connection_strings = client
+                .crtcli_tunneling_service()
+                .get_connection_strings()
+                .await?;
+
+            names
+                .iter()
+                .map(|name| {
+                    connection_strings
+                        .iter()
+                        .find(|(key, _)| key.eq_ignore_ascii_case(name.trim()))
+                        .map(|(key, value)| (key.clone(), value.clone()))
+                        .ok_or_else(|| {
+                            TunnelCommandError::AutoConnectionStringNotFound(
+                                name.clone(),
+                                connection_strings
+                                    .keys()
+                                    .map(String::as_str)
+                                    .collect::<Vec<_>>()
+                                    .join(", "),
+                            )
+                            .into()
+                        })
+                })
+                .collect()
+        }
+
+        /// Creates forward to a free local port for each unique server address of the connection strings.
+        fn get_auto_forward_mappings(
+            connection_strings: &indexmap::IndexMap<String, String>,
+        ) -> Result<Vec<ForwardMappingArg>, TunnelCommandError> {
+            let mut forward_mappings: Vec<ForwardMappingArg> = vec![];
+
+            for (name, value) in connection_strings {
+                let endpoint = ConnectionString::parse(value).endpoint().ok_or_else(|| {
+                    TunnelCommandError::AutoConnectionStringUnsupported(name.clone())
+                })?;
+
+                let exists = forward_mappings.iter().any(|mapping| {
+                    mapping.host.eq_ignore_ascii_case(&endpoint.host)
+                        && mapping.host_port == endpoint.port
+                });
+
+                if !exists {
+                    forward_mappings.push(ForwardMappingArg {
+                        bind_address: SocketAddr::new(std::net::Ipv4Addr::LOCALHOST.into(), 0),
+                        host: endpoint.host,
+                        host_port: endpoint.port,
+                    });
+                }
+            }
+
+            Ok(forward_mappings)
+        }
+
+        /// Prints local connection strings to stdout, or to stderr if stdout is used by json log.
+        fn print_auto_connection_strings(
+            connection_strings: &indexmap::IndexMap<String, String>,
+            listener_contexts: &[Arc<ListenerContext>],
+            to_stderr: bool,
+        ) {
+            let underline = Style::new().underline();
+            let italic = Style::new().italic();
+
+            for (name, local_connection_string) in
+                rewrite_connection_strings_to_local_forwards(connection_strings, listener_contexts)
+            {
+                let output = format!(
+                    "{underline}{name}{underline:#}\n{italic}{local_connection_string}{italic:#}"
+                );
+
+                match to_stderr {
+                    true => eprintln!("{output}"),
+                    false => println!("{output}"),
+                }
+
+                let endpoint = ConnectionString::parse(&connection_strings[&name]).endpoint();
+
+                if let Some(listener_context) = endpoint
+                    .and_then(|endpoint| find_local_forward_context(listener_contexts, &endpoint))
+                {
+                    listener_context
+                        .local_connection_strings
+                        .lock()
+                        .unwrap()
+                        .push((name, local_connection_string.to_string()));
+                }
+            }
+        }
+
+        fn print_forward_mappings_not_specified() {
+            let bold = Style::new().bold();
+            let bold_underline = Style::new().bold().underline();
+
+            eprintln!(
+                "{red_bold}error:{red_bold:#} the following required arguments were not provided:",
+                red_bold = Style::new()
+                    .fg_color(Some(Color::Ansi(AnsiColor::Red)))
+                    .bold(),
+            );
+
+            eprintln!(
+                "  {green}-L <[bind_address:]port:host:host_port>{green:#}, {green}-D <[bind_address:]port>{green:#}, {green}--auto <NAMES>{green:#} or {green}[TUNNELS]{green:#}",
+                green = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green))),
+            );
+
+            eprintln!();
+            eprintln!(
+                "{bold_underline}Usage:{bold_underline:#} {bold}crtcli app tunnel -L{bold:#} <[bind_address:]port:host:host_port>"
+            );
+            eprintln!();
+            eprintln!("For more information, try '{bold}--help{bold:#}'.");
+        }
+
+        async fn show_tunneling_dashboard(
+            client: Arc<CrtClient>,
+            listener_contexts: Vec<Arc<ListenerContext>>,
+        ) -> Result<(), std::io::Error> {
+            terminal::enable_raw_mode()?;
+            anstream::stderr().queue(terminal::EnterAlternateScreen)?;
+            anstream::stderr().queue(cursor::Hide)?;
+
+            let start_time = Instant::now();
+            let refresh_interval = Duration::from_millis(500);
+
+            let cancellation_token = start_polling_cancellation_token();
+            let mut stderr = anstream::stderr();
+
+            loop {
+                let elapsed_str = humanize_duration_time_precise(start_time.elapsed());
+
+                stderr
+                    .queue(cursor::MoveTo(0, 0))?
+                    .queue(style::Print(format!(
+                        "{bold_underline}crtcli tunneling{bold_underline:#}{underline} via {url} — {time} (Ctrl+C or q to quit){underline:#}",
+                        bold_underline = Style::new().bold().underline(),
+                        underline = Style::new().underline(),
+                        url = client.base_url(),
+                        time = elapsed_str,
+                    )))?
+                    .queue(terminal::Clear(terminal::ClearType::UntilNewLine))?
+                    .queue(cursor::MoveToNextLine(1))?;
+
+                for listener_context in &listener_contexts {
+                    render_listener_context(listener_context).await?;
+                }
+
+                stderr
+                    .queue(terminal::Clear(terminal::ClearType::FromCursorDown))?
+                    .flush()?;
+
+                tokio::select! {
+                    biased;
+                    _ = cancellation_token.cancelled() => { break; },
+                    _ = tokio::time::sleep(refresh_interval) => {}
+                }
+            }
+
+            return Ok(());
+
+            async fn render_listener_context(
+                listener_context: &ListenerContext,
+            ) -> Result<(), std::io::Error> {
+                let mut stderr = anstream::stderr();
+
+                let active = listener_context.active_connections.load(Ordering::Relaxed);
+                let total = listener_context.total_connections.load(Ordering::Relaxed);
+                let rx = humanize_bytes(listener_context.total_rx_bytes.load(Ordering::Relaxed));
+                let tx = humanize_bytes(listener_context.total_tx_bytes.load(Ordering::Relaxed));
+                let bind_addr = listener_context.kind.bind_address();
+                let host_addr = listener_context.kind.target_display();
+
+                let connections_padding = {
+                    const MAX_TOTAL_CONNECTIONS_WIDTH: usize = 4;
+                    const MAX_ACTIVE_CONNECTIONS_WIDTH: usize = 2;
+
+                    let active_connections_length = match active {
+                        _ if active >= 1000 => 4,
+                        _ if active >= 100 => 3,
+                        _ if active >= 10 => 2,
+                        _ => 1,
+                    };
+
+                    let total_connections_length = match total {
+                        _ if total >= 1000 => 4,
+                        _ if total >= 100 => 3,
+                        _ if total >= 10 => 2,
+                        _ => 1,
+                    };
+
+                    MAX_TOTAL_CONNECTIONS_WIDTH + MAX_ACTIVE_CONNECTIONS_WIDTH
+                        - active_connections_length
+                        - total_connections_length
+                };
+
+                stderr
+                    .queue(style::Print(format!(
+                        "  {cyan}{bind_addr}{cyan:#} -> {magenta}{host_addr}{magenta:#}",
+                        cyan = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Cyan))),
+                        magenta = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Magenta))),
+                    )))?
+                    .queue(terminal::Clear(terminal::ClearType::UntilNewLine))?
+                    .queue(cursor::MoveToNextLine(1))?
+                    .queue(style::Print(format!(
+                        "    {empty:>connections_padding$}{yellow}{active}{yellow:#}/{dim}{total}{dim:#} connections,  TX: {green}{tx:>10}{green:#}, RX: {green}{rx:>10}{green:#}",
+                        empty = "",
+                        yellow = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Yellow))),
+                        dim = Style::new().dimmed(),
+                        green = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green))),
+                    )))?;
+
+                if let ListenerKind::Dynamic(_) = &listener_context.kind {
+                    render_dynamic_connections(listener_context)?;
+                }
+
+                for (name, local_connection_string) in listener_context
+                    .local_connection_strings
+                    .lock()
+                    .unwrap()
+                    .iter()
+                {
+                    stderr
+                        .queue(terminal::Clear(terminal::ClearType::UntilNewLine))?
+                        .queue(cursor::MoveToNextLine(1))?
+                        .queue(style::Print(format!(
+                            "    {dim}{name}: {local_connection_string}{dim:#}",
+                            dim = Style::new().dimmed(),
+                        )))?;
+                }
+
+                if let Some(last_connection_error_str) = listener_context
+                    .last_connection_error_str
+                    .read()
+                    .await
+                    .clone()
+                {
+                    stderr
+                        .queue(terminal::Clear(terminal::ClearType::UntilNewLine))?
+                        .queue(cursor::MoveToNextLine(1))?
+                        .queue(style::Print(format!(
+                            "    {red}--> Error: {last_connection_error_str} <--{red:#}",
+                            red = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Red))),
+                        )))?;
+                }
+
+                stderr
+                    .queue(terminal::Clear(terminal::ClearType::UntilNewLine))?
+                    .queue(cursor::MoveToNextLine(1))?;
+
+                Ok(())
+            }
+        }
+
+        fn render_dynamic_connections(
+            listener_context: &ListenerContext,
+        ) -> Result<(), std::io::Error> {
+            const MAX_RENDERED_DYNAMIC_CONNECTIONS: usize = 10;
+
+            let mut stderr = anstream::stderr();
+            let dim = Style::new().dimmed();
+            let magenta = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Magenta)));
+
+            let connections = listener_context.dynamic_connections.lock().unwrap();
+
+            for connection in connections.iter().take(MAX_RENDERED_DYNAMIC_CONNECTIONS) {
+                stderr
+                    .queue(terminal::Clear(terminal::ClearType::UntilNewLine))?
+                    .queue(cursor::MoveToNextLine(1))?
+                    .queue(style::Print(format!(
+                        "      {dim}{client_addr} ->{dim:#} {magenta}{target}{magenta:#}",
+                        client_addr = connection.client_addr,
+                        target = connection.target,
+                    )))?;
+            }
+
+            if connections.len() > MAX_RENDERED_DYNAMIC_CONNECTIONS {
+                stderr
+                    .queue(terminal::Clear(terminal::ClearType::UntilNewLine))?
+                    .queue(cursor::MoveToNextLine(1))?
+                    .queue(style::Print(format!(
+                        "      {dim}... and {count} more{dim:#}",
+                        count = connections.len() - MAX_RENDERED_DYNAMIC_CONNECTIONS,
+                    )))?;
+            }
+
+            Ok(())
+        }
+
+        async fn run_exec(
+            client: &CrtClient,
+            listener_contexts: &[Arc<ListenerContext>],
+            command: &[String],
+        ) -> CommandResult {
+            let bold = Style::new().bold();
+
+            let connection_strings = client
+                .crtcli_tunneling_service()
+                .get_connection_strings()
+                .await?;
+
+            let local_connection_strings = rewrite_connection_strings_to_local_forwards(
+                &connection_strings,
+                listener_contexts,
+            );
+
+            for listener_context in listener_contexts {
+                eprintln!(
+                    "Forwarding {bold}{bind_addr}{bold:#} -> {target}",
+                    bind_addr = listener_context.kind.bind_address(),
+                    target = listener_context.kind.target_display(),
+                );
+            }
+
+            let env_vars = local_connection_strings
+                .iter()
+                .map(|(name, cs)| (connection_string_env_var_name(name), cs.to_string()))
+                .collect::<Vec<_>>();
+
+            for (env_var_name, _) in &env_vars {
+                eprintln!("Exporting {bold}{env_var_name}{bold:#}");
+            }
+
+            let status = tokio::process::Command::new(&command[0])
+                .args(&command[1..])
+                .envs(env_vars)
+                .status()
+                .await
+                .map_err(|err| TunnelCommandError::ExecSpawn(command[0].clone(), err))?;
+
+            match exec_exit_code(status) {
+                None => Ok(()),
+                Some(code) => Err(CommandHandledError(ExitCode::from(code)).into()),
+            }
+        }
+
+        /// Periodically requests tunneling status to keep the session alive,
+        /// expired session is re-authenticated by the client on this request.
+        fn start_session_check(
+            client: Arc<CrtClient>,
+            log_format: TunnelLogFormat,
+        ) -> JoinHandle<()> {
+            const SESSION_CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+            tokio::spawn(async move {
+                loop {
+                    tokio::time::sleep(SESSION_CHECK_INTERVAL).await;
+
+                    if let Err(err) = client.crtcli_tunneling_service().get_status().await {
+                        log_format.log(TunnelEvent::SessionCheckFailed {
+                            error: &err.to_string(),
+                        });
+                    }
+                }
+            })
+        }
+
+        fn hide_tunneling_dashboard() {
+            let _ = anstream::stderr().queue(cursor::Show);
+            let _ = anstream::stderr().queue(terminal::LeaveAlternateScreen);
+            let _ = terminal::disable_raw_mode();
+        }
+
+        fn start_polling_cancellation_token() -> CancellationToken {
+            let mut event_stream = EventStream::new();
+            let cancellation_token = CancellationToken::new();
+
+            let is_close_event = |key_event: event::KeyEvent| {
+                (key_event.code == event::KeyCode::Char('c')
+                    && key_event.modifiers.contains(event::KeyModifiers::CONTROL))
+                    || key_event.code == event::KeyCode::Char('q')
+            };
+
+            {
+                let cancellation_token = cancellation_token.clone();
+
+                tokio::spawn(async move {
+                    loop {
+                        if let Some(Ok(event::Event::Key(key_event))) = event_stream.next().await
+                            && is_close_event(key_event)
+                        {
+                            cancellation_token.cancel();
+                            break;
+                        }
+
+                        if cancellation_token.is_cancelled() {
+                            break;
+                        }
+                    }
+                });
+            }
+
+            cancellation_token
+        }
+
+        async fn start_tcp_listener_forwarding(
+            client: Arc<CrtClient>,
+            listener_kind: ListenerKind,
+            log_format: Option<TunnelLogFormat>,
+        ) -> Result<(Arc<ListenerContext>, JoinHandle<()>), CommandDynError> {
+            let bind_address = listener_kind.bind_address();
+
+            let listener = TcpListener::bind(bind_address)
+                .await
+                .map_err(|err| TunnelCommandError::TcpListenerBind(bind_address, err))?;
+
+            // Port 0 is replaced with the port assigned by OS
+            let listener_kind = listener_kind.with_bind_address(
+                listener
+                    .local_addr()
+                    .map_err(TunnelCommandError::TcpListenerLocalAddr)?,
+            );
+
+            let listener_context = Arc::new(ListenerContext::new(
+                client.clone(),
+                listener_kind,
+                log_format,
+            ));
+
+            let handle = {
+                let context = Arc::clone(&listener_context);
+
+                tokio::spawn(async move {
+                    loop {
+                        if let Ok((tcp_stream, client_addr)) = listener.accept().await {
+                            let context =
+                                ListenerConnectionContext::new(Arc::clone(&context), client_addr);
+
+                            tokio::spawn(async move {
+                                let result =
+                                    process_tcp_listener_connection(tcp_stream, &context).await;
+
+                                context.log_close(result.err().map(|err| err.to_string()));
+                            });
+                        }
+                    }
+                })
+            };
+
+            Ok((listener_context, handle))
+        }
+
+        async fn process_tcp_listener_connection(
+            mut tcp_stream: TcpStream,
+            connection_context: &ListenerConnectionContext,
+        ) -> CommandResult {
+            let (host, port) = match &connection_context.listener.kind {
+                ListenerKind::Local(forward_mapping) => {
+                    (forward_mapping.host.clone(), forward_mapping.host_port)
+                }
+                ListenerKind::Dynamic(_) => {
+                    let target = socks5::accept_connect_request(&mut tcp_stream).await?;
+
+                    connection_context.register_dynamic_target(target.to_string());
+
+                    (target.host, target.port)
+                }
+            };
+
+            let result = connect_with_retry(connection_context, &host, port).await;
+
+            connection_context
+                .listener
+                .apply_last_connection_error_from_result(&result)
+                .await;
+
+            if let ListenerKind::Dynamic(_) = &connection_context.listener.kind {
+                let reply = match &result {
+                    Ok(_) => socks5::Socks5Reply::Succeeded,
+                    Err(CrtClientError::WebSocket(_)) => socks5::Socks5Reply::HostUnreachable,
+                    Err(_) => socks5::Socks5Reply::GeneralFailure,
+                };
+
+                socks5::send_reply(&mut tcp_stream, reply).await?;
+            }
+
+            let websocket = result?;
+
+            if let Some(target) = connection_context.target() {
+                connection_context.listener.log(TunnelEvent::Open {
+                    listener: connection_context.listener.kind.bind_address(),
+                    client: connection_context.client_addr,
+                    target: &target,
+                });
+            }
+
+            let (mut tcp_read, mut tcp_write) = tokio::io::split(tcp_stream);
+            let (mut ws_write, mut ws_read) = websocket.split();
+
+            tokio::select! {
+                _ = forward_tcp_to_ws(&mut tcp_read, &mut ws_write, connection_context) => {},
+                _ = forward_ws_to_tcp(&mut ws_read, &mut tcp_write, connection_context) => {},
+            };
+
+            Ok(())
+        }
+
+        /// Opens tunnel websocket, in headless mode failed attempts are retried with exponential backoff.
+        async fn connect_with_retry(
+            connection_context: &ListenerConnectionContext,
+            host: &str,
+            port: u16,
+        ) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>, CrtClientError> {
+            const MAX_CONNECT_ATTEMPTS: u32 = 5;
+            const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(500);
+            const MAX_RETRY_DELAY: Duration = Duration::from_secs(10);
+
+            let listener = &connection_context.listener;
+            let mut attempt = 1;
+
+            loop {
+                let result = listener
+                    .client
+                    .crtcli_tunneling_service()
+                    .connect(host, port)
+                    .await;
+
+                match result {
+                    Err(err)
+                        if listener.log_format.is_some()
+                            && attempt < MAX_CONNECT_ATTEMPTS
+                            && is_retryable_connect_error(&err) =>
+                    {
+                        let delay =
+                            (INITIAL_RETRY_DELAY * 2u32.pow(attempt - 1)).min(MAX_RETRY_DELAY);
+
+                        listener.log(TunnelEvent::Reconnect {
+                            listener: listener.kind.bind_address(),
+                            target: &format!("{host}:{port}"),
+                            attempt,
+                            delay,
+                            error: &err.to_string(),
+                        });
+
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                    }
+                    result => return result,
+                }
+            }
+
+            fn is_retryable_connect_error(err: &CrtClientError) -> bool {
+                match err {
+                    CrtClientError::CrtCliTunnelingPackageNotInstalled => false,
+                    CrtClientError::WebSocket(websocket_err) => !matches!(
+                        websocket_err.as_ref(),
+                        tokio_tungstenite::tungstenite::Error::Http(response)
+                            if response.status().is_client_error()
+                                && response.status().as_u16() != 401
+                    ),
+                    _ => true,
+                }
+            }
+        }
+    }
+}
+
+/// Rewrites connection strings which point to the host and port of a local forward (-L)
+/// to the local address of this forward. Connection strings without matching forward are skipped.
+fn rewrite_connection_strings_to_local_forwards(
+    connection_strings: &indexmap::IndexMap<String, String>,
+    listener_contexts: &[Arc<ListenerContext>],
+) -> Vec<(String, ConnectionString)> {
+    connection_strings
+        .iter()
+        .filter_map(|(name, value)| {
+            let connection_string = ConnectionString::parse(value);
+            let endpoint = connection_string.endpoint()?;
+
+            let local_address = find_local_forward_context(listener_contexts, &endpoint)?
+                .kind
+                .bind_address();
+
+            let local_ip = match local_address.ip() {
+                ip if ip.is_unspecified() && ip.is_ipv4() => std::net::Ipv4Addr::LOCALHOST.into(),
+                ip if ip.is_unspecified() => std::net::Ipv6Addr::LOCALHOST.into(),
+                ip => ip,
+            };
+
+            Some((
+                name.clone(),
+                connection_string.with_endpoint(&local_ip.to_string(), local_address.port()),
+            ))
+        })
+        .collect()
+}
+
+fn find_local_forward_context<'a>(
+    listener_contexts: &'a [Arc<ListenerContext>],
+    endpoint: &ConnectionStringEndpoint,
+) -> Option<&'a Arc<ListenerContext>> {
+    listener_contexts
+        .iter()
+        .find(|context| match &context.kind {
+            ListenerKind::Local(forward_mapping) => {
+                forward_mapping.host.eq_ignore_ascii_case(&endpoint.host)
+                    && forward_mapping.host_port == endpoint.port
+            }
+            ListenerKind::Dynamic(_) => false,
+        })
+}
+
+struct ListenerContext {
+    client: Arc<CrtClient>,
+    kind: ListenerKind,
+    active_connections: AtomicUsize,
+    total_connections: AtomicUsize,
+    total_tx_bytes: AtomicU64,
+    total_rx_bytes: AtomicU64,
+    last_connection_error_str: RwLock<Option<String>>,
+    dynamic_connections: std::sync::Mutex<Vec<DynamicConnectionInfo>>,
+    local_connection_strings: std::sync::Mutex<Vec<(String, String)>>,
+    log_format: Option<TunnelLogFormat>,
+}
+
+/// Active connection of the SOCKS5 listener, shown on the tunneling dashboard.
+struct DynamicConnectionInfo {
+    id: usize,
+    client_addr: SocketAddr,
+    target: String,
+}
+
+impl ListenerContext {
+    pub fn new(
+        client: Arc<CrtClient>,
+        kind: ListenerKind,
+        log_format: Option<TunnelLogFormat>,
+    ) -> Self {
+        Self {
+            client,
+            kind,
+            active_connections: AtomicUsize::new(0),
+            total_connections: AtomicUsize::new(0),
+            total_tx_bytes: AtomicU64::new(0),
+            total_rx_bytes: AtomicU64::new(0),
+            last_connection_error_str: RwLock::new(None),
+            dynamic_connections: std::sync::Mutex::new(vec![]),
+            local_connection_strings: std::sync::Mutex::new(vec![]),
+            log_format,
+        }
+    }
+
+    pub fn log(&self, event: TunnelEvent) {
+        if let Some(log_format) = self.log_format {
+            log_format.log(event);
+        }
+    }
+
+    pub async fn apply_last_connection_error_from_result<V>(
+        &self,
+        result: &Result<V, CrtClientError>,
+    ) {
+        match result {
+            Ok(_) => {
+                if self.last_connection_error_str.read().await.is_some() {
+                    self.last_connection_error_str.write().await.take();
+                }
+            }
+            Err(err) => {
+                self.last_connection_error_str
+                    .write()
+                    .await
+                    .replace(err.to_string());
+            }
+        }
+    }
+}
+
+struct ListenerConnectionContext {
+    listener: Arc<ListenerContext>,
+    id: usize,
+    client_addr: SocketAddr,
+    started_at: Instant,
+    tx_bytes: AtomicU64,
+    rx_bytes: AtomicU64,
+}
+
+impl ListenerConnectionContext {
+    pub fn new(listener: Arc<ListenerContext>, client_addr: SocketAddr) -> Self {
+        listener.active_connections.fetch_add(1, Ordering::Relaxed);
+        let id = listener.total_connections.fetch_add(1, Ordering::Relaxed);
+
+        Self {
+            listener,
+            id,
+            client_addr,
+            started_at: Instant::now(),
+            tx_bytes: AtomicU64::new(0),
+            rx_bytes: AtomicU64::new(0),
+        }
+    }
+
+    pub fn target(&self) -> Option<String> {
+        match &self.listener.kind {
+            ListenerKind::Local(_) => Some(self.listener.kind.target_display()),
+            ListenerKind::Dynamic(_) => self
+                .listener
+                .dynamic_connections
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|connection| connection.id == self.id)
+                .map(|connection| connection.target.clone()),
+        }
+    }
+
+    pub fn add_tx_bytes(&self, bytes: u64) {
+        self.tx_bytes.fetch_add(bytes, Ordering::Relaxed);
+        self.listener
+            .total_tx_bytes
+            .fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn add_rx_bytes(&self, bytes: u64) {
+        self.rx_bytes.fetch_add(bytes, Ordering::Relaxed);
+        self.listener
+            .total_rx_bytes
+            .fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn log_close(&self, error: Option<String>) {
+        self.listener.log(TunnelEvent::Close {
+            listener: self.listener.kind.bind_address(),
+            client: self.client_addr,
+            target: self.target().as_deref(),
+            tx_bytes: self.tx_bytes.load(Ordering::Relaxed),
+            rx_bytes: self.rx_bytes.load(Ordering::Relaxed),
+            duration: self.started_at.elapsed(),
+            error: error.as_deref(),
+        });
+    }
+
+    pub fn register_dynamic_target(&self, target: String) {
+        self.listener
+            .dynamic_connections
+            .lock()
+            .unwrap()
+            .push(DynamicConnectionInfo {
+                id: self.id,
+                client_addr: self.client_addr,
+                target,
+            });
+    }
+}
+
+impl Drop for ListenerConnectionContext {
+    fn drop(&mut self) {
+        self.listener
+            .active_connections
+            .fetch_sub(1, Ordering::Relaxed);
+
+        self.listener
+            .dynamic_connections
+            .lock()
+            .unwrap()
+            .retain(|connection| connection.id != self.id);
+    }
+}
+
+async fn forward_ws_to_tcp<W>(
+    ws_read: &mut SplitStream<WebSocketStream<impl AsyncRead + AsyncWrite + Unpin>>,
+    tcp_write: &mut W,
+    listener_context: &ListenerConnectionContext,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+where
+    W: AsyncWrite + Unpin,
+{
+    while let Some(message_result) = ws_read.next().await {
+        match message_result {
+            Ok(message) => match message {
+                Message::Binary(data) => {
+                    tcp_write.write_all(&data).await?;
+                    tcp_write.flush().await?;
+
+                    listener_context.add_rx_bytes(data.len() as u64);
+                }
+                Message::Text(_) => {
+                    return Err("websocket received text message, which is not supported".into());
+                }
+                Message::Close(_) => {
+                    return Ok(());
+                }
+                _ => {}
+            },
+            Err(e) => {
+                return Err(Box::new(e));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn forward_tcp_to_ws<R>(
+    tcp_read: &mut R,
+    ws_write: &mut SplitSink<WebSocketStream<impl AsyncRead + AsyncWrite + Unpin>, Message>,
+    connection_context: &ListenerConnectionContext,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+where
+    R: AsyncRead + Unpin,
+{
+    static WS_PING_INTERVAL: Duration = Duration::from_secs(30);
+
+    let mut buffer = vec![0u8; 81920];
+
+    loop {
+        tokio::select! {
+            message = tcp_read.read(&mut buffer) => {
+                match message {
+                    Ok(0) => {
+                        let _ = ws_write.send(Message::Close(None)).await;
+
+                        return Ok(());
+                    }
+                    Ok(n) => {
+                        ws_write
+                            .send(Message::Binary(buffer[..n].to_vec().into()))
+                            .await?;
+                        ws_write.flush().await?;
+
+                        connection_context.add_tx_bytes(n as u64);
+                    }
+                    Err(e) => {
+                        if e.kind() == tokio::io::ErrorKind::WouldBlock {
+                            tokio::task::yield_now().await;
+
+                            continue;
+                        }
+
+                        return Err(Box::new(e));
+                    }
+                }
+            },
+            _ = tokio::time::sleep(WS_PING_INTERVAL) => {
+                ws_write.send(Message::Ping(Bytes::new())).await?;
+                ws_write.flush().await?;
+            },
+        }
+    }
+}
+
+/// Returns the exit code to exit with after the executed command, `None` if it succeeded.
+///
+/// Codes out of the `1..=255` range are clamped to stay non-zero, a command terminated
+/// by a signal results in `128 + signal` like in shells.
+fn exec_exit_code(status: std::process::ExitStatus) -> Option<u8> {
+    if status.success() {
+        return None;
+    }
+
+    if let Some(code) = status.code() {
+        return Some(code.clamp(1, 255) as u8);
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+
+        if let Some(signal) = status.signal() {
+            return Some((128 + signal).clamp(1, 255) as u8);
+        }
+    }
+
+    Some(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dynamic_forward_bind_address() {
+        let parse = |value: &str| DynamicForwardArg::try_from(value).map(|arg| arg.bind_address);
+
+        assert_eq!(
+            parse("1080"),
+            Ok("127.0.0.1:1080".parse::<SocketAddr>().unwrap())
+        );
+        assert_eq!(
+            parse(":1080"),
+            Ok("0.0.0.0:1080".parse::<SocketAddr>().unwrap())
+        );
+        assert_eq!(
+            parse("[::1]:1080"),
+            Ok("[::1]:1080".parse::<SocketAddr>().unwrap())
+        );
+        assert_eq!(
+            parse("[::]:1080"),
+            Ok("[::]:1080".parse::<SocketAddr>().unwrap())
+        );
+        assert_eq!(parse("[::1]"), Err(ForwardMappingArgParsingError::Format));
+        assert_eq!(
+            parse("localhost:1080"),
+            Err(ForwardMappingArgParsingError::IpAddr(
+                "localhost".to_owned()
+            ))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn exec_exit_code_stays_non_zero() {
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::ExitStatus;
+
+        assert_eq!(exec_exit_code(ExitStatus::from_raw(0)), None);
+        assert_eq!(exec_exit_code(ExitStatus::from_raw(3 << 8)), Some(3));
+        assert_eq!(exec_exit_code(ExitStatus::from_raw(255 << 8)), Some(255));
+        assert_eq!(exec_exit_code(ExitStatus::from_raw(9)), Some(137));
+        assert_eq!(exec_exit_code(ExitStatus::from_raw(15)), Some(143));
+    }
+}
@@ -0,0 +1,218 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+const SOCKS_VERSION: u8 = 0x05;
+
+const AUTH_METHOD_NO_AUTH: u8 = 0x00;
+const AUTH_METHOD_NO_ACCEPTABLE: u8 = 0xFF;
+
+const COMMAND_CONNECT: u8 = 0x01;
+
+const ADDRESS_TYPE_IPV4: u8 = 0x01;
+const ADDRESS_TYPE_DOMAIN_NAME: u8 = 0x03;
+const ADDRESS_TYPE_IPV6: u8 = 0x04;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Socks5Target {
+    pub host: String,
+    pub port: u16,
+}
+
+impl std::fmt::Display for Socks5Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.host.contains(':') {
+            true => write!(f, "[{}]:{}", self.host, self.port),
+            false => write!(f, "{}:{}", self.host, self.port),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Socks5Error {
+    #[error("socks5 io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("unsupported socks protocol version: {0}")]
+    UnsupportedVersion(u8),
+
+    #[error("socks5 client does not support authentication without credentials")]
+    NoAcceptableAuthMethod,
+
+    #[error("unsupported socks5 command: {0}, only CONNECT is supported")]
+    UnsupportedCommand(u8),
+
+    #[error("unsupported socks5 address type: {0}")]
+    UnsupportedAddressType(u8),
+
+    #[error("invalid socks5 domain name")]
+    InvalidDomainName,
+}
+
+/// Reply codes of the SOCKS5 CONNECT request (RFC 1928, section 6).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Socks5Reply {
+    Succeeded = 0x00,
+    GeneralFailure = 0x01,
+    HostUnreachable = 0x04,
+    CommandNotSupported = 0x07,
+    AddressTypeNotSupported = 0x08,
+}
+
+/// Performs SOCKS5 greeting without authentication and reads the CONNECT request.
+///
+/// On unsupported requests the corresponding failure reply is sent before returning an error.
+/// After the target is returned, the caller must respond with [`send_reply`].
+pub async fn accept_connect_request<S>(stream: &mut S) -> Result<Socks5Target, Socks5Error>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let version = stream.read_u8().await?;
+
+    if version != SOCKS_VERSION {
+        return Err(Socks5Error::UnsupportedVersion(version));
+    }
+
+    let methods_count = stream.read_u8().await?;
+    let mut methods = vec![0u8; methods_count as usize];
+    stream.read_exact(&mut methods).await?;
+
+    if !methods.contains(&AUTH_METHOD_NO_AUTH) {
+        stream
+            .write_all(&[SOCKS_VERSION, AUTH_METHOD_NO_ACCEPTABLE])
+            .await?;
+
+        return Err(Socks5Error::NoAcceptableAuthMethod);
+    }
+
+    stream
+        .write_all(&[SOCKS_VERSION, AUTH_METHOD_NO_AUTH])
+        .await?;
+
+    let mut header = [0u8; 4];
+    stream.read_exact(&mut header).await?;
+
+    let [version, command, _reserved, address_type] = header;
+
+    if version != SOCKS_VERSION {
+        return Err(Socks5Error::UnsupportedVersion(version));
+    }
+
+    let host = match address_type {
+        ADDRESS_TYPE_IPV4 => {
+            let mut octets = [0u8; 4];
+            stream.read_exact(&mut octets).await?;
+
+            Ipv4Addr::from(octets).to_string()
+        }
+        ADDRESS_TYPE_IPV6 => {
+            let mut octets = [0u8; 16];
+            stream.read_exact(&mut octets).await?;
+
+            Ipv6Addr::from(octets).to_string()
+        }
+        ADDRESS_TYPE_DOMAIN_NAME => {
+            let length = stream.read_u8().await?;
+            let mut domain = vec![0u8; length as usize];
+            stream.read_exact(&mut domain).await?;
+
+            String::from_utf8(domain).map_err(|_| Socks5Error::InvalidDomainName)?
+        }
+        _ => {
+            send_reply(stream, Socks5Reply::AddressTypeNotSupported).await?;
+
+            return Err(Socks5Error::UnsupportedAddressType(address_type));
+        }
+    };
+
+    let port = stream.read_u16().await?;
+
+    if command != COMMAND_CONNECT {
+        send_reply(stream, Socks5Reply::CommandNotSupported).await?;
+
+        return Err(Socks5Error::UnsupportedCommand(command));
+    }
+
+    Ok(Socks5Target { host, port })
+}
+
+/// Sends reply to the CONNECT request, bound address is always reported as `0.0.0.0:0`
+/// because the real connection is established by the Creatio server.
+pub async fn send_reply<S>(stream: &mut S, reply: Socks5Reply) -> Result<(), std::io::Error>
+where
+    S: AsyncWrite + Unpin,
+{
+    stream
+        .write_all(&[
+            SOCKS_VERSION,
+            reply as u8,
+            0x00,
+            ADDRESS_TYPE_IPV4,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+        ])
+        .await?;
+
+    stream.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn accepts_connect_request_with_domain_name() {
+        let (mut client, mut server) = tokio::io::duplex(256);
+
+        let server_task = tokio::spawn(async move { accept_connect_request(&mut server).await });
+
+        client.write_all(&[0x05, 0x02, 0x02, 0x00]).await.unwrap();
+
+        let mut greeting_reply = [0u8; 2];
+        client.read_exact(&mut greeting_reply).await.unwrap();
+        assert_eq!(greeting_reply, [0x05, 0x00]);
+
+        client
+            .write_all(&[
+                0x05, 0x01, 0x00, 0x03, 7, b'd', b'b', b'-', b'h', b'o', b's', b't',
+            ])
+            .await
+            .unwrap();
+        client.write_all(&5432u16.to_be_bytes()).await.unwrap();
+
+        assert_eq!(
+            server_task.await.unwrap().unwrap(),
+            Socks5Target {
+                host: "db-host".to_owned(),
+                port: 5432
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_unsupported_command() {
+        let (mut client, mut server) = tokio::io::duplex(256);
+
+        let server_task = tokio::spawn(async move { accept_connect_request(&mut server).await });
+
+        client.write_all(&[0x05, 0x01, 0x00]).await.unwrap();
+        client
+            .write_all(&[0x05, 0x02, 0x00, 0x01, 10, 0, 0, 1, 0x1F, 0x90])
+            .await
+            .unwrap();
+
+        let mut reply = [0u8; 12];
+        client.read_exact(&mut reply).await.unwrap();
+
+        assert_eq!(&reply[..2], &[0x05, 0x00]);
+        assert_eq!(reply[3], Socks5Reply::CommandNotSupported as u8);
+        assert!(matches!(
+            server_task.await.unwrap(),
+            Err(Socks5Error::UnsupportedCommand(0x02))
+        ));
+    }
+}
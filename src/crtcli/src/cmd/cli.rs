@@ -73,7 +73,7 @@ enum Commands {
     #[clap(verbatim_doc_comment, visible_alias = "a")]
     App {
         #[command(flatten)]
-        args: crate::cmd::app::AppCommandArgs,
+        args: Box<crate::cmd::app::AppCommandArgs>,
 
         #[command(subcommand)]
         command: Option<crate::cmd::app::AppCommands>,
@@ -96,7 +96,7 @@ enum Commands {
 impl CliCommand for Commands {
     fn run(self) -> CommandResult {
         match self {
-            Commands::App { args, command } => run_app_command(args, command),
+            Commands::App { args, command } => run_app_command(*args, command),
             Commands::Pkg { command } => command.run(),
         }
     }
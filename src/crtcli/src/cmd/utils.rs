@@ -50,6 +50,19 @@ pub fn generate_zip_package_filename(package_name: &str) -> String {
     format!("{package_name}_{now_str}.zip")
 }
 
+/// Asks the user for confirmation in the terminal, returns `false` for any answer except "y" / "yes".
+pub fn confirm(prompt: &str) -> std::io::Result<bool> {
+    use std::io::Write;
+
+    eprint!("{prompt} [y/N] ");
+    std::io::stderr().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
 pub fn humanize_bytes(bytes: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = 1024 * KB;
@@ -66,6 +79,18 @@ pub fn humanize_bytes(bytes: u64) -> String {
     }
 }
 
+/// Formats the duration with two most significant units, e.g. "45s", "12m 5s", "3h 20m" or "2d 4h".
+pub fn humanize_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m {}s", secs / 60, secs % 60),
+        3600..86400 => format!("{}h {}m", secs / 3600, (secs % 3600) / 60),
+        _ => format!("{}d {}h", secs / 86400, (secs % 86400) / 3600),
+    }
+}
+
 pub fn humanize_duration_time_precise(duration: Duration) -> String {
     let secs = duration.as_secs();
 
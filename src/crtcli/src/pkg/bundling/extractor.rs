@@ -411,6 +411,22 @@ pub fn extract_zip_package_to_folder(
     Ok(package_folders)
 }
 
+/// Checks if two versions of the package file are equal, ignoring insignificant schema differences
+/// the same way as [`FilesAlreadyExistsInFolderStrategy::SmartMerge`] does.
+pub fn is_pkg_file_content_smart_equal(relative_path: &str, source: &[u8], target: &[u8]) -> bool {
+    let config = PackageToFolderExtractorConfig::default()
+        .with_files_already_exists_in_folder_strategy(
+            FilesAlreadyExistsInFolderStrategy::SmartMerge,
+        );
+
+    check_pkg_file_content_equal(
+        &config,
+        relative_path,
+        Some(LazyCell::new(|| Cow::Borrowed(source))),
+        Some(target),
+    )
+}
+
 fn check_pkg_file_content_equal<'a>(
     config: &PackageToFolderExtractorConfig,
     relative_path: &str,
@@ -39,6 +39,10 @@ impl PkgJsonWrapper {
         })
     }
 
+    pub fn from_value(value: Value, is_bom: bool) -> Self {
+        Self { is_bom, value }
+    }
+
     pub fn from_file(path: &Path) -> Result<Self, PkgJsonWrapperCreateError> {
         Self::new(&std::fs::read(path)?)
     }
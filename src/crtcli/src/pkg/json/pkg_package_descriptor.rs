@@ -109,6 +109,10 @@ impl PkgPackageDescriptorDependOnItem<'_> {
         self.0["Name"].as_str()
     }
 
+    pub fn version(&self) -> Option<&str> {
+        self.0["PackageVersion"].as_str()
+    }
+
     pub fn pkg_type(&self) -> u64 {
         self.0["Type"].as_u64().unwrap_or(0)
     }
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 const AUTOGENERATED_FOLDER: &str = "Autogenerated";
 const AUTOGENERATED_LIB_FOLDER: &str = "Lib";
 const AUTOGENERATED_SRC_FOLDER: &str = "Src";
@@ -19,3 +21,118 @@ pub const PKG_FOLDERS: [&str; 6] = [
 ];
 
 pub const PKG_DESCRIPTOR_FILE: &str = "descriptor.json";
+
+/// Package source file referenced by a server-side path produced by Creatio compilation.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PkgSourceFileRef {
+    pub package_name: Option<String>,
+    pub relative_path: PathBuf,
+}
+
+/// Maps a server-side compilation file path to the file path relative to the package folder.
+///
+/// Supports schema sources generated into `Autogenerated/Src/<SchemaName>[.<PackageName>].cs`
+/// (mapped to `Schemas/<SchemaName>/<SchemaName>.cs`) and package files under `Files/src/...`.
+pub fn map_server_source_path_to_package_file(server_path: &str) -> Option<PkgSourceFileRef> {
+    let components = server_path
+        .split(['/', '\\'])
+        .filter(|c| !c.is_empty())
+        .collect::<Vec<_>>();
+
+    let preceding = |i: usize| i.checked_sub(1).map(|i| components[i].to_owned());
+
+    if let Some(i) = components.windows(2).rposition(|w| {
+        w[0].eq_ignore_ascii_case(AUTOGENERATED_FOLDER)
+            && w[1].eq_ignore_ascii_case(AUTOGENERATED_SRC_FOLDER)
+    }) {
+        let filename = *components.last()?;
+        let mut name_parts = filename.split('.').collect::<Vec<_>>();
+
+        if name_parts.len() < 2 || !name_parts.pop()?.eq_ignore_ascii_case("cs") {
+            return None;
+        }
+
+        let schema_name = name_parts[0];
+
+        let package_name = match name_parts.get(1) {
+            Some(package_name) => Some((*package_name).to_owned()),
+            None => preceding(i),
+        };
+
+        return Some(PkgSourceFileRef {
+            package_name,
+            relative_path: PathBuf::from(SCHEMAS_FOLDER)
+                .join(schema_name)
+                .join(format!("{schema_name}.cs")),
+        });
+    }
+
+    if components.windows(2).any(|w| {
+        w[0].eq_ignore_ascii_case(AUTOGENERATED_FOLDER)
+            && w[1].eq_ignore_ascii_case(AUTOGENERATED_LIB_FOLDER)
+    }) {
+        return None;
+    }
+
+    let i = components.windows(2).rposition(|w| {
+        w[0].eq_ignore_ascii_case(FILES_FOLDER) && w[1].eq_ignore_ascii_case("src")
+    })?;
+
+    Some(PkgSourceFileRef {
+        package_name: preceding(i),
+        relative_path: components[i..].iter().collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_autogenerated_schema_source() {
+        assert_eq!(
+            map_server_source_path_to_package_file(
+                r"C:\inetpub\creatio\Terrasoft.WebApp\Terrasoft.Configuration\Pkg\UsrPackage\Autogenerated\Src\UsrService.UsrPackage.cs"
+            ),
+            Some(PkgSourceFileRef {
+                package_name: Some("UsrPackage".to_owned()),
+                relative_path: PathBuf::from("Schemas/UsrService/UsrService.cs"),
+            })
+        );
+
+        assert_eq!(
+            map_server_source_path_to_package_file(
+                "/app/Terrasoft.Configuration/Pkg/UsrPackage/Autogenerated/Src/UsrService.cs"
+            ),
+            Some(PkgSourceFileRef {
+                package_name: Some("UsrPackage".to_owned()),
+                relative_path: PathBuf::from("Schemas/UsrService/UsrService.cs"),
+            })
+        );
+    }
+
+    #[test]
+    fn map_files_source() {
+        assert_eq!(
+            map_server_source_path_to_package_file(
+                "/app/Terrasoft.Configuration/Pkg/UsrPackage/Files/src/cs/Helpers/UsrHelper.cs"
+            ),
+            Some(PkgSourceFileRef {
+                package_name: Some("UsrPackage".to_owned()),
+                relative_path: PathBuf::from("Files/src/cs/Helpers/UsrHelper.cs"),
+            })
+        );
+    }
+
+    #[test]
+    fn map_unknown_source() {
+        assert_eq!(
+            map_server_source_path_to_package_file(
+                "/app/Terrasoft.Configuration/Pkg/UsrPackage/Autogenerated/Lib/UsrLib.dll"
+            ),
+            None
+        );
+
+        assert_eq!(map_server_source_path_to_package_file(""), None);
+    }
+}
@@ -4,6 +4,7 @@ use crate::pkg::json::{
 use crate::pkg::*;
 use anstyle::{AnsiColor, Color, Style};
 use flate2::read::GzDecoder;
+use std::fmt::{Display, Formatter};
 use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
@@ -207,31 +208,51 @@ pub fn get_package_descriptors_from_package_reader(
 
     reader.seek(SeekFrom::Start(position))?;
 
-    return Ok(results);
-
-    fn get_package_descriptor_as_gzip(
-        reader: &mut impl Read,
-    ) -> Result<PkgPackageDescriptorJsonWrapper, GetPackageDescriptorFromGzipReaderError> {
-        let descriptor = bundling::PkgGZipDecoder::new(GzDecoder::new(reader))
-            .filter_map(|f| -> Option<Result<_, _>> {
-                match f {
-                    Err(error) => Some(Err(error)),
-                    Ok(file) => match file.filename == paths::PKG_DESCRIPTOR_FILE {
-                        true => Some(Ok(file)),
-                        false => None,
-                    },
-                }
-            })
-            .next();
-
-        let descriptor =
-            descriptor.ok_or(GetPackageDescriptorFromGzipReaderError::DescriptorNotFound)??;
+    Ok(results)
+}
 
-        let descriptor =
-            PkgPackageDescriptorJsonWrapper::from(PkgJsonWrapper::new(&descriptor.content)?);
+/// Reads package descriptors from the zip archive of packages sequentially, without seeking.
+///
+/// Used to get descriptors from the archive which is being streamed to another place at the same time.
+pub fn get_package_descriptors_from_zip_stream(
+    reader: &mut impl Read,
+) -> Result<Vec<PkgPackageDescriptorJsonWrapper>, GetPackageDescriptorFromReaderError> {
+    let mut results = vec![];
 
-        Ok(descriptor)
+    while let Some(mut gzip) = zip::read::read_zipfile_from_stream(reader)? {
+        results.push(get_package_descriptor_as_gzip(&mut gzip).map_err(|err| {
+            GetPackageDescriptorFromReaderError::AsZipError {
+                filename_in_zip: gzip.name().to_owned(),
+                source: err,
+            }
+        })?);
     }
+
+    Ok(results)
+}
+
+fn get_package_descriptor_as_gzip(
+    reader: &mut impl Read,
+) -> Result<PkgPackageDescriptorJsonWrapper, GetPackageDescriptorFromGzipReaderError> {
+    let descriptor = bundling::PkgGZipDecoder::new(GzDecoder::new(reader))
+        .filter_map(|f| -> Option<Result<_, _>> {
+            match f {
+                Err(error) => Some(Err(error)),
+                Ok(file) => match file.filename == paths::PKG_DESCRIPTOR_FILE {
+                    true => Some(Ok(file)),
+                    false => None,
+                },
+            }
+        })
+        .next();
+
+    let descriptor =
+        descriptor.ok_or(GetPackageDescriptorFromGzipReaderError::DescriptorNotFound)??;
+
+    let descriptor =
+        PkgPackageDescriptorJsonWrapper::from(PkgJsonWrapper::new(&descriptor.content)?);
+
+    Ok(descriptor)
 }
 
 pub fn cmp_file_content_and_apply_with_log(
@@ -291,3 +312,266 @@ pub fn cmp_file_content_and_apply_with_log(
         _ => Ok(false),
     }
 }
+
+#[derive(Error, Debug)]
+#[error("cyclic dependency detected between packages: {}", .0.join(", "))]
+pub struct PackageDependencyCycleError(pub Vec<String>);
+
+/// Orders packages so that each package goes after the packages it depends on.
+///
+/// Accepts pairs of package name and its DependsOn package names. Dependencies outside
+/// of the given set are ignored, the original order is preserved where possible.
+pub fn sort_packages_by_depends_on(
+    packages: Vec<(String, Vec<String>)>,
+) -> Result<Vec<String>, PackageDependencyCycleError> {
+    let is_in_set = |name: &str, set: &[(String, Vec<String>)]| {
+        set.iter().any(|(n, _)| n.eq_ignore_ascii_case(name))
+    };
+
+    let mut pending = packages;
+    let mut result: Vec<String> = Vec::with_capacity(pending.len());
+
+    while !pending.is_empty() {
+        let ready_index = pending.iter().position(|(_, depends_on)| {
+            depends_on.iter().all(|d| {
+                !is_in_set(d, &pending) || result.iter().any(|r| r.eq_ignore_ascii_case(d))
+            })
+        });
+
+        match ready_index {
+            Some(i) => result.push(pending.remove(i).0),
+            None => {
+                return Err(PackageDependencyCycleError(
+                    pending.into_iter().map(|(name, _)| name).collect(),
+                ));
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageDependencyProblem {
+    pub package: String,
+    pub dependency: String,
+    pub kind: PackageDependencyProblemKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackageDependencyProblemKind {
+    Missing,
+    VersionMismatch { required: String, installed: String },
+}
+
+impl Display for PackageDependencyProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            PackageDependencyProblemKind::Missing => write!(
+                f,
+                "{} depends on {}, which is not installed",
+                self.package, self.dependency
+            ),
+            PackageDependencyProblemKind::VersionMismatch {
+                required,
+                installed,
+            } => write!(
+                f,
+                "{} depends on {} {required}, but {installed} is installed",
+                self.package, self.dependency
+            ),
+        }
+    }
+}
+
+/// Checks DependsOn of the packages against installed packages (pairs of name and version).
+///
+/// Dependencies on the checked packages themselves are skipped as they are installed together.
+/// Version mismatch is reported only if the installed version is lower than the required one.
+pub fn check_package_dependencies(
+    packages: &[PkgPackageDescriptorJsonWrapper],
+    installed: &[(String, Option<String>)],
+) -> Vec<PackageDependencyProblem> {
+    let mut problems = vec![];
+
+    for package in packages {
+        let package_name = package.name().unwrap_or("_");
+
+        for dependency in package.depends_on().unwrap_or_default() {
+            let Some(dependency_name) = dependency.name() else {
+                continue;
+            };
+
+            if packages.iter().any(|p| {
+                p.name()
+                    .is_some_and(|n| n.eq_ignore_ascii_case(dependency_name))
+            }) {
+                continue;
+            }
+
+            let kind = match installed
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(dependency_name))
+            {
+                None => PackageDependencyProblemKind::Missing,
+                Some((_, installed_version)) => match (dependency.version(), installed_version) {
+                    (Some(required), Some(installed))
+                        if compare_package_versions(installed, required).is_lt() =>
+                    {
+                        PackageDependencyProblemKind::VersionMismatch {
+                            required: required.to_owned(),
+                            installed: installed.to_owned(),
+                        }
+                    }
+                    _ => continue,
+                },
+            };
+
+            problems.push(PackageDependencyProblem {
+                package: package_name.to_owned(),
+                dependency: dependency_name.to_owned(),
+                kind,
+            });
+        }
+    }
+
+    problems
+}
+
+/// Compares dot-separated package versions (e.g. "7.8.0") numerically, non-numeric parts are treated as 0.
+pub fn compare_package_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let parse = |v: &str| {
+        v.split('.')
+            .map(|x| x.trim().parse::<u64>().unwrap_or(0))
+            .collect::<Vec<_>>()
+    };
+
+    let (a, b) = (parse(a), parse(b));
+
+    (0..a.len().max(b.len()))
+        .map(|i| a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)))
+        .find(|o| o.is_ne())
+        .unwrap_or(std::cmp::Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pkg(name: &str, depends_on: &[&str]) -> (String, Vec<String>) {
+        (
+            name.to_owned(),
+            depends_on.iter().map(|d| (*d).to_owned()).collect(),
+        )
+    }
+
+    #[test]
+    fn sort_packages_by_depends_on_orders_dependencies_first() {
+        let sorted = sort_packages_by_depends_on(vec![
+            pkg("UsrApp", &["UsrCore", "UsrUi", "CrtBase"]),
+            pkg("UsrUi", &["UsrCore"]),
+            pkg("UsrCore", &["CrtBase"]),
+            pkg("UsrOther", &[]),
+        ])
+        .unwrap();
+
+        assert_eq!(sorted, vec!["UsrCore", "UsrUi", "UsrApp", "UsrOther"]);
+    }
+
+    #[test]
+    fn sort_packages_by_depends_on_detects_cycle() {
+        let err = sort_packages_by_depends_on(vec![
+            pkg("UsrA", &["UsrB"]),
+            pkg("UsrB", &["usra"]),
+            pkg("UsrC", &[]),
+        ])
+        .unwrap_err();
+
+        assert_eq!(err.0, vec!["UsrA", "UsrB"]);
+    }
+
+    #[test]
+    fn check_package_dependencies_reports_missing_and_outdated() {
+        let descriptor = |name: &str, depends_on: serde_json::Value| {
+            PkgPackageDescriptorJsonWrapper::from(PkgJsonWrapper::from_value(
+                serde_json::json!({
+                    "Descriptor": {
+                        "Name": name,
+                        "DependsOn": depends_on
+                    }
+                }),
+                false,
+            ))
+        };
+
+        let packages = vec![
+            descriptor(
+                "UsrApp",
+                serde_json::json!([
+                    { "Name": "CrtBase", "PackageVersion": "8.1.0" },
+                    { "Name": "CrtCore", "PackageVersion": "8.0.0" },
+                    { "Name": "UsrCore", "PackageVersion": "1.0.0" },
+                    { "Name": "UsrMissing", "PackageVersion": "1.0.0" },
+                ]),
+            ),
+            descriptor("UsrCore", serde_json::json!([])),
+        ];
+
+        let installed = vec![
+            ("CrtBase".to_owned(), Some("8.0.5".to_owned())),
+            ("CrtCore".to_owned(), Some("8.1.5.2176".to_owned())),
+        ];
+
+        let problems = check_package_dependencies(&packages, &installed);
+
+        assert_eq!(
+            problems.iter().map(|p| p.to_string()).collect::<Vec<_>>(),
+            vec![
+                "UsrApp depends on CrtBase 8.1.0, but 8.0.5 is installed",
+                "UsrApp depends on UsrMissing, which is not installed",
+            ]
+        );
+    }
+
+    #[test]
+    fn get_package_descriptors_from_zip_stream_reads_all_packages() {
+        let gzip_package = |name: &str| {
+            let mut gzip = vec![];
+            let mut encoder = bundling::PkgGZipEncoder::new(&mut gzip, None);
+
+            encoder
+                .write_file(&bundling::PkgGZipFile {
+                    filename: paths::PKG_DESCRIPTOR_FILE.to_owned(),
+                    content: serde_json::to_vec(&serde_json::json!({
+                        "Descriptor": { "Name": name }
+                    }))
+                    .unwrap(),
+                })
+                .unwrap();
+
+            drop(encoder);
+
+            gzip
+        };
+
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(vec![]));
+
+        for name in ["UsrCore", "UsrApp"] {
+            zip.start_file(
+                format!("{name}.gz"),
+                zip::write::SimpleFileOptions::default(),
+            )
+            .unwrap();
+            std::io::Write::write_all(&mut zip, &gzip_package(name)).unwrap();
+        }
+
+        let zip = zip.finish().unwrap().into_inner();
+
+        let descriptors = get_package_descriptors_from_zip_stream(&mut zip.as_slice()).unwrap();
+
+        assert_eq!(
+            descriptors.iter().map(|d| d.name()).collect::<Vec<_>>(),
+            vec![Some("UsrCore"), Some("UsrApp")]
+        );
+    }
+}
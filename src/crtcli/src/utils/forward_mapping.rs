@@ -0,0 +1,138 @@
+use clap::builder::{ValueParser, ValueParserFactory};
+use std::net::SocketAddr;
+use thiserror::Error;
+
+/// Local port forwarding rule in the SSH like format `[bind_address:]port:host:host_port`.
+#[derive(Debug, Clone)]
+pub struct ForwardMappingArg {
+    pub bind_address: SocketAddr,
+    pub host: String,
+    pub host_port: u16,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ForwardMappingArgParsingError {
+    #[error("invalid format")]
+    Format,
+
+    #[error("invalid IP address: '{0}'")]
+    IpAddr(String),
+
+    #[error("invalid port number: '{0}'")]
+    Port(String),
+}
+
+impl TryFrom<&str> for ForwardMappingArg {
+    type Error = ForwardMappingArgParsingError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let parts = split_forward_spec(value)?;
+
+        let (bind_address, port_str, host, host_port_str) = match parts.len() {
+            3 => ("127.0.0.1", parts[0], parts[1], parts[2]),
+            4 => {
+                let bind_address = if parts[0].is_empty() {
+                    "0.0.0.0"
+                } else {
+                    parts[0]
+                };
+
+                (bind_address, parts[1], parts[2], parts[3])
+            }
+            _ => return Err(ForwardMappingArgParsingError::Format),
+        };
+
+        let port = port_str
+            .parse::<u16>()
+            .map_err(|_| ForwardMappingArgParsingError::Port(port_str.to_string()))?;
+
+        let host_port = host_port_str
+            .parse::<u16>()
+            .map_err(|_| ForwardMappingArgParsingError::Port(host_port_str.to_string()))?;
+
+        let bind_address = SocketAddr::new(
+            bind_address
+                .parse()
+                .map_err(|_| ForwardMappingArgParsingError::IpAddr(bind_address.to_string()))?,
+            port,
+        );
+
+        Ok(ForwardMappingArg {
+            bind_address,
+            host: host.to_string(),
+            host_port,
+        })
+    }
+}
+
+/// Splits the forward specification by colons, IPv6 addresses should be enclosed
+/// in square brackets (e.g. `[::1]:5432:db:5432`), brackets are removed from the parts.
+fn split_forward_spec(value: &str) -> Result<Vec<&str>, ForwardMappingArgParsingError> {
+    let mut parts = vec![];
+    let mut rest = value;
+
+    loop {
+        let (part, next) = match rest.strip_prefix('[') {
+            Some(bracketed) => {
+                let (address, next) = bracketed
+                    .split_once(']')
+                    .ok_or(ForwardMappingArgParsingError::Format)?;
+
+                match next.strip_prefix(':') {
+                    Some(next) => (address, Some(next)),
+                    None if next.is_empty() => (address, None),
+                    None => return Err(ForwardMappingArgParsingError::Format),
+                }
+            }
+            None => match rest.split_once(':') {
+                Some((part, next)) => (part, Some(next)),
+                None => (rest, None),
+            },
+        };
+
+        parts.push(part);
+
+        match next {
+            Some(next) => rest = next,
+            None => return Ok(parts),
+        }
+    }
+}
+
+impl ValueParserFactory for ForwardMappingArg {
+    type Parser = ValueParser;
+
+    fn value_parser() -> Self::Parser {
+        ValueParser::new(|s: &str| ForwardMappingArg::try_from(s).map_err(|e| e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_forward_mapping_with_ipv6_addresses() {
+        let mapping = ForwardMappingArg::try_from("[::1]:5432:[fd00::5]:5433").unwrap();
+
+        assert_eq!(
+            mapping.bind_address,
+            "[::1]:5432".parse::<SocketAddr>().unwrap()
+        );
+        assert_eq!(mapping.host, "fd00::5");
+        assert_eq!(mapping.host_port, 5433);
+
+        let mapping = ForwardMappingArg::try_from("5432:db:5432").unwrap();
+
+        assert_eq!(
+            mapping.bind_address,
+            "127.0.0.1:5432".parse::<SocketAddr>().unwrap()
+        );
+        assert_eq!(mapping.host, "db");
+
+        assert_eq!(
+            ForwardMappingArg::try_from("[::1:5432:db:5432").unwrap_err(),
+            ForwardMappingArgParsingError::Format
+        );
+    }
+}
@@ -1,7 +1,12 @@
 pub mod bom;
 
+pub mod forward_mapping;
+
 mod json_msdate_preserve_formatter;
 
 pub mod lexical_str;
 
 pub use json_msdate_preserve_formatter::*;
+
+#[cfg(test)]
+pub mod test_utils;
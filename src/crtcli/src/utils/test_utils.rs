@@ -0,0 +1,45 @@
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// Unique temporary directory path for tests, removed with all its content on drop (even if the test fails).
+///
+/// The directory itself is not created, so tests can check how the code under test creates it.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new(prefix: &str) -> Self {
+        Self(std::env::temp_dir().join(format!("crtcli-test-{prefix}-{}", uuid::Uuid::new_v4())))
+    }
+
+    /// Same as [`TempDir::new`], but also creates the directory.
+    pub fn create(prefix: &str) -> Self {
+        let dir = Self::new(prefix);
+
+        std::fs::create_dir_all(&dir.0).expect("failed to create test temp dir");
+
+        dir
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = match self.0.is_dir() {
+            true => std::fs::remove_dir_all(&self.0),
+            false => std::fs::remove_file(&self.0),
+        };
+    }
+}